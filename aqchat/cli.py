@@ -0,0 +1,94 @@
+"""Command line tools for aqchat, e.g. to check how files are split:
+
+```
+python cli.py split src/lib.rs --format text
+python cli.py split path/to/repo --max-tokens 512 > chunks.jsonl
+```
+"""
+import argparse
+import json
+import sys
+from pathlib import Path
+from typing import Iterable, List, Optional, TextIO
+from pipelines import HeuristicTokenizer
+from pipelines.chunk import Chunk, SplitFailure, write_jsonl
+from pipelines.detectors import Language
+from pipelines.repo_walker import walk_repo
+from pipelines.split_errors import SplitError
+from pipelines.splitter_builder import SplitterBuilder
+
+def _write_text(chunks: Iterable[Chunk], out: TextIO) -> None:
+    for chunk in chunks:
+        language = chunk.language.value if chunk.language is not None else "text"
+        out.write(f"==> {chunk.location} ({language}) <==\n{chunk.text}\n\n")
+
+def split_command(args: argparse.Namespace, out: TextIO = sys.stdout, err: TextIO = sys.stderr) -> int:
+    """Split a file or every file of a directory, printing the chunks to *out*.
+
+    Returns:
+        The exit status, 1 if any file could not be split.
+    """
+    builder = SplitterBuilder()
+    if args.max_tokens is not None:
+        builder.tokenizer(HeuristicTokenizer()).max_chunk_tokens(args.max_tokens)
+    builder.normalize_newlines(args.normalize_newlines)
+    try:
+        splitter = builder.build()
+    except ValueError as ex:
+        err.write(f"error: {ex}\n")
+        return 2
+
+    language = Language(args.language) if args.language else None
+    path = Path(args.path)
+    failures: List[SplitFailure] = []
+
+    def iter_chunks() -> Iterable[Chunk]:
+        if path.is_dir():
+            files = [(rel_path, path) for rel_path in walk_repo(path)]
+        else:
+            files = [(path, None)]
+
+        for file_path, root in files:
+            try:
+                chunks = splitter.split_path(file_path, root=root, language=language)
+            except SplitError as ex:
+                failures.append(SplitFailure(path=ex.path, error=ex))
+                err.write(f"error: {ex}\n")
+                continue
+            yield from chunks
+
+    # Directories default to JSON Lines, which is written as files are split
+    output_format = args.format or ("jsonl" if path.is_dir() else "json")
+    if output_format == "jsonl":
+        write_jsonl(iter_chunks(), out)
+    elif output_format == "json":
+        json.dump([chunk.to_dict() for chunk in iter_chunks()], out, ensure_ascii=False, indent=2)
+        out.write("\n")
+    else:
+        _write_text(iter_chunks(), out)
+
+    return 1 if failures else 0
+
+def make_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser(prog="aqchat", description="aqchat command line tools")
+    commands = parser.add_subparsers(dest="command", required=True)
+
+    split = commands.add_parser("split", help="split a file or directory and print the chunks")
+    split.add_argument("path", help="a file, or a directory which is walked respecting .gitignore files")
+    split.add_argument("--format", choices=["json", "jsonl", "text"],
+                       help="output format (default: json for files, jsonl for directories)")
+    split.add_argument("--max-tokens", type=int, help="maximum estimated number of tokens per chunk")
+    split.add_argument("--language", choices=[language.value for language in Language],
+                       help="split as this language instead of detecting it")
+    split.add_argument("--normalize-newlines", action="store_true",
+                       help="convert CRLF and CR line endings in chunk text to LF")
+    split.set_defaults(run=split_command)
+
+    return parser
+
+def main(argv: Optional[List[str]] = None) -> int:
+    args = make_parser().parse_args(argv)
+    return args.run(args)
+
+if __name__ == "__main__":
+    sys.exit(main())
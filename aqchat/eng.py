@@ -3,6 +3,7 @@ import streamlit as st
 from gh import extract_repo_name, GitHubRepo
 import settings
 from pipelines import AbstractChatPipeline, AbstractMemoryPipeline, OllamaChatPipeline, CodeMemoryPipeline, TestingChatPipeline
+from pipelines import OpenAIChatProvider, ProviderChatPipeline, ConsoleDiagnostics, LogLevel
 from misc import get_data_dir
 
 @st.cache_resource
@@ -42,7 +43,8 @@ def get_memory_pipeline(repo_name: str) -> AbstractMemoryPipeline:
     memory = CodeMemoryPipeline(
         persist_directory=data_dir / f"chroma/{repo_name}",
         ollama_url=ollama_url,
-        ollama_embedding_model=ollama_embedding_model
+        ollama_embedding_model=ollama_embedding_model,
+        diagnostics=ConsoleDiagnostics(LogLevel.WARNING),
     )
 
     # If the pipeline didn't load the vector store from disk,
@@ -77,7 +79,8 @@ def get_chat_pipeline(repo_name: str) -> AbstractChatPipeline:
     pipeline_setting = os.environ.get('USE_CHAT_PIPELINE', "TESTING")
 
     # If Ollama is specified in the USE_CHAT_PIPELINE environment
-    # variable, then initialize the ollama chat pipeline.
+    # variable, then initialize the ollama chat pipeline. If OpenAI
+    # is specified, answer through an OpenAI-compatible endpoint.
 
     # Otherwise (as in, by default) initialize the testing pipeline.
     # In a development environment, this allows us to test
@@ -95,6 +98,27 @@ def get_chat_pipeline(repo_name: str) -> AbstractChatPipeline:
             ollama_model=ollama_model,
             chat_settings=chat_settings
         )
+    elif pipeline_setting == "OPENAI":
+        # Any OpenAI-compatible endpoint, e.g. a hosted API or a local server
+        # compose passes unset variables as empty strings
+        openai_url = os.environ.get('OPENAI_BASE_URL') or "https://api.openai.com/v1"
+        print(f"[pipeline] connecting to OpenAI-compatible server on {openai_url}")
+
+        openai_model = os.environ.get('OPENAI_MODEL') or "gpt-4o-mini"
+        print(f"[pipeline] using model {openai_model}")
+
+        provider = OpenAIChatProvider(
+            openai_url,
+            openai_model,
+            api_key=os.environ.get('OPENAI_API_KEY') or None,
+        )
+        chat_pipeline = ProviderChatPipeline(
+            provider,
+            memory=memory,
+            chat_settings=chat_settings,
+            # OpenAI-compatible APIs reject Ollama-specific parameters
+            option_keys=("temperature", "top_p"),
+        )
     else:
         chat_pipeline = TestingChatPipeline(memory=memory)
     
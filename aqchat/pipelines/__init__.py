@@ -1,13 +1,76 @@
-from pipelines.abstract_chat import AbstractChatPipeline
+from pipelines.abstract_chat import AbstractChatPipeline, ChatError
 from pipelines.ollama_chat_pipeline import OllamaChatPipeline
 from pipelines.testing_chat_pipeline import TestingChatPipeline
+from pipelines.provider_chat_pipeline import ProviderChatPipeline
+from pipelines.conversation import Conversation
+from pipelines.abstract_chat_provider import AbstractChatProvider, Completion
+from pipelines.openai_chat_provider import OpenAIChatProvider
+from pipelines.ollama_chat_provider import OllamaChatProvider
+from pipelines.testing_chat_provider import TestingChatProvider
+from pipelines.abstract_diagnostics import AbstractDiagnostics, Diagnostic, DiagnosticKind, LogLevel
+from pipelines.null_diagnostics import NullDiagnostics
+from pipelines.console_diagnostics import ConsoleDiagnostics
+from pipelines.testing_diagnostics import TestingDiagnostics
 from pipelines.abstract_memory import AbstractMemoryPipeline
 from pipelines.code_memory_pipeline import CodeMemoryPipeline
+from pipelines.abstract_tokenizer import AbstractTokenizer
+from pipelines.heuristic_tokenizer import HeuristicTokenizer
+from pipelines.abstract_embedder import AbstractEmbedder, EmbedError
+from pipelines.langchain_embedder import LangchainEmbedder
+from pipelines.testing_embedder import TestingEmbedder
+from pipelines.retrying_embedder import RetryingEmbedder
+from pipelines.batching_embedder import BatchingEmbedder
+from pipelines.retry import RetryPolicy
+from pipelines.async_indexer import IndexResult, index_dir_async
+from pipelines.abstract_vector_store import AbstractVectorStore, VectorStoreError
+from pipelines.in_memory_store import InMemoryStore
+from pipelines.search_query import SearchQuery
+from pipelines.qdrant_export import QdrantUploader
+from pipelines.abstract_reranker import AbstractReranker
+from pipelines.lexical_reranker import LexicalReranker
+from pipelines.prompt_template import PromptTemplate
+from pipelines.engine import Answer, Engine
 
 __all__ = [
     "AbstractChatPipeline",
+    "ChatError",
     "OllamaChatPipeline",
     "TestingChatPipeline",
+    "ProviderChatPipeline",
+    "Conversation",
+    "AbstractChatProvider",
+    "Completion",
+    "OpenAIChatProvider",
+    "OllamaChatProvider",
+    "TestingChatProvider",
+    "AbstractDiagnostics",
+    "Diagnostic",
+    "DiagnosticKind",
+    "LogLevel",
+    "NullDiagnostics",
+    "ConsoleDiagnostics",
+    "TestingDiagnostics",
     "AbstractMemoryPipeline",
     "CodeMemoryPipeline",
+    "AbstractTokenizer",
+    "HeuristicTokenizer",
+    "AbstractEmbedder",
+    "EmbedError",
+    "LangchainEmbedder",
+    "TestingEmbedder",
+    "RetryingEmbedder",
+    "BatchingEmbedder",
+    "RetryPolicy",
+    "IndexResult",
+    "index_dir_async",
+    "AbstractVectorStore",
+    "VectorStoreError",
+    "InMemoryStore",
+    "SearchQuery",
+    "QdrantUploader",
+    "AbstractReranker",
+    "LexicalReranker",
+    "PromptTemplate",
+    "Answer",
+    "Engine",
 ]
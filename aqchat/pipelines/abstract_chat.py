@@ -1,8 +1,21 @@
-from typing import Dict, List, Iterator
+from typing import Dict, List, Iterator, Optional
 
 from langchain_core.messages.base import BaseMessageChunk
 from abc import ABC, abstractmethod
 
+class ChatError(Exception):
+    """Raised when a chat completion failed, e.g. because the model server
+    returned an error or the response stream was malformed.
+
+    ``status`` is the HTTP status code of the failed request, if any, and
+    ``retry_after`` the delay in seconds the server asked for before retrying.
+    """
+
+    def __init__(self, message: str, status: Optional[int] = None, retry_after: Optional[float] = None):
+        super().__init__(message)
+        self.status = status
+        self.retry_after = retry_after
+
 class AbstractChatPipeline(ABC):
     """This interface represents an LLM chat pipeline which can be
     queried with a memory interface and a current message list.
@@ -0,0 +1,50 @@
+import asyncio
+from abc import ABC, abstractmethod
+from dataclasses import dataclass
+from typing import Any, Dict, Iterator, List, Optional
+
+@dataclass
+class Completion:
+    """The answer of a chat model to a list of messages.
+
+    ``finish_reason`` is why the model stopped, e.g. ``"stop"`` or
+    ``"length"``, if reported by the provider.
+    """
+
+    text: str
+    model: Optional[str] = None
+    finish_reason: Optional[str] = None
+
+class AbstractChatProvider(ABC):
+    """This interface represents a provider of chat completions, e.g. a local
+    Ollama server or a hosted OpenAI-compatible API.
+
+    ``messages`` are lists of chat messages of the form
+    ``[{"role": "user" | "assistant" | "system", "content": "..."}, ...]``, and
+    ``options`` are generation parameters such as ``{"temperature": 0.2}``,
+    passed to the provider as-is.
+
+    NOTE: Implementers of this class MUST be thread-safe, as it is shared by
+    the chat pipeline, which may be accessed concurrently.
+    """
+
+    @abstractmethod
+    async def complete(self, messages: List[Dict[str, str]], options: Optional[Dict[str, Any]] = None) -> Completion:
+        """Return the answer of the model to *messages*.
+
+        Raises:
+            ChatError: If the completion failed.
+        """
+        pass
+
+    def stream(self, messages: List[Dict[str, str]], options: Optional[Dict[str, Any]] = None) -> Iterator[str]:
+        """Stream the answer of the model to *messages*, yielding pieces of
+        text as they arrive.
+
+        By default the whole completion is yielded at once, providers which
+        support streaming should override this.
+
+        Raises:
+            ChatError: If the completion failed.
+        """
+        yield asyncio.run(self.complete(messages, options)).text
@@ -0,0 +1,52 @@
+from abc import ABC, abstractmethod
+from dataclasses import dataclass
+from enum import Enum
+from typing import Optional
+
+class LogLevel(Enum):
+    """The severity of a diagnostic."""
+
+    INFO = "info"
+    WARNING = "warning"
+    ERROR = "error"
+
+class DiagnosticKind(Enum):
+    """What a diagnostic is about."""
+
+    # A file which could not be read, loaded or split was skipped
+    SKIPPED_FILE = "skipped_file"
+
+    # A file without a boundary detector was split with the fallback splitter
+    PARSE_FALLBACK = "parse_fallback"
+
+    # Part of a malformed file could not be parsed and was split as plain code
+    PARTIAL_PARSE = "partial_parse"
+
+    # A code unit exceeding the chunk size limit was subdivided
+    OVERSIZED_CHUNK = "oversized_chunk"
+
+    # Any other event, e.g. a vector store which could not be loaded
+    OTHER = "other"
+
+@dataclass(frozen=True)
+class Diagnostic:
+    """An event of splitting or indexing, e.g. a skipped file, concerning the
+    file at ``path`` if known."""
+
+    level: LogLevel
+    kind: DiagnosticKind
+    message: str
+    path: Optional[str] = None
+
+class AbstractDiagnostics(ABC):
+    """This interface represents a sink for the diagnostics of splitting and
+    indexing, e.g. to log them or to collect them for display.
+
+    NOTE: Implementers of this class MUST be thread-safe, as diagnostics are
+    emitted from worker threads when splitting files in parallel.
+    """
+
+    @abstractmethod
+    def emit(self, diagnostic: Diagnostic) -> None:
+        """Handle *diagnostic*, which must not raise."""
+        pass
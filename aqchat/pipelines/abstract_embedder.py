@@ -0,0 +1,48 @@
+from abc import ABC, abstractmethod
+from typing import List, Optional, Sequence
+from pipelines.chunk import Chunk
+
+class EmbedError(Exception):
+    """Raised when texts could not be embedded, e.g. because the embedding
+    model could not be reached.
+
+    ``status`` is the HTTP status code of the failed request, if any, and
+    ``retry_after`` the delay in seconds the server asked for before retrying.
+    """
+
+    def __init__(self, message: str, status: Optional[int] = None, retry_after: Optional[float] = None):
+        super().__init__(message)
+        self.status = status
+        self.retry_after = retry_after
+
+class AbstractEmbedder(ABC):
+    """This interface represents an embedding model which turns texts
+    into vectors, e.g. a local Ollama model or a hosted API.
+
+    NOTE: Implementers of this class MUST be thread-safe, as it is shared by
+    the memory pipeline, which may be accessed concurrently.
+    """
+
+    @abstractmethod
+    async def embed(self, texts: List[str]) -> List[List[float]]:
+        """Embed *texts*, returning one vector per text in the same order.
+
+        Raises:
+            EmbedError: If the texts could not be embedded.
+        """
+        pass
+
+    async def embed_chunks(self, chunks: Sequence[Chunk]) -> List[List[float]]:
+        """Embed the ``embedding_text`` of each of *chunks*.
+
+        Raises:
+            EmbedError: If the chunks could not be embedded, or the embedding
+            model returned the wrong number of vectors.
+        """
+        if not chunks:
+            return []
+
+        vectors = await self.embed([chunk.embedding_text for chunk in chunks])
+        if len(vectors) != len(chunks):
+            raise EmbedError(f"Expected {len(chunks)} vectors, got {len(vectors)}")
+        return vectors
@@ -0,0 +1,23 @@
+from abc import ABC, abstractmethod
+from typing import List, Sequence, Tuple
+from pipelines.chunk import Chunk
+
+class AbstractReranker(ABC):
+    """This interface represents a reranker which scores retrieved candidate
+    chunks by their relevance to a query, e.g. a lexical scorer or a
+    cross-encoder model.
+
+    NOTE: Implementers of this class MUST be thread-safe, as it is shared by
+    the retrieval pipeline, which may be accessed concurrently.
+    """
+
+    @abstractmethod
+    async def rerank(self, query: str, candidates: Sequence[Chunk]) -> List[Tuple[int, float]]:
+        """Score *candidates* for *query*.
+
+        Returns:
+            ``(index, score)`` tuples with indices into *candidates*, best first.
+            Candidates with equal scores should keep their order in
+            *candidates*, so that results are deterministic.
+        """
+        pass
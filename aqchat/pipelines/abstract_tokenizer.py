@@ -0,0 +1,19 @@
+from abc import ABC, abstractmethod
+from typing import Hashable, Optional
+
+class AbstractTokenizer(ABC):
+    """This interface represents a tokenizer which is used to measure
+    the size of chunks in tokens, e.g. the tokenizer of the embedding model.
+    """
+
+    @abstractmethod
+    def count_tokens(self, text: str) -> int:
+        """Return the number of tokens in *text*."""
+        pass
+
+    def cache_key(self) -> Optional[Hashable]:
+        """Return a key of the tokenizer and its settings, which is equal for
+        tokenizers counting the same tokens, so that chunks split with it can
+        be cached, or None if it has no such key. Splitters don't use their
+        ``parse_cache`` with tokenizers without a key."""
+        return None
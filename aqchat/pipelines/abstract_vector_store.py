@@ -0,0 +1,93 @@
+from abc import ABC, abstractmethod
+from typing import Callable, List, Optional, Sequence, Tuple
+from pipelines.chunk import Chunk
+from pipelines.search_query import SearchQuery
+
+class VectorStoreError(Exception):
+    """Raised when a vector store operation is invalid, e.g. because a vector
+    has the wrong dimension."""
+
+class AbstractVectorStore(ABC):
+    """This interface represents a store of chunks and their embedding vectors
+    which can be searched for the chunks most similar to a query vector.
+
+    All vectors of a store have the same dimension, which is recorded when the
+    first vector is added unless it was configured. Vectors and queries of
+    another dimension are rejected, e.g. after switching embedding models, as
+    their scores would be meaningless. Switching models therefore requires an
+    explicit ``clear`` or ``migrate``.
+
+    NOTE: Implementers of this class MUST be thread-safe, as it is shared by
+    the memory pipeline, which may be accessed concurrently.
+    """
+
+    @abstractmethod
+    def add(self, chunks: Sequence[Chunk], vectors: Sequence[Sequence[float]]) -> None:
+        """Add *chunks* with their embedding *vectors*, one vector per chunk.
+
+        Raises:
+            VectorStoreError: If the number of vectors doesn't match the number
+            of chunks, or a vector's dimension doesn't match the store's.
+        """
+        pass
+
+    @abstractmethod
+    def search(
+        self,
+        query: Sequence[float],
+        k: int,
+        where: Optional[Callable[[Chunk], bool]] = None,
+        min_score: Optional[float] = None,
+    ) -> List[Tuple[Chunk, float]]:
+        """Return up to *k* chunks most similar to the *query* vector with their
+        scores, best first.
+
+        Scores are the cosine similarity of the vectors, from -1 to 1, where
+        higher is more similar. For normalized vectors of a typical embedding
+        model, scores range from 0 (unrelated) to 1 (identical direction).
+
+        If *min_score* is given, chunks scoring below it are left out, even if
+        fewer than *k* chunks remain, so the result is empty if none reach it.
+
+        If *where* is given, only chunks for which it returns True are
+        considered. They are filtered before the top *k* are selected, so up to
+        *k* matching chunks are returned even if better chunks don't match.
+
+        Chunks with equal scores are ordered by ``Chunk.id``, so that results
+        don't depend on the order the chunks were added in.
+
+        Raises:
+            VectorStoreError: If the query's dimension doesn't match the store's.
+        """
+        pass
+
+    @abstractmethod
+    def clear(self, dimension: Optional[int] = None) -> None:
+        """Remove all chunks, and set the dimension of the store to *dimension*,
+        or if not given, record it again from the first vector added."""
+        pass
+
+    def migrate(self, chunks: Sequence[Chunk], vectors: Sequence[Sequence[float]]) -> None:
+        """Replace all chunks of the store with *chunks* and their *vectors*,
+        whose dimension becomes the store's, e.g. after re-embedding the chunks
+        with another model.
+
+        Raises:
+            VectorStoreError: If the number of vectors doesn't match the number
+            of chunks, or the vectors have different dimensions.
+        """
+        self.clear()
+        self.add(chunks, vectors)
+
+    def search_query(self, query: SearchQuery) -> List[Tuple[Chunk, float]]:
+        """Return the chunks matching *query*, see ``search``."""
+        return self.search(
+            query.vector, query.top_k,
+            where=query.matches if query.has_filters else None,
+            min_score=query.score_threshold,
+        )
+
+    @abstractmethod
+    def __len__(self) -> int:
+        """Return the number of chunks in the store."""
+        pass
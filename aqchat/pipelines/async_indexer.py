@@ -0,0 +1,117 @@
+import asyncio
+import os
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Iterable, List, Optional
+from pipelines.abstract_embedder import AbstractEmbedder
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk, SplitFailure
+from pipelines.progress import EmbeddingBatch, FileChunked, FileFailed, FileStarted, Finished, ProgressCallback, report
+from pipelines.repo_walker import path_sort_key, walk_repo
+from pipelines.split_errors import SplitError
+
+@dataclass
+class IndexResult:
+    """The chunks of a directory with their embedding vectors, where
+    ``vectors[i]`` is the vector of ``chunks[i]``, and the files which could
+    not be split."""
+
+    chunks: List[Chunk] = field(default_factory=list)
+    vectors: List[List[float]] = field(default_factory=list)
+    failures: List[SplitFailure] = field(default_factory=list)
+
+async def index_dir_async(
+    splitter: CodeBoundaryTextSplitter,
+    root: str | os.PathLike,
+    embedder: AbstractEmbedder,
+    include: Optional[Iterable[str]] = None,
+    exclude: Optional[Iterable[str]] = None,
+    *,
+    max_open_files: int = 16,
+    batch_size: int = 64,
+    progress: Optional[ProgressCallback] = None,
+) -> IndexResult:
+    """Split and embed every file in the repository at *root* with
+    *splitter* and *embedder*, overlapping file IO, parsing and embedding.
+
+    Files are read concurrently, at most *max_open_files* at a time, and split
+    on worker threads so that parsing doesn't block the event loop. Chunks are
+    passed to the embedder in batches of *batch_size* as soon as enough of
+    them were split, while other files are still being read and split; wrap
+    the embedder in a ``BatchingEmbedder`` to embed a batch in several
+    requests at once. An ``EmbedError`` stops the indexing.
+
+    The files are walked and read as in ``CodeBoundaryTextSplitter.index_dir``,
+    i.e. large files are memory-mapped according to ``mmap_min_bytes``. If
+    *progress* is given, it is called with the events of each file from the
+    thread splitting it, so it must be thread-safe, with an
+    ``EmbeddingBatch`` after each batch and finally with ``Finished``.
+
+    Returns:
+        The chunks with their vectors and the failures in the order of
+        ``par_index_dir``, i.e. with the files sorted by path and the chunks of
+        each file in source order. The order doesn't depend on scheduling.
+    """
+    if max_open_files < 1:
+        raise ValueError("max_open_files must be at least 1")
+    if batch_size < 1:
+        raise ValueError("batch_size must be at least 1")
+
+    paths = await asyncio.to_thread(lambda: list(walk_repo(root, include=include, exclude=exclude)))
+    open_files = asyncio.Semaphore(max_open_files)
+    batches: asyncio.Queue = asyncio.Queue()
+    pending: List[Chunk] = []
+    failures: List[SplitFailure] = []
+    embedded: List[Chunk] = []
+    vectors: List[List[float]] = []
+    split_count = 0
+
+    def queue_batches(final: bool = False) -> None:
+        while len(pending) >= batch_size or (final and pending):
+            batches.put_nowait(pending[:batch_size])
+            del pending[:batch_size]
+
+    async def split(path: Path) -> None:
+        nonlocal split_count
+        report(progress, FileStarted(path.as_posix()))
+        try:
+            async with open_files:
+                contents = await asyncio.to_thread(splitter.read_path, path, root=root)
+            chunks = await asyncio.to_thread(splitter.split_file, path, contents)
+        except SplitError as ex:
+            splitter.emit_skipped(ex)
+            report(progress, FileFailed(ex.path, ex))
+            failures.append(SplitFailure(path=ex.path, error=ex))
+            return
+
+        report(progress, FileChunked(path.as_posix(), len(chunks)))
+        split_count += len(chunks)
+        pending.extend(chunks)
+        queue_batches()
+
+    async def split_all() -> None:
+        try:
+            await asyncio.gather(*(split(path) for path in paths))
+            queue_batches(final=True)
+        finally:
+            batches.put_nowait(None)
+
+    async def embed_all() -> None:
+        while (batch := await batches.get()) is not None:
+            vectors.extend(await embedder.embed_chunks(batch))
+            embedded.extend(batch)
+            report(progress, EmbeddingBatch(len(embedded), split_count))
+
+    await asyncio.gather(split_all(), embed_all())
+
+    # Chunks are split and embedded in the order the files are read, so sort
+    # them by file to get the same result for every run. The chunks of a file
+    # are queued at once, so the stable sort keeps them in source order.
+    order = sorted(range(len(embedded)), key=lambda i: path_sort_key(embedded[i].path))
+    failures.sort(key=lambda failure: path_sort_key(failure.path))
+    report(progress, Finished(len(paths), len(embedded), len(failures)))
+    return IndexResult(
+        chunks=[embedded[i] for i in order],
+        vectors=[vectors[i] for i in order],
+        failures=failures,
+    )
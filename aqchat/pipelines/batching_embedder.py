@@ -0,0 +1,75 @@
+import asyncio
+from typing import List, Optional
+from pipelines.abstract_embedder import AbstractEmbedder, EmbedError
+from pipelines.abstract_tokenizer import AbstractTokenizer
+from pipelines.heuristic_tokenizer import HeuristicTokenizer
+
+class BatchingEmbedder(AbstractEmbedder):
+    """Embedder which groups texts into batches for another embedder, so that
+    many texts are embedded with few requests.
+
+    Args:
+        embedder: The embedder the batches are passed to.
+        batch_size: The maximum number of texts per batch.
+        max_batch_tokens: The maximum total number of tokens per batch, e.g.
+        the provider's per-request cap. A single text exceeding it is sent
+        in a batch of its own.
+        tokenizer: Counts the tokens of texts, by default ``HeuristicTokenizer``.
+        max_concurrency: The maximum number of batches embedded at the same time.
+    """
+
+    def __init__(
+        self,
+        embedder: AbstractEmbedder,
+        batch_size: int = 64,
+        *,
+        max_batch_tokens: Optional[int] = None,
+        tokenizer: Optional[AbstractTokenizer] = None,
+        max_concurrency: int = 1,
+    ):
+        if batch_size < 1:
+            raise ValueError("batch_size must be at least 1")
+        if max_concurrency < 1:
+            raise ValueError("max_concurrency must be at least 1")
+
+        self.embedder = embedder
+        self.batch_size = batch_size
+        self.max_batch_tokens = max_batch_tokens
+        self.tokenizer = tokenizer or HeuristicTokenizer()
+        self.max_concurrency = max_concurrency
+
+    def batches(self, texts: List[str]) -> List[range]:
+        """Return the ranges of indices of *texts* which form the batches."""
+        batches = []
+        start = 0
+        tokens = 0
+        for i, text in enumerate(texts):
+            text_tokens = self.tokenizer.count_tokens(text) if self.max_batch_tokens is not None else 0
+            full = i - start == self.batch_size
+            over_budget = self.max_batch_tokens is not None and i > start and tokens + text_tokens > self.max_batch_tokens
+            if full or over_budget:
+                batches.append(range(start, i))
+                start = i
+                tokens = 0
+            tokens += text_tokens
+
+        if start < len(texts):
+            batches.append(range(start, len(texts)))
+        return batches
+
+    async def embed(self, texts: List[str]) -> List[List[float]]:
+        semaphore = asyncio.Semaphore(self.max_concurrency)
+        vectors: List[Optional[List[float]]] = [None] * len(texts)
+
+        async def embed_batch(batch: range) -> None:
+            async with semaphore:
+                batch_vectors = await self.embedder.embed([texts[i] for i in batch])
+            if len(batch_vectors) != len(batch):
+                raise EmbedError(f"Expected {len(batch)} vectors, got {len(batch_vectors)}")
+
+            # Batches may finish in any order, so place the vectors by index
+            for i, vector in zip(batch, batch_vectors):
+                vectors[i] = vector
+
+        await asyncio.gather(*(embed_batch(batch) for batch in self.batches(texts)))
+        return vectors
@@ -1,11 +1,75 @@
+import codecs
+import mmap
 import os
-from typing import List, Dict, Any, Optional, Iterable
+from bisect import bisect_left, bisect_right
+from concurrent.futures import ThreadPoolExecutor
+from enum import Enum
+from pathlib import Path
+from typing import BinaryIO, List, Dict, Any, Optional, Iterable, Iterator, Set, Tuple
 from langchain.text_splitter import TextSplitter
 from langchain.docstore.document import Document
-from pipelines.detectors import CodeBoundaryDetector
+from pipelines.abstract_diagnostics import AbstractDiagnostics, Diagnostic, DiagnosticKind, LogLevel
+from pipelines.abstract_tokenizer import AbstractTokenizer
+from pipelines.detectors import CodeBoundaryDetector, GlueRules, Language
+from pipelines.chunk import Chunk, ChunkKind, SplitFailure
+from pipelines.doc_comments import parse_doc_comment
+from pipelines.null_diagnostics import NullDiagnostics
+from pipelines.parse_cache import ParseCache
+from pipelines.progress import FileChunked, FileFailed, FileStarted, Finished, ProgressCallback, report
+from pipelines.recursive_split import recursive_split
+from pipelines.reindex import FileStatus, ReindexResult, content_hash
+from pipelines.repo_walker import path_sort_key, walk_repo
+from pipelines.split_errors import (
+    SplitEncodingError,
+    SplitError,
+    SplitIoError,
+    SplitParseError,
+    UnsupportedLanguageError,
+)
+from pipelines.split_report import SplitReport
 from langchain_text_splitters.character import RecursiveCharacterTextSplitter
 from langchain_text_splitters import TextSplitter
 
+# Separators at which a line exceeding the size limit is cut, see ``recursive_split``
+_LINE_SEPARATORS = (". ", " ")
+
+# Size of the regions of a memory-mapped file which are decoded at a time
+_MMAP_REGION_BYTES = 1 << 20
+
+# Size of the reads from a reader passed to ``split_reader``
+_READ_BYTES = 1 << 16
+
+# Byte order mark, which is not part of the text of a file
+_BOM = '\ufeff'
+_BOM_BYTES = len(_BOM.encode('utf-8'))
+
+def _decode_utf8(path: str, regions: Iterable[bytes]) -> str:
+    """Decode the UTF-8 encoded file at *path* from consecutive *regions*,
+    which may split characters anywhere.
+
+    Raises:
+        SplitEncodingError: If the file is not valid UTF-8.
+    """
+    decoder = codecs.getincrementaldecoder('utf-8')()
+    parts = []
+    offset = 0
+    for region in regions:
+        # Bytes of a character cut at the end of the previous region
+        pending = len(decoder.getstate()[0])
+        try:
+            parts.append(decoder.decode(region))
+        except UnicodeDecodeError as ex:
+            raise SplitEncodingError(path, f"is not valid UTF-8: {ex.reason} at byte {offset - pending + ex.start}") from ex
+        offset += len(region)
+
+    pending = len(decoder.getstate()[0])
+    try:
+        parts.append(decoder.decode(b'', final=True))
+    except UnicodeDecodeError as ex:
+        raise SplitEncodingError(path, f"is not valid UTF-8: {ex.reason} at byte {offset - pending + ex.start}") from ex
+
+    return ''.join(parts)
+
 def _get_extension_from_path(path: str):
     """
     Extracts the file extension from a file path.
@@ -18,6 +82,51 @@ def _get_extension_from_path(path: str):
         return filename[dot_index:]
     return None
 
+class FallbackMode(Enum):
+    """How text without a boundary detector (e.g. of an unsupported language) is split."""
+
+    # Split with the Langchain recursive text splitter
+    TEXT_SPLITTER = "text_splitter"
+
+    # Split into chunks of up to the size limit, cutting between paragraphs
+    # where possible, otherwise between lines
+    LINES = "lines"
+
+    # Don't split, but raise UnsupportedLanguageError from the file-oriented
+    # methods, e.g. to only index files of supported languages
+    ERROR = "error"
+
+def _merge_primary(a: Chunk, b: Chunk) -> Chunk:
+    """Return which of chunks *a* and *b* determines the kind and name of the
+    chunk merged from them, e.g. a comment merged with the function it precedes
+    is that function."""
+    if a.kind.is_definition:
+        return a
+    if b.kind.is_definition:
+        return b
+
+    ranks = [ChunkKind.COMMENT, ChunkKind.IMPORT, ChunkKind.OTHER]
+    return max(a, b, key=lambda chunk: ranks.index(chunk.kind))
+
+def _normalize_newlines(text: str) -> Tuple[str, List[int]]:
+    """Convert the CRLF and lone CR line endings of *text* to LF.
+
+    Returns:
+        The normalized text, and the sorted byte offsets in its UTF-8 encoding
+        of the LFs which were preceded by a CR that was removed. A byte offset
+        in the normalized text plus the number of such LFs before it is the
+        corresponding offset in the original text.
+    """
+    removed = []
+    offset = 0
+
+    for line in text.split('\r\n')[:-1]:
+        offset += len(line.encode('utf-8'))
+        removed.append(offset)
+        offset += 1
+
+    return text.replace('\r\n', '\n').replace('\r', '\n'), removed
+
 class CodeBoundaryTextSplitter(TextSplitter):
     """
     This class splits code files based on language-specific boundaries (classes, functions, etc.).
@@ -35,6 +144,18 @@ class CodeBoundaryTextSplitter(TextSplitter):
         keep_separator: bool = True,
         add_start_index: bool = False,
         strip_whitespace: bool = True,
+        max_chunk_bytes: Optional[int] = None,
+        min_chunk_bytes: int = 0,
+        overlap_lines: int = 0,
+        fallback: FallbackMode = FallbackMode.TEXT_SPLITTER,
+        tokenizer: Optional[AbstractTokenizer] = None,
+        max_chunk_tokens: Optional[int] = None,
+        language: Optional[Language] = None,
+        normalize_newlines: bool = False,
+        parse_cache: Optional[ParseCache] = None,
+        mmap_min_bytes: Optional[int] = None,
+        diagnostics: Optional[AbstractDiagnostics] = None,
+        glue_rules: Optional[Dict[Language, GlueRules]] = None,
     ):
         """
         Initialize the CodeBoundaryTextSplitter.
@@ -47,8 +168,45 @@ class CodeBoundaryTextSplitter(TextSplitter):
             keep_separator: Whether to keep boundary separators
             add_start_index: Whether to add start index to metadata
             strip_whitespace: Whether to strip whitespace from chunks
-            default_splitter: This is used as a splitter for raw text documents,
-            as well as any extensions for which there is no available boundary detector.
+            max_chunk_bytes: Maximum size of each code chunk in UTF-8 bytes. If not
+            given, code chunks are limited to `chunk_size` as measured by
+            `length_function` (by default, 4000 characters). A code unit exceeding
+            the limit is subdivided at statement boundaries where possible.
+            min_chunk_bytes: Chunks smaller than this many UTF-8 bytes are merged
+            with an adjacent chunk, preferably the following one. Disabled by default.
+            overlap_lines: When a code unit is subdivided, each piece after the first
+            is prefixed with up to this many lines from the end of the previous piece,
+            as far as the size limit allows. Disabled by default.
+            tokenizer: Tokenizer used to measure chunks for `max_chunk_tokens`,
+            e.g. the tokenizer of the embedding model. `HeuristicTokenizer` can be
+            used if no real tokenizer is available.
+            max_chunk_tokens: Maximum size of each code chunk in tokens, as counted
+            by `tokenizer`. This takes precedence over `max_chunk_bytes`, but only
+            if a tokenizer is given; otherwise the byte-based limit is used.
+            fallback: How to split raw text documents, as well as any extensions
+            for which there is no available boundary detector. By default, the
+            Langchain recursive text splitter is used.
+            language: Language used when splitting text without a boundary detector
+            or language, including files whose language could not be detected.
+            normalize_newlines: Whether to convert CRLF and lone CR line endings
+            to LF in the text of chunks. Byte offsets and line numbers still refer
+            to the original text. Disabled by default.
+            parse_cache: Cache of the chunks of previously split texts, which
+            is used to skip splitting a text with the same content, language
+            and settings again. The cache may be shared with other splitters.
+            It is not used with a tokenizer without a ``cache_key``. No cache
+            is used by default.
+            mmap_min_bytes: Files of at least this many bytes are memory-mapped
+            and decoded one region at a time, instead of being read into memory
+            first, which reduces the peak memory use of splitting very large
+            files. Disabled by default.
+            diagnostics: Sink for diagnostics such as skipped files, files split
+            with the fallback splitter and subdivided code units. By default,
+            diagnostics are discarded.
+            glue_rules: Rules for attaching leading comments and attributes to
+            definitions, per language. Languages which are not given use their
+            ``default_glue_rules``. Only applies when the boundary detector is
+            created from the language, not to a given boundary detector.
         """
         super().__init__(
             chunk_size=chunk_size,
@@ -58,78 +216,1034 @@ class CodeBoundaryTextSplitter(TextSplitter):
             add_start_index=add_start_index,
             strip_whitespace=strip_whitespace,
         )
+        self.max_chunk_bytes = max_chunk_bytes
+        self.min_chunk_bytes = min_chunk_bytes
+        self.overlap_lines = overlap_lines
+        self.fallback = fallback
+        self.tokenizer = tokenizer
+        self.max_chunk_tokens = max_chunk_tokens
+        self.language = language
+        self.normalize_newlines = normalize_newlines
+        self.parse_cache = parse_cache
+        self.mmap_min_bytes = mmap_min_bytes
+        self.diagnostics = diagnostics if diagnostics is not None else NullDiagnostics()
+        self.glue_rules = dict(glue_rules or {})
         self.default_splitter = RecursiveCharacterTextSplitter()
     
-    def split_text(self, text: str, *, boundary_detector: CodeBoundaryDetector = None) -> List[str]:
-        """Split text based on code boundaries."""
+    def split_text(self, text: str, *, boundary_detector: CodeBoundaryDetector = None, language: Language = None) -> List[str]:
+        """Split text based on code boundaries.
+
+        Either a boundary detector or a language may be supplied. If only a
+        language is given, the default boundary detector for that language is used.
+        """
+        chunks = self.split_chunks(text, boundary_detector=boundary_detector, language=language)
+        return [chunk.text for chunk in chunks]
+
+    def split_chunks(
+        self,
+        text: str,
+        *,
+        boundary_detector: CodeBoundaryDetector = None,
+        language: Language = None,
+        path: Optional[str] = None,
+    ) -> List[Chunk]:
+        """Split text based on code boundaries, returning chunks with their
+        byte offsets and line ranges in *text*.
+
+        See ``split_text`` for a description of the arguments. *path* is the
+        path of the file of *text*, if any, which is used in diagnostics.
+        """
+        return list(self.split_iter(text, boundary_detector=boundary_detector, language=language, path=path))
+
+    def split_file(self, path: str | os.PathLike, contents: str, *, language: Optional[Language] = None) -> List[Chunk]:
+        """Split the contents of a file as *language*, or if not given, detecting
+        its language from *path* and *contents* with ``Language.detect``.
+
+        Files of an unsupported language are split according to `fallback`.
+        The resulting chunks record *path* as their ``path``.
+
+        A byte order mark at the start of *contents* is not part of any chunk,
+        but is counted by the byte offsets of the chunks, so that they still
+        index into the file.
+
+        Raises:
+            UnsupportedLanguageError: If the language is not supported and
+            `fallback` is ``FallbackMode.ERROR``.
+            SplitParseError: If splitting the contents failed.
+        """
+        path = Path(path).as_posix()
+        offset = 0
+        if contents.startswith(_BOM):
+            contents = contents[len(_BOM):]
+            offset = _BOM_BYTES
+
+        if language is None:
+            language = Language.detect(path, contents)
+        if language is None and self.language is None:
+            if self.fallback == FallbackMode.ERROR:
+                raise UnsupportedLanguageError(path, "language is not supported")
+            self._emit(LogLevel.INFO, DiagnosticKind.PARSE_FALLBACK,
+                       f"Splitting file of unsupported language with the fallback splitter: {path}", path)
+
+        try:
+            chunks = self.split_chunks(contents, language=language, path=path)
+        except Exception as ex:
+            raise SplitParseError(path, f"could not be split: {ex}") from ex
+
+        for chunk in chunks:
+            chunk.path = path
+            chunk.start_byte += offset
+            chunk.end_byte += offset
+        return chunks
+
+    def split_bytes(self, path: str | os.PathLike, data: bytes, *, language: Optional[Language] = None) -> List[Chunk]:
+        """Split the UTF-8 encoded contents of the file at *path*, e.g. a file
+        which was not read from disk, see ``split_file``.
+
+        Raises:
+            SplitEncodingError: If *data* is not valid UTF-8.
+            UnsupportedLanguageError, SplitParseError: See ``split_file``.
+        """
+        return self._split_data(path, data, language)
+
+    def split_reader(self, path: str | os.PathLike, reader: BinaryIO, *, language: Optional[Language] = None) -> List[Chunk]:
+        """Read the UTF-8 encoded contents of the file at *path* from the
+        binary *reader*, e.g. a stream from object storage, and split them,
+        see ``split_file``.
+
+        The contents are decoded while they are read, so invalid UTF-8 is
+        reported without reading the rest, but the whole text is kept in
+        memory, since splitting by structure needs all of it.
+
+        Raises:
+            SplitIoError: If reading from *reader* failed.
+            SplitEncodingError: If the contents are not valid UTF-8.
+            UnsupportedLanguageError, SplitParseError: See ``split_file``.
+        """
+        path = Path(path).as_posix()
+        try:
+            contents = _decode_utf8(path, iter(lambda: reader.read(_READ_BYTES), b''))
+        except OSError as ex:
+            raise SplitIoError(path, f"could not be read: {ex}") from ex
+
+        return self.split_file(path, contents, language=language)
+
+    def split_path(
+        self,
+        path: str | os.PathLike,
+        *,
+        root: Optional[str | os.PathLike] = None,
+        language: Optional[Language] = None,
+    ) -> List[Chunk]:
+        """Read and split the file at *path*, relative to *root* if given, as
+        *language* or its detected language, see ``split_file``.
+
+        The chunks record *path* as their ``path``.
+
+        Raises:
+            SplitIoError: If the file could not be read.
+            SplitEncodingError: If the file is not valid UTF-8.
+            UnsupportedLanguageError, SplitParseError: See ``split_file``.
+        """
+        return self.split_file(path, self.read_path(path, root=root), language=language)
+
+    def read_path(self, path: str | os.PathLike, *, root: Optional[str | os.PathLike] = None) -> str:
+        """Read and decode the file at *path*, relative to *root* if given, as
+        ``split_path`` does, memory-mapping it if it has at least
+        ``mmap_min_bytes`` bytes.
+
+        Raises:
+            SplitIoError: If the file could not be read.
+            SplitEncodingError: If the file is not valid UTF-8.
+        """
+        if self.mmap_min_bytes is not None:
+            contents = self._read_mapped(path, root)
+            if contents is not None:
+                return contents
+
+        return _decode_utf8(Path(path).as_posix(), [self._read_path(path, root)])
+
+    def _read_mapped(self, path: str | os.PathLike, root: Optional[str | os.PathLike]) -> Optional[str]:
+        """Read the file at *path* by memory-mapping it, decoding it one region
+        at a time, or return None if it is smaller than ``mmap_min_bytes``.
+
+        Raises:
+            SplitIoError: If the file could not be read.
+            SplitEncodingError: If the file is not valid UTF-8.
+        """
+        path = Path(path)
+        try:
+            with open(Path(root) / path if root is not None else path, 'rb') as file:
+                size = os.fstat(file.fileno()).st_size
+                if size < self.mmap_min_bytes or size == 0:
+                    return None
+
+                with mmap.mmap(file.fileno(), 0, access=mmap.ACCESS_READ) as mapped:
+                    regions = (mapped[offset:offset + _MMAP_REGION_BYTES] for offset in range(0, size, _MMAP_REGION_BYTES))
+                    return _decode_utf8(path.as_posix(), regions)
+        except OSError as ex:
+            raise SplitIoError(path.as_posix(), f"could not be read: {ex}") from ex
+
+    def _emit(self, level: LogLevel, kind: DiagnosticKind, message: str, path: Optional[str] = None) -> None:
+        self.diagnostics.emit(Diagnostic(level, kind, message, path))
+
+    def emit_skipped(self, ex: SplitError) -> None:
+        """Emit the diagnostic of skipping a file which could not be split
+        because of *ex*, as the indexing methods do."""
+        self._emit(LogLevel.WARNING, DiagnosticKind.SKIPPED_FILE, f"Skipping file which could not be split: {ex}", ex.path)
+
+    def _read_path(self, path: str | os.PathLike, root: Optional[str | os.PathLike]) -> bytes:
+        path = Path(path)
+        try:
+            return (Path(root) / path if root is not None else path).read_bytes()
+        except OSError as ex:
+            raise SplitIoError(path.as_posix(), f"could not be read: {ex}") from ex
+
+    def _split_path_reporting(
+        self,
+        path: str | os.PathLike,
+        root: Optional[str | os.PathLike],
+        progress: Optional[ProgressCallback],
+    ) -> List[Chunk]:
+        """``split_path``, reporting the file's progress events to *progress*."""
+        report(progress, FileStarted(Path(path).as_posix()))
+        try:
+            chunks = self.split_path(path, root=root)
+        except SplitError as ex:
+            self.emit_skipped(ex)
+            report(progress, FileFailed(ex.path, ex))
+            raise
+
+        report(progress, FileChunked(Path(path).as_posix(), len(chunks)))
+        return chunks
+
+    def _split_data(self, path: str | os.PathLike, data: bytes, language: Optional[Language] = None) -> List[Chunk]:
+        return self.split_file(path, _decode_utf8(Path(path).as_posix(), [data]), language=language)
+
+    def index_dir(
+        self,
+        root: str | os.PathLike,
+        include: Optional[Iterable[str]] = None,
+        exclude: Optional[Iterable[str]] = None,
+        *,
+        failures: Optional[List[SplitFailure]] = None,
+        progress: Optional[ProgressCallback] = None,
+    ) -> Iterator[Chunk]:
+        """Lazily split every file in the repository at *root*, yielding
+        chunks with their ``path`` relative to *root*.
+
+        Files ignored by `.gitignore` or `.git/info/exclude`, the `.git`
+        directory and binary files are skipped. *include* and *exclude* are
+        globs over relative paths which further restrict the files, see
+        ``walk_repo``.
+
+        Files which can't be split are skipped as well, emitting a diagnostic.
+        If a *failures* list is given, each of them is appended to it.
+
+        Files are split in sorted path order (see ``path_sort_key``), and the
+        chunks of each file are yielded in source order, so the order is the
+        same for every run.
+
+        If *progress* is given, it is called with the events of each file and
+        finally ``Finished`` once all files were split.
+        """
+        file_count = chunk_count = failure_count = 0
+
+        for rel_path in walk_repo(root, include=include, exclude=exclude):
+            file_count += 1
+            try:
+                chunks = self._split_path_reporting(rel_path, root, progress)
+            except SplitError as ex:
+                failure_count += 1
+                if failures is not None:
+                    failures.append(SplitFailure(path=ex.path, error=ex))
+                continue
+
+            chunk_count += len(chunks)
+            yield from chunks
+
+        report(progress, Finished(file_count, chunk_count, failure_count))
+
+    def reindex_dir(
+        self,
+        root: str | os.PathLike,
+        previous_hashes: Dict[str, str],
+        include: Optional[Iterable[str]] = None,
+        exclude: Optional[Iterable[str]] = None,
+    ) -> ReindexResult:
+        """Incrementally reindex the repository at *root*, only splitting the
+        files which were added or modified since the last index.
+
+        Args:
+            root: The root directory of the repository, walked as in ``index_dir``.
+            previous_hashes: The ``hashes`` of the previous ``ReindexResult``,
+            i.e. a dict mapping relative paths to content hashes. Pass an empty
+            dict to index everything.
+            include: See ``index_dir``.
+            exclude: See ``index_dir``.
+        """
+        result = ReindexResult()
+
+        for rel_path in walk_repo(root, include=include, exclude=exclude):
+            path = rel_path.as_posix()
+            try:
+                data = self._read_path(rel_path, root)
+            except SplitError as ex:
+                self.emit_skipped(ex)
+                result.failures.append(SplitFailure(path=path, error=ex))
+                continue
+
+            # Compare contents rather than modification times, so that e.g.
+            # switching branches back and forth doesn't reindex anything
+            file_hash = content_hash(data)
+            previous_hash = previous_hashes.get(path)
+            if previous_hash is None:
+                result.statuses[path] = FileStatus.ADDED
+            elif previous_hash != file_hash:
+                result.statuses[path] = FileStatus.MODIFIED
+            else:
+                result.statuses[path] = FileStatus.UNCHANGED
+                result.hashes[path] = file_hash
+                continue
+
+            try:
+                result.chunks.extend(self._split_data(rel_path, data))
+            except SplitError as ex:
+                self.emit_skipped(ex)
+                result.failures.append(SplitFailure(path=path, error=ex))
+                continue
+            result.hashes[path] = file_hash
+
+        for path in previous_hashes:
+            if path not in result.statuses:
+                result.statuses[path] = FileStatus.DELETED
+
+        return result
+
+    def index_dir_report(
+        self,
+        root: str | os.PathLike,
+        include: Optional[Iterable[str]] = None,
+        exclude: Optional[Iterable[str]] = None,
+        *,
+        progress: Optional[ProgressCallback] = None,
+    ) -> SplitReport:
+        """Split every file in the repository at *root* like ``index_dir``,
+        returning the chunks in the same order together with a report of how
+        each file was split and why files were skipped."""
+        paths = walk_repo(root, include=include, exclude=exclude)
+        return self.split_files_report(paths, root=root, progress=progress)
+
+    def split_files_report(
+        self,
+        paths: Iterable[str | os.PathLike],
+        *,
+        root: Optional[str | os.PathLike] = None,
+        progress: Optional[ProgressCallback] = None,
+    ) -> SplitReport:
+        """Read and split the files at *paths* (relative to *root*, if given)
+        one by one, see ``index_dir_report``."""
+        result = SplitReport()
+
+        for path in paths:
+            try:
+                chunks = self._split_path_reporting(path, root, progress)
+            except SplitError as ex:
+                result.files_skipped += 1
+                result.errors.append(SplitFailure(path=ex.path, error=ex))
+                continue
+
+            if self._split_with_fallback(path, chunks):
+                result.files_fallback += 1
+            else:
+                result.files_parsed += 1
+            result.chunks.extend(chunks)
+
+        report(progress, Finished(result.files, len(result.chunks), result.files_skipped))
+        return result
+
+    def _split_with_fallback(self, path: str | os.PathLike, chunks: List[Chunk]) -> bool:
+        """Return True if the file at *path* was split into *chunks* with the
+        fallback splitter, as its language is not supported."""
+        if chunks:
+            return chunks[0].language is None
+        return self.language is None and Language.detect(path, '') is None
+
+    def par_index_dir(
+        self,
+        root: str | os.PathLike,
+        include: Optional[Iterable[str]] = None,
+        exclude: Optional[Iterable[str]] = None,
+        *,
+        max_workers: Optional[int] = None,
+        progress: Optional[ProgressCallback] = None,
+    ) -> Tuple[List[Chunk], List[SplitFailure]]:
+        """Split every file in the repository at *root* concurrently.
+
+        This is the concurrent version of ``index_dir``, see ``par_split_files``
+        for a description of the result.
+        """
+        paths = walk_repo(root, include=include, exclude=exclude)
+        return self.par_split_files(paths, root=root, max_workers=max_workers, progress=progress)
+
+    def par_split_files(
+        self,
+        paths: Iterable[str | os.PathLike],
+        *,
+        root: Optional[str | os.PathLike] = None,
+        max_workers: Optional[int] = None,
+        progress: Optional[ProgressCallback] = None,
+    ) -> Tuple[List[Chunk], List[SplitFailure]]:
+        """Read and split the files at *paths* (relative to *root*, if given)
+        concurrently on a pool of *max_workers* threads.
+
+        A file which can't be split doesn't stop the others from being split,
+        but is reported as a failure with the ``SplitError`` raised by
+        ``split_path`` instead.
+
+        If *progress* is given, it is called with the events of each file from
+        the worker thread splitting it, so it must be thread-safe. ``Finished``
+        is reported from the calling thread once all files were split.
+
+        Returns:
+            The chunks of all files with the files sorted by path (see
+            ``path_sort_key``) and the chunks of each file in source order, and
+            the failures sorted by path. The order doesn't depend on scheduling,
+            so the result is the same as when splitting the files one by one in
+            sorted order, e.g. with ``index_dir``.
+        """
+        files: List[Tuple[Path, List[Chunk]]] = []
+        failures: List[SplitFailure] = []
+
+        with ThreadPoolExecutor(max_workers=max_workers) as executor:
+            futures = [(Path(path), executor.submit(self._split_path_reporting, path, root, progress)) for path in paths]
+            for path, future in futures:
+                try:
+                    files.append((path, future.result()))
+                except SplitError as ex:
+                    failures.append(SplitFailure(path=ex.path, error=ex))
+
+        files.sort(key=lambda file: path_sort_key(file[0]))
+        chunks = [chunk for _, file_chunks in files for chunk in file_chunks]
+        failures.sort(key=lambda failure: path_sort_key(failure.path))
+        report(progress, Finished(len(futures), len(chunks), len(failures)))
+        return chunks, failures
+
+    def split_iter(
+        self,
+        text: str,
+        *,
+        boundary_detector: CodeBoundaryDetector = None,
+        language: Language = None,
+        path: Optional[str] = None,
+    ) -> Iterator[Chunk]:
+        """Lazily split text based on code boundaries, yielding chunks one at a time.
+
+        See ``split_chunks`` for a description of the arguments. With a
+        ``parse_cache``, the text is split completely before the first chunk
+        is yielded, unless its chunks are cached, in which case no diagnostics
+        are emitted for it again.
+        """
+        key = self._cache_key(text, boundary_detector, language) if self.parse_cache is not None else None
+        if key is None:
+            yield from self._split_uncached(text, boundary_detector, language, path)
+            return
+
+        chunks = self.parse_cache.get(key)
+        if chunks is None:
+            chunks = list(self._split_uncached(text, boundary_detector, language, path))
+            self.parse_cache.put(key, chunks)
+        yield from chunks
+
+    def _cache_key(
+        self,
+        text: str,
+        boundary_detector: Optional[CodeBoundaryDetector],
+        language: Optional[Language],
+    ) -> Optional[Tuple]:
+        """The key of the chunks of *text* in the parse cache, which includes
+        the settings affecting the chunks, as the cache may be shared, or None
+        if the tokenizer's settings can't be part of a key."""
+        tokenizer_key = None
+        if self.tokenizer is not None:
+            tokenizer_key = self.tokenizer.cache_key()
+            if tokenizer_key is None:
+                return None
+
+        return (
+            content_hash(text.encode('utf-8')),
+            language,
+            type(boundary_detector) if boundary_detector is not None else None,
+            self.language,
+            self._chunk_size,
+            self._length_function,
+            self.max_chunk_bytes,
+            self.min_chunk_bytes,
+            self.overlap_lines,
+            self.fallback,
+            tokenizer_key,
+            self.max_chunk_tokens,
+            self.normalize_newlines,
+            tuple(sorted((language.value, rules) for language, rules in self.glue_rules.items())),
+            getattr(boundary_detector, 'glue_rules', None),
+        )
+
+    def _split_uncached(
+        self,
+        text: str,
+        boundary_detector: Optional[CodeBoundaryDetector],
+        language: Optional[Language],
+        path: Optional[str],
+    ) -> Iterator[Chunk]:
+        if self.normalize_newlines and '\r' in text:
+            # Split the normalized text, then map the chunks back onto the original
+            normalized, removed = _normalize_newlines(text)
+            for chunk in self._split_iter(normalized, boundary_detector, language, path):
+                chunk.start_byte += bisect_left(removed, chunk.start_byte)
+                chunk.end_byte += bisect_left(removed, chunk.end_byte)
+                yield chunk
+            return
+
+        yield from self._split_iter(text, boundary_detector, language, path)
+
+    def _split_iter(
+        self,
+        text: str,
+        boundary_detector: Optional[CodeBoundaryDetector],
+        language: Optional[Language],
+        path: Optional[str],
+    ) -> Iterator[Chunk]:
+        if boundary_detector is None and language is None:
+            language = self.language
+
+        if boundary_detector is None and language is not None:
+            boundary_detector = language.get_boundary_detector(self.glue_rules.get(language))
+        elif boundary_detector is not None and language is None:
+            language = Language.from_detector(boundary_detector)
+
+        lines = text.split('\n')
+        source = text.encode('utf-8')
+
+        # Byte offset of the start of each line in the original text
+        line_offsets = []
+        offset = 0
+        for line in lines:
+            line_offsets.append(offset)
+            offset += len(line.encode('utf-8')) + 1
 
         # If no boundary detector was supplied for a specific language,
-        # default to the Langchain text splitter. This will work fine for e.g. text,
+        # use the fallback splitter. This will work fine for e.g. text,
         # markdown, etc.
         if boundary_detector is None:
-            return self.default_splitter.split_text(text)
+            if self.fallback == FallbackMode.LINES:
+                chunks = self._iter_line_chunks(lines, line_offsets, source)
+            else:
+                chunks = self._locate_chunks(text, self.default_splitter.split_text(text))
+        else:
+            chunks = self._iter_boundary_chunks(boundary_detector, text, lines, line_offsets, source, path)
 
-        lines = text.split('\n')
+        imports = boundary_detector.find_imports(text) if boundary_detector is not None else None
+
+        for chunk in self._merge_small_chunks(source, line_offsets, chunks):
+            chunk.language = language
+            chunk.imports = list(imports or [])
+            yield chunk
+
+    def _iter_boundary_chunks(
+        self,
+        boundary_detector: CodeBoundaryDetector,
+        text: str,
+        lines: List[str],
+        line_offsets: List[int],
+        source: bytes,
+        path: Optional[str],
+    ) -> Iterator[Chunk]:
+        """Yield the chunks of *text* found with *boundary_detector*, in source
+        order, where *path* is the path of the file used in diagnostics."""
         boundaries = boundary_detector.find_boundaries(text)
-        
+        statement_ends = boundary_detector.find_statement_ends(text)
+        heading_paths = boundary_detector.find_heading_paths(text) or {}
+        parent_contexts = boundary_detector.find_parent_contexts(text) or {}
+        import_lines = boundary_detector.find_import_lines(text) or set()
+
+        # Code which could not be parsed is split as plain code between the
+        # boundaries, as a range without boundary type
+        for first, last in boundary_detector.find_unparsed_ranges(text) or []:
+            self._emit(LogLevel.WARNING, DiagnosticKind.PARTIAL_PARSE,
+                       f"{path or ''}:{first + 1}-{last + 1}: Code could not be parsed "
+                       f"and was split as plain code", path)
+            boundaries.append((first, last, None, 0))
+
         # Sort boundaries by start line
         boundaries.sort(key=lambda x: x[0])
-        
-        chunks = []
+
+        def split_range(
+            first: int,
+            last: int,
+            signature: Optional[str] = None,
+            heading_path: Optional[List[str]] = None,
+            parent_context: Optional[str] = None,
+            kind: Optional[ChunkKind] = None,
+            name: Optional[str] = None,
+        ) -> Iterator[Chunk]:
+            # Leave out blank lines at either end
+            while first < last and not lines[first].strip():
+                first += 1
+            while last > first and not lines[last].strip():
+                last -= 1
+
+            range_text = '\n'.join(lines[first:last + 1])
+            if not range_text.strip():
+                return
+
+            # Code between boundaries is classified by its contents
+            if kind is None:
+                kind = self._classify_range(lines, first, last, import_lines)
+
+            # Every piece of a subdivided unit carries the unit's doc comment
+            doc = parse_doc_comment(boundary_detector.extract_doc_comment(range_text))
+
+            spans = self._subdivide(lines, line_offsets, source, first, last, statement_ends)
+            if len(spans) > 1:
+                unit = f"`{signature}`" if signature else "Code"
+                self._emit(LogLevel.INFO, DiagnosticKind.OVERSIZED_CHUNK,
+                           f"{path or ''}:{first + 1}-{last + 1}: {unit} exceeds the chunk size limit "
+                           f"and was split into {len(spans)} pieces", path)
+            pieces = []
+            for start, end, overlap in spans:
+                chunk = self._make_chunk(source, line_offsets, start, end)
+                chunk.overlap_prefix_lines = overlap
+                chunk.doc = doc
+                chunk.heading_path = heading_path
+                chunk.parent_context = parent_context
+                chunk.kind = kind
+                chunk.name = name
+                if chunk.text.strip():
+                    pieces.append(chunk)
+
+            for index, chunk in enumerate(pieces, start=1):
+                if len(spans) > 1:
+                    chunk.signature = signature
+                    chunk.part = (index, len(pieces))
+                yield chunk
+
+        def split_line_items(i: int, items: List[Tuple[int, int, str]], parent_context: Optional[str]) -> Iterator[Chunk]:
+            # Each of several items on line *i* gets a chunk spanning its own code
+            line = lines[i]
+            for start, end, boundary_type in items:
+                start_byte = line_offsets[i] + len(line[:start].encode('utf-8'))
+                end_byte = line_offsets[i] + len(line[:end].encode('utf-8'))
+                signature = line[start:end]
+                if self._size(signature) > self._size_limit():
+                    spans = self._split_line(source, start_byte, end_byte)
+                else:
+                    spans = [(start_byte, end_byte)]
+
+                for index, (span_start, span_end) in enumerate(spans, start=1):
+                    chunk = self._make_chunk(source, line_offsets, span_start, span_end)
+                    chunk.parent_context = parent_context
+                    chunk.kind = ChunkKind.from_boundary_type(boundary_type, nested=parent_context is not None)
+                    chunk.name = boundary_detector.extract_name(signature)
+                    if len(spans) > 1:
+                        chunk.signature = signature
+                        chunk.part = (index, len(spans))
+                    yield chunk
+
         current_pos = 0
-        
+
+        # Stack of (end_line, signature) for the boundaries enclosing the current one
+        enclosing: List[Tuple[int, str]] = []
+
         # Process each boundary
         for start_line, end_line, boundary_type, indent_level in boundaries:
             # Add any code before this boundary as a separate chunk
             if start_line > current_pos:
-                pre_boundary_text = '\n'.join(lines[current_pos:start_line])
-                if pre_boundary_text.strip():
-                    chunks.extend(self._split_large_chunk(pre_boundary_text))
-            
+                yield from split_range(current_pos, start_line - 1)
+
+            if boundary_type is None:
+                for chunk in split_range(start_line, end_line):
+                    chunk.partial_parse = True
+                    yield chunk
+                current_pos = max(current_pos, end_line + 1)
+                continue
+
             # Add the boundary itself as a chunk
-            boundary_text = '\n'.join(lines[start_line:end_line + 1])
-            if boundary_text.strip():
-                chunks.extend(self._split_large_chunk(boundary_text))
-            
-            current_pos = end_line + 1
-        
+            if start_line in heading_paths:
+                # Sections of a document start with their heading
+                signature = lines[start_line].strip()
+            else:
+                signature = self._find_signature(lines, start_line, end_line)
+
+            # Nested boundaries (e.g. methods) get the signature of the innermost
+            # enclosing boundary (e.g. `impl Foo {`) as context, others may be
+            # given a context by the detector (e.g. the receiver of Go methods)
+            while enclosing and enclosing[-1][0] < start_line:
+                enclosing.pop()
+            parent_context = enclosing[-1][1] if enclosing else parent_contexts.get(start_line)
+            enclosing.append((end_line, signature))
+
+            items = boundary_detector.split_line_items(lines[start_line]) if start_line == end_line else None
+            if items:
+                yield from split_line_items(start_line, items, parent_context)
+            else:
+                kind = ChunkKind.from_boundary_type(boundary_type, nested=parent_context is not None)
+                name = boundary_detector.extract_name(signature)
+                yield from split_range(start_line, end_line, signature, heading_paths.get(start_line), parent_context, kind, name)
+
+            # Nested boundaries (e.g. methods) are already covered by the
+            # chunk of the enclosing boundary, so don't move backwards
+            current_pos = max(current_pos, end_line + 1)
+
         # Add any remaining code after the last boundary
         if current_pos < len(lines):
-            remaining_text = '\n'.join(lines[current_pos:])
-            if remaining_text.strip():
-                chunks.extend(self._split_large_chunk(remaining_text))
-        
-        return [chunk for chunk in chunks if chunk.strip()]
-    
-    def _split_large_chunk(self, text: str) -> List[str]:
-        """Split a chunk that exceeds the size limit."""
-        if self._length_function(text) <= self._chunk_size:
-            return [text]
-        
-        # If the chunk is too large, split it by lines
-        lines = text.split('\n')
+            yield from split_range(current_pos, len(lines) - 1)
+
+    def _iter_line_chunks(self, lines: List[str], line_offsets: List[int], source: bytes) -> Iterator[Chunk]:
+        """Yield chunks of text without structure, packing as many paragraphs
+        into each chunk as the size limit allows.
+
+        Paragraphs exceeding the limit on their own are cut between lines, and
+        lines exceeding the limit on their own are hard split.
+        """
+        limit = self._size_limit()
+
+        # (first, last) line ranges of the paragraphs, i.e. runs of non-blank lines
+        paragraphs = []
+        for i, line in enumerate(lines):
+            if not line.strip():
+                continue
+            if paragraphs and paragraphs[-1][1] == i - 1:
+                paragraphs[-1][1] = i
+            else:
+                paragraphs.append([i, i])
+
+        def text_of(first: int, last: int) -> str:
+            return '\n'.join(lines[first:last + 1])
+
+        def chunk_of(first: int, last: int) -> Chunk:
+            end_byte = line_offsets[last] + len(lines[last].encode('utf-8'))
+            return self._make_chunk(source, line_offsets, line_offsets[first], end_byte)
+
+        group = None  # (first, last) lines of the paragraphs packed so far
+        for first, last in paragraphs:
+            if group is not None and self._size(text_of(group[0], last)) <= limit:
+                group = (group[0], last)
+                continue
+
+            if group is not None:
+                yield chunk_of(*group)
+                group = None
+
+            if self._size(text_of(first, last)) <= limit:
+                group = (first, last)
+            else:
+                for start, end, overlap in self._subdivide(lines, line_offsets, source, first, last, None):
+                    chunk = self._make_chunk(source, line_offsets, start, end)
+                    chunk.overlap_prefix_lines = overlap
+                    yield chunk
+
+        if group is not None:
+            yield chunk_of(*group)
+
+    def _merge_small_chunks(self, source: bytes, line_offsets: List[int], chunks: Iterable[Chunk]) -> Iterator[Chunk]:
+        """Merge chunks smaller than `min_chunk_bytes` into an adjacent chunk.
+
+        Small chunks are merged forward into the next chunk where possible, so
+        that consecutive small chunks coalesce into the definition that follows
+        them. Otherwise, they are merged backward into the previous chunk, except
+        for comments preceding a definition, which belong with that definition.
+        Chunks from different texts (i.e. files) are never merged.
+
+        Chunks nested inside another chunk (e.g. methods inside a class) are
+        never merged, and neither are pieces of a subdivided code unit or code
+        which could not be parsed. Chunks are also not merged if the result
+        would exceed the size limit.
+
+        *chunks* must be in source order, and are consumed lazily.
+        """
+        if self.min_chunk_bytes <= 0:
+            yield from chunks
+            return
+
+        def is_small(chunk: Chunk) -> bool:
+            return len(chunk.text.encode('utf-8')) < self.min_chunk_bytes
+
+        def merge(a: Chunk, b: Chunk) -> Optional[Chunk]:
+            if a.end_byte > b.start_byte or a.signature is not None or b.signature is not None:
+                return None
+            if a.partial_parse or b.partial_parse:
+                return None
+            merged = self._make_chunk(source, line_offsets, a.start_byte, b.end_byte)
+            if self._size(merged.text) > self._size_limit():
+                return None
+
+            # Keep the doc comment at the start of the merged chunk
+            merged.doc = a.doc if a.doc is not None else b.doc
+            merged.heading_path = a.heading_path if a.heading_path is not None else b.heading_path
+            primary = _merge_primary(a, b)
+            merged.kind = primary.kind
+            merged.name = primary.name
+            return merged
+
+        # The previous top-level chunk is held back until we know that nothing
+        # will be merged backward into it. Each top-level chunk is kept together
+        # with the chunks nested inside it, which follow it in source order.
+        previous: Optional[Chunk] = None
+        previous_nested: List[Chunk] = []
+        pending: Optional[Chunk] = None  # small chunk waiting to be merged forward
+        pending_nested: List[Chunk] = []
+        covered_until = 0
+
+        for chunk in chunks:
+            # Chunks nested in another chunk (e.g. methods) are left as they are
+            if chunk.end_byte <= covered_until:
+                (pending_nested if pending is not None else previous_nested).append(chunk)
+                continue
+            covered_until = chunk.end_byte
+
+            nested: List[Chunk] = []
+            if pending is not None:
+                forward = merge(pending, chunk)
+                if forward is not None:
+                    chunk = forward
+                    nested = pending_nested
+                else:
+                    backward = None
+                    if previous is not None and not self._is_comment(pending.text):
+                        backward = merge(previous, pending)
+
+                    if backward is not None:
+                        previous = backward
+                        previous_nested.extend(pending_nested)
+                    else:
+                        if previous is not None:
+                            yield previous
+                            yield from previous_nested
+                        previous, previous_nested = pending, pending_nested
+                pending, pending_nested = None, []
+
+            if is_small(chunk):
+                pending, pending_nested = chunk, nested
+            else:
+                if previous is not None:
+                    yield previous
+                    yield from previous_nested
+                previous, previous_nested = chunk, nested
+
+        if pending is not None:
+            # Nothing follows, the only option left is merging backward
+            backward = merge(previous, pending) if previous is not None else None
+            if backward is not None:
+                previous = backward
+                previous_nested.extend(pending_nested)
+            else:
+                if previous is not None:
+                    yield previous
+                    yield from previous_nested
+                previous, previous_nested = pending, pending_nested
+
+        if previous is not None:
+            yield previous
+            yield from previous_nested
+
+    def _classify_range(self, lines: List[str], first: int, last: int, import_lines: Set[int]) -> ChunkKind:
+        """Classify the code from line *first* to *last* which is not part of
+        any boundary, given the lines of the file's import statements."""
+        if self._is_comment('\n'.join(lines[first:last + 1])):
+            return ChunkKind.COMMENT
+
+        # Imports, possibly preceded by e.g. a license header or module docs
+        code_lines = [
+            i for i in range(first, last + 1)
+            if lines[i].strip() and not self._is_comment(lines[i])
+        ]
+        if all(i in import_lines for i in code_lines):
+            return ChunkKind.IMPORT
+        return ChunkKind.OTHER
+
+    def _is_comment(self, text: str) -> bool:
+        """Return True if every non-blank line of *text* is a comment."""
+        return all(
+            line.strip().startswith(('//', '/*', '*', '#'))
+            for line in text.split('\n') if line.strip()
+        )
+
+    def _size(self, text: str) -> int:
+        """Size of *text*, in the unit that the chunk size limit uses."""
+        if self._uses_tokens():
+            return self.tokenizer.count_tokens(text)
+        if self.max_chunk_bytes is not None:
+            return len(text.encode('utf-8'))
+        return self._length_function(text)
+
+    def _size_limit(self) -> int:
+        if self._uses_tokens():
+            return self.max_chunk_tokens
+        if self.max_chunk_bytes is not None:
+            return self.max_chunk_bytes
+        return self._chunk_size
+
+    def _uses_tokens(self) -> bool:
+        """Return True if the size limit is measured in tokens."""
+        return self.tokenizer is not None and self.max_chunk_tokens is not None
+
+    def _find_signature(self, lines: List[str], first: int, last: int) -> str:
+        """Return the first line of a code unit which is not a comment, attribute or decorator."""
+        depth = 0  # Brackets left open by an attribute or decorator continuing on the next line
+        for line in lines[first:last + 1]:
+            stripped = line.strip()
+            if depth > 0 or stripped.startswith(('#[', '#![', '@')):
+                depth = max(depth + sum(stripped.count(c) for c in '([{') - sum(stripped.count(c) for c in ')]}'), 0)
+                continue
+            if stripped and not stripped.startswith(('//', '/*', '*', '#')):
+                return stripped
+        return lines[first].strip()
+
+    def _make_chunk(self, source: bytes, line_offsets: List[int], start_byte: int, end_byte: int) -> Chunk:
+        """Create a chunk spanning *start_byte* to *end_byte* of *source*."""
+        # Don't leave a dangling carriage return at the end of CRLF chunks
+        if end_byte > start_byte and source[end_byte - 1:end_byte] == b'\r':
+            end_byte -= 1
+
+        return Chunk(
+            text=source[start_byte:end_byte].decode('utf-8'),
+            start_byte=start_byte,
+            end_byte=end_byte,
+            start_line=bisect_right(line_offsets, start_byte),
+            end_line=bisect_right(line_offsets, max(end_byte - 1, start_byte)),
+        )
+
+    def _locate_chunks(self, text: str, texts: List[str]) -> List[Chunk]:
+        """Find the position of each of *texts* (in order) within *text*."""
         chunks = []
-        current_chunk = []
-        current_size = 0
-        
-        for line in lines:
-            line_size = self._length_function(line + '\n')
-            
-            # If adding this line would exceed the limit, save current chunk
-            if current_size + line_size > self._chunk_size and current_chunk:
-                chunks.append('\n'.join(current_chunk))
-                current_chunk = []
-                current_size = 0
-            
-            current_chunk.append(line)
-            current_size += line_size
-        
-        # Add the last chunk if it has content
-        if current_chunk:
-            chunks.append('\n'.join(current_chunk))
-        
+        search_from = 0
+
+        for chunk_text in texts:
+            index = text.find(chunk_text, search_from)
+            if index < 0:
+                # Not a verbatim substring, we can't tell where it came from
+                continue
+
+            start_byte = len(text[:index].encode('utf-8'))
+            start_line = text.count('\n', 0, index) + 1
+            chunks.append(Chunk(
+                text=chunk_text,
+                start_byte=start_byte,
+                end_byte=start_byte + len(chunk_text.encode('utf-8')),
+                start_line=start_line,
+                end_line=start_line + chunk_text.count('\n'),
+            ))
+
+            # Chunks may overlap, so the next one can start before this one ends
+            search_from = index + 1
+
         return chunks
-    
+
+    def _subdivide(
+        self,
+        lines: List[str],
+        line_offsets: List[int],
+        source: bytes,
+        first: int,
+        last: int,
+        statement_ends: Optional[Set[int]],
+    ) -> List[Tuple[int, int, int]]:
+        """Split a (0-based, inclusive) range of lines that exceeds the size limit.
+
+        The range is preferably cut after a line in *statement_ends*, otherwise
+        after any line. Lines which exceed the limit on their own are hard split.
+
+        Each piece after the first starts with up to `overlap_lines` lines from
+        the end of the previous piece, as many as fit within the size limit.
+        Pieces which follow a hard split line get no overlap.
+
+        Returns a list of (start_byte, end_byte, overlap_lines) spans.
+        """
+        def span(a: int, b: int, overlap: int = 0) -> Tuple[int, int, int]:
+            return line_offsets[a], line_offsets[b] + len(lines[b].encode('utf-8')), overlap
+
+        limit = self._size_limit()
+        if self._size('\n'.join(lines[first:last + 1])) <= limit:
+            return [span(first, last)]
+
+        line_sizes = {}
+        def line_size(i: int) -> int:
+            if i not in line_sizes:
+                line_sizes[i] = self._size(lines[i] + '\n')
+            return line_sizes[i]
+
+        spans = []
+        piece_start = first  # first line of the current piece, excluding overlap
+        overlap = 0
+        current_size = 0
+        last_split = None  # last statement end seen in the current piece
+
+        def start_piece(start: int, previous_start: Optional[int]):
+            """Start a new piece at line *start*, overlapping the previous piece
+            (which started at line *previous_start*) if possible."""
+            nonlocal piece_start, overlap, current_size, last_split
+            piece_start = start
+            overlap = 0 if previous_start is None else min(self.overlap_lines, start - previous_start)
+            current_size = sum(line_size(j) for j in range(start - overlap, start))
+            last_split = None
+
+        i = first
+        while i <= last:
+            size = line_size(i)
+
+            if current_size + size > limit and i > piece_start:
+                # Cut at the last statement boundary if there was one, otherwise
+                # at the previous line, then start over from the cut
+                cut = last_split if last_split is not None else i - 1
+                spans.append(span(piece_start - overlap, cut, overlap))
+                start_piece(cut + 1, piece_start - overlap)
+                i = piece_start
+                continue
+
+            # Give up as much overlap as needed to fit the first line of the piece
+            while overlap > 0 and current_size + size > limit:
+                current_size -= line_size(piece_start - overlap)
+                overlap -= 1
+
+            if size > limit:
+                # A single line which is too large on its own
+                spans.extend((start, end, 0) for start, end in self._split_line(source, *span(i, i)[:2]))
+                start_piece(i + 1, None)
+                i += 1
+                continue
+
+            current_size += size
+            if statement_ends is None or i in statement_ends:
+                last_split = i
+            i += 1
+
+        # Add the last chunk
+        if piece_start <= last:
+            spans.append(span(piece_start - overlap, last, overlap))
+
+        return spans
+
+    def _split_line(self, source: bytes, start_byte: int, end_byte: int) -> List[Tuple[int, int]]:
+        """Split the byte span of a line exceeding the size limit into pieces
+        within the limit, see ``recursive_split``.
+
+        The line is preferably cut after sentences, then after words, e.g. in
+        long paragraphs of prose written on a single line. Otherwise, it is
+        hard split on character boundaries, so a multi-byte UTF-8 character is
+        never split, and where possible neither is a character from the
+        combining marks, variation selectors and joiners which follow it.
+        """
+        text = source[start_byte:end_byte].decode('utf-8')
+        spans = []
+        offset = start_byte
+
+        for piece in recursive_split(text, self._size_limit(), _LINE_SEPARATORS, self._size):
+            piece_bytes = len(piece.encode('utf-8'))
+            spans.append((offset, offset + piece_bytes))
+            offset += piece_bytes
+
+        return spans
+
     def create_documents(
         self, 
         texts: List[str], 
@@ -143,38 +1257,7 @@ class CodeBoundaryTextSplitter(TextSplitter):
         
         for i, text in enumerate(texts):
             metadata = metadatas[i] if metadatas else {}
-
-            boundary_detector = None
-
-            source: str = metadata.get("source")
-            if source:
-                # extract extension from the filename
-                ext = _get_extension_from_path(source)
-
-                if ext:
-                    boundary_detector = boundary_detectors.get(ext)
-            
-            # Split the text. We pass the boundary detector found for
-            # the file's extension, if none was found, then split_text
-            # will default to the Langchain text splitter.
-            chunks = self.split_text(text, boundary_detector=boundary_detector)
-            
-            # Create documents for each chunk
-            for j, chunk in enumerate(chunks):
-                doc_metadata = metadata.copy()
-
-                if include_metadata:
-                    doc_metadata.update({
-                        'chunk_index': j,
-                        'total_chunks': len(chunks),
-                    })
-                    if boundary_detector is not None:
-                        doc_metadata['boundary_types'] = boundary_detector.get_boundary_types()
-                    
-                    if self._add_start_index:
-                        doc_metadata['start_index'] = text.find(chunk)
-                
-                documents.append(Document(page_content=chunk, metadata=doc_metadata))
+            documents.extend(self._iter_text_documents(text, metadata, boundary_detectors, include_metadata))
         
         return documents
 
@@ -184,12 +1267,96 @@ class CodeBoundaryTextSplitter(TextSplitter):
                         boundary_detectors: Dict[str, CodeBoundaryDetector],
                         include_metadata: bool = False) -> List[Document]:
         """Split documents."""
-        texts, metadatas = [], []
+        return list(self.split_documents_iter(documents,
+                                              boundary_detectors=boundary_detectors,
+                                              include_metadata=include_metadata))
+
+    def split_documents_iter(self,
+                             documents: Iterable[Document],
+                             *,
+                             boundary_detectors: Dict[str, CodeBoundaryDetector],
+                             include_metadata: bool = False) -> Iterator[Document]:
+        """Lazily split documents, yielding the resulting documents one at a time.
+
+        *documents* is consumed lazily as well, so e.g. files can be loaded,
+        split and embedded without holding a whole repository in memory.
+        """
         for doc in documents:
-            texts.append(doc.page_content)
-            metadatas.append(doc.metadata)
-        return self.create_documents(texts,
-                                     metadatas=metadatas,
-                                     boundary_detectors=boundary_detectors,
-                                     include_metadata=include_metadata)
-    
\ No newline at end of file
+            yield from self._iter_text_documents(doc.page_content, doc.metadata, boundary_detectors, include_metadata)
+
+    def _iter_text_documents(
+        self,
+        text: str,
+        metadata: Dict[str, Any],
+        boundary_detectors: Dict[str, CodeBoundaryDetector],
+        include_metadata: bool,
+    ) -> Iterator[Document]:
+        """Split one text (i.e. file) and yield a Document for each chunk."""
+        boundary_detector = None
+
+        source: str = metadata.get("source")
+        if source:
+            # extract extension from the filename
+            ext = _get_extension_from_path(source)
+
+            if ext:
+                boundary_detector = boundary_detectors.get(ext.lower())
+        
+        # Split the text. We pass the boundary detector found for
+        # the file's extension, if none was found, then split_iter
+        # will default to the Langchain text splitter.
+        if boundary_detector is None and source:
+            self._emit(LogLevel.INFO, DiagnosticKind.PARSE_FALLBACK,
+                       f"Splitting file without a boundary detector with the fallback splitter: {source}", source)
+        chunks = self.split_iter(text, boundary_detector=boundary_detector, path=source)
+
+        # The total chunk count is only known once the whole text is split
+        if include_metadata:
+            chunks = list(chunks)
+        
+        # Create documents for each chunk
+        for j, chunk in enumerate(chunks):
+            doc_metadata = metadata.copy()
+
+            # Location of the chunk in the source file
+            doc_metadata.update({
+                'start_line': chunk.start_line,
+                'end_line': chunk.end_line,
+                'start_byte': chunk.start_byte,
+                'end_byte': chunk.end_byte,
+            })
+            if chunk.signature is not None:
+                doc_metadata['signature'] = chunk.signature
+            if chunk.overlap_prefix_lines:
+                doc_metadata['overlap_prefix_lines'] = chunk.overlap_prefix_lines
+            if chunk.part is not None:
+                doc_metadata['part_index'], doc_metadata['part_count'] = chunk.part
+            if chunk.partial_parse:
+                doc_metadata['partial_parse'] = True
+            if chunk.doc is not None and chunk.doc.brief:
+                doc_metadata['brief'] = chunk.doc.brief
+            if chunk.parent_context is not None:
+                doc_metadata['parent_context'] = chunk.parent_context
+            if chunk.heading_path:
+                # Lists are not supported as metadata by the vector store
+                doc_metadata['heading_path'] = ' > '.join(chunk.heading_path)
+            if chunk.language is not None:
+                doc_metadata['language'] = chunk.language.value
+            doc_metadata['kind'] = chunk.kind.value
+            if chunk.name is not None:
+                doc_metadata['name'] = chunk.name
+            if chunk.imports:
+                doc_metadata['imports'] = '\n'.join(chunk.imports)
+
+            if include_metadata:
+                doc_metadata.update({
+                    'chunk_index': j,
+                    'total_chunks': len(chunks),
+                })
+                if boundary_detector is not None:
+                    doc_metadata['boundary_types'] = boundary_detector.get_boundary_types()
+                
+                if self._add_start_index:
+                    doc_metadata['start_index'] = text.find(chunk.text)
+            
+            yield Document(page_content=chunk.text, metadata=doc_metadata)
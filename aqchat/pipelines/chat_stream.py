@@ -0,0 +1,168 @@
+import codecs
+import json
+import urllib.error
+import urllib.request
+from typing import Any, Callable, Dict, Iterable, Iterator, List, Optional
+from pipelines.abstract_chat import ChatError
+from pipelines.retry import parse_retry_after
+
+# Sends a POST request with the given URL, body and headers and returns the
+# response body as an iterable of byte chunks, as they arrive
+Transport = Callable[[str, bytes, Dict[str, str]], Iterable[bytes]]
+
+# Size of the reads from the response body by the default transport
+_READ_SIZE = 1024
+
+# Data of the server-sent event which ends a chat completion stream
+_DONE = "[DONE]"
+
+class SseParser:
+    """Incremental parser for a stream of server-sent events.
+
+    Network reads may end anywhere, including in the middle of an event or a
+    UTF-8 sequence, so incomplete data is buffered until the rest arrives.
+    """
+
+    def __init__(self):
+        self._decoder = codecs.getincrementaldecoder("utf-8")()
+        self._buffer = ""
+        self._data: List[str] = []
+
+    def feed(self, chunk: bytes) -> List[str]:
+        """Feed the next *chunk* of the stream, returning the data of the
+        events completed by it."""
+        self._buffer += self._decoder.decode(chunk)
+
+        events = []
+        while True:
+            # A line may end with CRLF, LF or CR, but a CR at the end of the
+            # buffer may be followed by a LF in the next chunk
+            end = min((i for i in (self._buffer.find("\r"), self._buffer.find("\n")) if i >= 0), default=-1)
+            if end < 0 or (self._buffer[end] == "\r" and end == len(self._buffer) - 1):
+                break
+
+            line = self._buffer[:end]
+            skip = 2 if self._buffer.startswith("\r\n", end) else 1
+            self._buffer = self._buffer[end + skip:]
+
+            event = self._feed_line(line)
+            if event is not None:
+                events.append(event)
+
+        return events
+
+    def _feed_line(self, line: str) -> Optional[str]:
+        # A blank line dispatches the event
+        if line == "":
+            if not self._data:
+                return None
+            event = "\n".join(self._data)
+            self._data = []
+            return event
+
+        # Lines starting with a colon are comments, e.g. keep-alives
+        if line.startswith(":"):
+            return None
+
+        name, _, value = line.partition(":")
+        if name == "data":
+            self._data.append(value[1:] if value.startswith(" ") else value)
+        return None
+
+def iter_completion_deltas(chunks: Iterable[bytes]) -> Iterator[str]:
+    """Parse an OpenAI-compatible chat completion event stream, yielding the
+    content deltas of the answer as they arrive.
+
+    Raises:
+        ChatError: If the stream contains an error or malformed event, or ends
+        before the ``[DONE]`` event.
+    """
+    parser = SseParser()
+    for chunk in chunks:
+        for data in parser.feed(chunk):
+            if data == _DONE:
+                return
+            delta = _parse_delta(data)
+            if delta:
+                yield delta
+
+    raise ChatError("Chat completion stream ended before [DONE]")
+
+def _parse_delta(data: str) -> Optional[str]:
+    try:
+        event = json.loads(data)
+    except json.JSONDecodeError as ex:
+        raise ChatError(f"Malformed chat completion event: {data[:100]!r}") from ex
+
+    if not isinstance(event, dict):
+        raise ChatError(f"Malformed chat completion event: {data[:100]!r}")
+    if "error" in event:
+        error = event["error"]
+        message = error.get("message", error) if isinstance(error, dict) else error
+        raise ChatError(f"Chat completion failed: {message}")
+
+    choices = event.get("choices") or []
+    if not choices:
+        return None
+    return (choices[0].get("delta") or {}).get("content")
+
+def stream_chat_completion(
+    base_url: str,
+    model: str,
+    messages: List[Dict[str, str]],
+    *,
+    api_key: Optional[str] = None,
+    options: Optional[Dict[str, Any]] = None,
+    transport: Optional[Transport] = None,
+) -> Iterator[str]:
+    """Stream a chat completion from the OpenAI-compatible server at
+    *base_url* (e.g. ``http://localhost:11434/v1``), yielding the content
+    deltas of the answer as they arrive.
+
+    Args:
+        model: The name of the model.
+        messages: The chat messages, see ``AbstractChatPipeline.query``.
+        api_key: The API key sent as bearer token, if any.
+        options: Further request parameters, e.g. ``{"temperature": 0.2}``.
+        transport: Sends the request, by default using ``urllib``.
+
+    Raises:
+        ChatError: If the request failed or the response stream was malformed.
+    """
+    body = json.dumps({**(options or {}), "model": model, "messages": messages, "stream": True})
+    headers = {"Content-Type": "application/json", "Accept": "text/event-stream"}
+    if api_key:
+        headers["Authorization"] = f"Bearer {api_key}"
+
+    transport = transport or urllib_transport
+    yield from iter_completion_deltas(transport(base_url.rstrip("/") + "/chat/completions", body.encode("utf-8"), headers))
+
+def chat_completion(
+    base_url: str,
+    model: str,
+    messages: List[Dict[str, str]],
+    **kwargs: Any,
+) -> str:
+    """Return the whole answer of a chat completion, see ``stream_chat_completion``."""
+    return "".join(stream_chat_completion(base_url, model, messages, **kwargs))
+
+def urllib_transport(url: str, body: bytes, headers: Dict[str, str]) -> Iterator[bytes]:
+    """Transport which sends the request with ``urllib``.
+
+    Raises:
+        ChatError: If the server could not be reached or returned an error status.
+    """
+    request = urllib.request.Request(url, data=body, headers=headers, method="POST")
+    try:
+        with urllib.request.urlopen(request) as response:
+            while True:
+                chunk = response.read1(_READ_SIZE)
+                if not chunk:
+                    return
+                yield chunk
+    except urllib.error.HTTPError as ex:
+        detail = ex.read().decode("utf-8", errors="replace")[:200]
+        retry_after = parse_retry_after(ex.headers.get("Retry-After") if ex.headers else None)
+        raise ChatError(f"{url} returned HTTP {ex.code}: {detail}", status=ex.code, retry_after=retry_after) from ex
+    except OSError as ex:
+        raise ChatError(f"Could not connect to {url}: {ex}") from ex
@@ -0,0 +1,315 @@
+import hashlib
+import json
+from dataclasses import dataclass, field
+from enum import Enum
+from typing import Any, Dict, Iterable, Iterator, List, Optional, TextIO, Tuple
+from pipelines.detectors import Language
+from pipelines.imports import relevant_imports
+from pipelines.split_errors import SplitError
+
+CHUNK_ID_VERSION = 1
+"""The version of the hashing scheme of ``Chunk.id``, which is only increased
+if the IDs change, e.g. with a major version."""
+
+class ChunkKind(Enum):
+    """What a chunk contains, e.g. a function or the imports of a file."""
+
+    FUNCTION = "function"
+    METHOD = "method"
+    STRUCT = "struct"
+    ENUM = "enum"
+    TRAIT = "trait"
+    IMPL = "impl"
+    MACRO = "macro"
+    COMMENT = "comment"
+    IMPORT = "import"
+    OTHER = "other"
+
+    @classmethod
+    def from_boundary_type(cls, boundary_type: str, nested: bool) -> "ChunkKind":
+        """Return the kind of a chunk found as a boundary of *boundary_type*
+        (see ``CodeBoundaryDetector.get_boundary_types``), where *nested* is
+        True if it belongs to another definition, e.g. a method.
+
+        Classes count as structs, and interfaces as traits.
+        """
+        if boundary_type == 'function' and nested:
+            return cls.METHOD
+        return _BOUNDARY_KINDS.get(boundary_type, cls.OTHER)
+
+    @property
+    def is_definition(self) -> bool:
+        """True for the kinds of definitions, i.e. all kinds but comments,
+        imports and other code."""
+        return self not in (ChunkKind.COMMENT, ChunkKind.IMPORT, ChunkKind.OTHER)
+
+_BOUNDARY_KINDS = {
+    'function': ChunkKind.FUNCTION,
+    'struct': ChunkKind.STRUCT,
+    'class': ChunkKind.STRUCT,
+    'enum': ChunkKind.ENUM,
+    'trait': ChunkKind.TRAIT,
+    'interface': ChunkKind.TRAIT,
+    'impl': ChunkKind.IMPL,
+    'macro': ChunkKind.MACRO,
+}
+
+@dataclass
+class DocMeta:
+    """Structured contents of the doc comment attached to a definition.
+
+    Recognized tags are ``@brief``, ``@allow``, ``@param`` and ``@return``. Any
+    other tags are kept verbatim in ``tags``, keyed by tag name without the ``@``.
+    """
+
+    raw: str
+    brief: Optional[str] = None
+    allow: List[str] = field(default_factory=list)
+    params: Dict[str, str] = field(default_factory=dict)
+    returns: Optional[str] = None
+    tags: Dict[str, List[str]] = field(default_factory=dict)
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to a JSON-compatible dict, see ``write_jsonl`` for the schema."""
+        return {
+            "raw": self.raw,
+            "brief": self.brief,
+            "allow": list(self.allow),
+            "params": dict(self.params),
+            "returns": self.returns,
+            "tags": {tag: list(values) for tag, values in self.tags.items()},
+        }
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "DocMeta":
+        """Create from a dict produced by ``to_dict``."""
+        return cls(
+            raw=data["raw"],
+            brief=data.get("brief"),
+            allow=list(data.get("allow", [])),
+            params=dict(data.get("params", {})),
+            returns=data.get("returns"),
+            tags={tag: list(values) for tag, values in data.get("tags", {}).items()},
+        )
+
+@dataclass
+class Chunk:
+    """A piece of source text produced by the code boundary splitter.
+
+    Byte offsets index into the UTF-8 encoding of the original, unmodified
+    source, so ``source.encode("utf-8")[chunk.start_byte:chunk.end_byte]``
+    decodes to exactly ``chunk.text``, unless the splitter normalized line
+    endings, in which case it decodes to the text with its original endings.
+
+    Line numbers are 1-based and ``end_line`` is inclusive.
+
+    When a code unit was too large for a single chunk and had to be subdivided,
+    each of its pieces carries the unit's ``signature`` (e.g. ``impl Foo {``).
+    Pieces may also start with lines repeated from the end of the previous
+    piece, in which case ``overlap_prefix_lines`` is the number of such lines.
+    ``part`` is ``(i, n)`` for the i-th of the n pieces of the unit, counting
+    from 1, and None for chunks which were not subdivided. Without overlap,
+    the pieces cover the unit in order, apart from the line breaks at which
+    it was cut.
+
+    ``doc`` holds the parsed doc comment of the definition in the chunk, if any.
+
+    Chunks nested inside another definition (e.g. methods) carry the signature
+    of the innermost enclosing definition (e.g. ``impl Foo for Bar {``) as
+    ``parent_context``, which is None for top-level chunks.
+
+    ``heading_path`` holds the titles of the headings enclosing the chunk in a
+    document, outermost first, e.g. ``["Installation", "From source"]``.
+
+    ``language`` is the language the chunk was split as, or None if the text was
+    not split structurally, e.g. because its language is not supported.
+
+    ``path`` is the path of the file the chunk was split from, if known.
+
+    ``also_at`` lists the locations (``path:start_line-end_line``) of identical
+    chunks which were dropped by ``dedup_chunks``.
+
+    ``imports`` lists the top-level import statements of the file the chunk was
+    split from, e.g. ``use std::time::{SystemTime, UNIX_EPOCH};``.
+
+    ``kind`` is what the chunk contains, e.g. ``ChunkKind.METHOD``, and ``name``
+    the name of the symbol it defines without generic parameters, e.g.
+    ``log_message``. ``name`` is None if the chunk doesn't define a single
+    named symbol, e.g. for imports.
+
+    ``partial_parse`` is True for chunks of malformed code which could not be
+    parsed, e.g. a function missing its closing brace, which are split as plain
+    code. The other chunks of the file are found as usual.
+
+    ``id()`` identifies the chunk by its location and content, e.g. to upsert
+    chunks into an external store when re-indexing.
+    """
+
+    text: str
+    start_byte: int
+    end_byte: int
+    start_line: int
+    end_line: int
+    signature: Optional[str] = None
+    overlap_prefix_lines: int = 0
+    doc: Optional[DocMeta] = None
+    parent_context: Optional[str] = None
+    heading_path: Optional[List[str]] = None
+    language: Optional[Language] = None
+    path: Optional[str] = None
+    also_at: List[str] = field(default_factory=list)
+    imports: List[str] = field(default_factory=list)
+    kind: ChunkKind = ChunkKind.OTHER
+    name: Optional[str] = None
+    part: Optional[Tuple[int, int]] = None
+    partial_parse: bool = False
+
+    @property
+    def embedding_text(self) -> str:
+        """The text to embed for the chunk, i.e. its text preceded by its
+        ``parent_context`` if any, so that e.g. methods can be found by the
+        type they belong to."""
+        if self.parent_context:
+            return f"{self.parent_context}\n{self.text}"
+        return self.text
+
+    def relevant_imports(self) -> List[str]:
+        """The ``imports`` binding a name which is used in the chunk's text."""
+        return relevant_imports(self.imports, self.text)
+
+    def id(self) -> str:
+        """A deterministic ID of the chunk, which is the same across runs and
+        machines and changes if its ``path``, byte range or ``text`` changes.
+
+        The ID is the hex encoded 16-byte BLAKE2b digest of the UTF-8 encoding
+        of ``"{CHUNK_ID_VERSION}\\0{path}\\0{start_byte}\\0{end_byte}\\0{text}"``,
+        where a missing path is empty. Other fields, e.g. ``kind``, don't
+        affect the ID.
+        """
+        key = f"{CHUNK_ID_VERSION}\0{self.path or ''}\0{self.start_byte}\0{self.end_byte}\0{self.text}"
+        return hashlib.blake2b(key.encode('utf-8'), digest_size=16).hexdigest()
+
+    @property
+    def location(self) -> str:
+        """The location of the chunk as ``path:start_line-end_line``."""
+        return f"{self.path or ''}:{self.start_line}-{self.end_line}"
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to a JSON-compatible dict, see ``write_jsonl`` for the schema."""
+        return {
+            "text": self.text,
+            "language": self.language.value if self.language is not None else None,
+            "path": self.path,
+            "start_line": self.start_line,
+            "end_line": self.end_line,
+            "start_byte": self.start_byte,
+            "end_byte": self.end_byte,
+            "parent_context": self.parent_context,
+            "signature": self.signature,
+            "overlap_prefix_lines": self.overlap_prefix_lines,
+            "heading_path": list(self.heading_path) if self.heading_path is not None else None,
+            "doc": self.doc.to_dict() if self.doc is not None else None,
+            "also_at": list(self.also_at),
+            "imports": list(self.imports),
+            "kind": self.kind.value,
+            "name": self.name,
+            "part": list(self.part) if self.part is not None else None,
+            "partial_parse": self.partial_parse,
+        }
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "Chunk":
+        """Create from a dict produced by ``to_dict``."""
+        language = data.get("language")
+        heading_path = data.get("heading_path")
+        doc = data.get("doc")
+        part = data.get("part")
+        return cls(
+            text=data["text"],
+            start_byte=data["start_byte"],
+            end_byte=data["end_byte"],
+            start_line=data["start_line"],
+            end_line=data["end_line"],
+            signature=data.get("signature"),
+            overlap_prefix_lines=data.get("overlap_prefix_lines", 0),
+            doc=DocMeta.from_dict(doc) if doc is not None else None,
+            parent_context=data.get("parent_context"),
+            heading_path=list(heading_path) if heading_path is not None else None,
+            language=Language(language) if language is not None else None,
+            path=data.get("path"),
+            also_at=list(data.get("also_at", [])),
+            imports=list(data.get("imports", [])),
+            kind=ChunkKind(data.get("kind", ChunkKind.OTHER.value)),
+            name=data.get("name"),
+            part=tuple(part) if part is not None else None,
+            partial_parse=data.get("partial_parse", False),
+        )
+
+@dataclass
+class SplitFailure:
+    """A file which could not be read or split, with the error raised."""
+
+    path: str
+    error: SplitError
+
+def dedup_chunks(chunks: Iterable[Chunk], ignore_whitespace: bool = False) -> List[Chunk]:
+    """Drop chunks whose text is identical to an earlier chunk.
+
+    The first occurrence is kept and records the locations of its duplicates
+    in ``also_at``. Chunks are compared by a hash of their text; with
+    *ignore_whitespace*, leading and trailing whitespace of each line and
+    blank lines at either end are ignored, so e.g. differently indented copies
+    of a function are also duplicates.
+    """
+    kept: Dict[bytes, Chunk] = {}
+    result = []
+
+    for chunk in chunks:
+        text = chunk.text
+        if ignore_whitespace:
+            text = '\n'.join(line.strip() for line in text.strip().split('\n'))
+
+        digest = hashlib.blake2b(text.encode('utf-8'), digest_size=16).digest()
+        original = kept.get(digest)
+        if original is None:
+            kept[digest] = chunk
+            result.append(chunk)
+        else:
+            original.also_at.append(chunk.location)
+
+    return result
+
+def write_jsonl(chunks: Iterable[Chunk], writer: TextIO) -> None:
+    """Write *chunks* to *writer* as JSON Lines, i.e. one JSON object per line.
+
+    Each object has the following fields, which are kept stable:
+
+    - ``text``: the text of the chunk
+    - ``language``: the language the chunk was split as (e.g. ``"rust"``), or null
+    - ``path``: the path of the file the chunk was split from, or null
+    - ``start_line``, ``end_line``: 1-based, inclusive line range
+    - ``start_byte``, ``end_byte``: byte range in the UTF-8 encoded file
+    - ``parent_context``: signature of the enclosing definition, or null
+    - ``signature``: signature of the subdivided code unit, or null
+    - ``overlap_prefix_lines``: number of lines repeated from the previous piece
+    - ``heading_path``: list of enclosing heading titles, or null
+    - ``doc``: null, or an object with the parsed doc comment fields ``raw``,
+      ``brief``, ``allow``, ``params``, ``returns`` and ``tags``
+    - ``also_at``: list of locations of identical chunks removed by deduplication
+    - ``imports``: list of the top-level import statements of the file
+    - ``kind``: what the chunk contains, e.g. ``"function"`` or ``"method"``
+    - ``name``: name of the symbol defined by the chunk, or null
+    - ``part``: ``[i, n]`` for the i-th of n pieces of a subdivided code unit, or null
+    - ``partial_parse``: true for malformed code which could not be parsed
+
+    Fields may be added in the future, so readers should ignore unknown fields.
+    """
+    for chunk in chunks:
+        writer.write(json.dumps(chunk.to_dict(), ensure_ascii=False))
+        writer.write("\n")
+
+def read_jsonl(reader: TextIO) -> Iterator[Chunk]:
+    """Lazily read chunks written by ``write_jsonl`` from *reader*."""
+    for line in reader:
+        if line.strip():
+            yield Chunk.from_dict(json.loads(line))
@@ -0,0 +1,109 @@
+import re
+from dataclasses import dataclass, field
+from typing import Callable, Dict, List, Optional, Sequence, Tuple
+from pipelines.chunk import Chunk
+from pipelines.context_budget import CONTEXT_SEPARATOR
+from pipelines.prompt_template import PromptTemplate
+
+# Appended to the system prompt so that the model cites the numbered chunks
+CITATION_INSTRUCTIONS = (
+    "Answer using the numbered context passages below. After each statement "
+    "based on a passage, cite it by its number in square brackets, e.g. [1] or "
+    "[2, 3]. Only cite passages that are given."
+)
+
+# Inline citations such as `[1]` or `[2, 3]`
+_CITATION_PATTERN = re.compile(r'\[(\d+(?:\s*,\s*\d+)*)\]')
+
+@dataclass
+class Citation:
+    """Reference from an answer to a chunk placed in its context, where
+    ``index`` is the 1-based number of the chunk in the context."""
+
+    index: int
+    path: Optional[str]
+    start_line: int
+    end_line: int
+
+    @property
+    def location(self) -> str:
+        """The location of the cited chunk as ``path:start_line-end_line``."""
+        return f"{self.path or ''}:{self.start_line}-{self.end_line}"
+
+@dataclass
+class CitedAnswer:
+    """The answer of a chat model with references to its sources.
+
+    ``citations`` holds a citation for every chunk placed in the context, while
+    ``cited`` only holds those the model cited inline, in order of first use.
+    """
+
+    text: str
+    citations: List[Citation] = field(default_factory=list)
+    cited: List[Citation] = field(default_factory=list)
+
+def format_cited_context(chunks: Sequence[Chunk]) -> Tuple[str, List[Citation]]:
+    """Join *chunks* into the context of a prompt, each preceded by its number
+    and location, e.g. ``[1] src/lib.rs:10-24``.
+
+    Returns:
+        The context and a citation for each chunk.
+    """
+    citations = []
+    passages = []
+    for index, chunk in enumerate(chunks, start=1):
+        citation = Citation(index, chunk.path, chunk.start_line, chunk.end_line)
+        citations.append(citation)
+        passages.append(f"[{index}] {citation.location}\n{chunk.embedding_text}")
+    return CONTEXT_SEPARATOR.join(passages), citations
+
+def parse_citations(text: str, citations: Sequence[Citation]) -> List[Citation]:
+    """Return the *citations* referenced inline in the answer *text*, in order
+    of first use. Numbers which don't refer to any of *citations* are ignored."""
+    by_index = {citation.index: citation for citation in citations}
+    cited: Dict[int, Citation] = {}
+
+    for match in _CITATION_PATTERN.finditer(text):
+        for number in match.group(1).split(','):
+            citation = by_index.get(int(number))
+            if citation is not None and citation.index not in cited:
+                cited[citation.index] = citation
+
+    return list(cited.values())
+
+def cited_messages(
+    question: str,
+    chunks: Sequence[Chunk],
+    system_prompt: Optional[str] = None,
+    template: Optional[PromptTemplate] = None,
+) -> Tuple[List[Dict[str, str]], List[Citation]]:
+    """Build the chat messages asking *question* with the numbered *chunks* as
+    context, see ``answer_with_citations``.
+
+    Returns:
+        The messages and a citation for each chunk.
+    """
+    context, citations = format_cited_context(chunks)
+    messages = (template or PromptTemplate()).render(question, context, CITATION_INSTRUCTIONS, system_prompt)
+    return messages, citations
+
+def answer_with_citations(
+    question: str,
+    chunks: Sequence[Chunk],
+    complete: Callable[[List[Dict[str, str]]], str],
+    system_prompt: Optional[str] = None,
+    template: Optional[PromptTemplate] = None,
+) -> CitedAnswer:
+    """Answer *question* with the numbered *chunks* as context.
+
+    Args:
+        question: The question of the user.
+        chunks: The retrieved chunks to place in the context, most relevant first.
+        complete: Returns the answer of the chat model for a list of chat
+        messages, see ``AbstractChatPipeline.query``.
+        system_prompt: Instructions preceding the citation instructions, if any.
+        template: The template of the messages, by default ``PromptTemplate()``.
+    """
+    messages, citations = cited_messages(question, chunks, system_prompt, template)
+    text = complete(messages)
+    return CitedAnswer(text=text, citations=citations, cited=parse_citations(text, citations))
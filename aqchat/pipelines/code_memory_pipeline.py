@@ -2,7 +2,8 @@ import os
 import shutil
 from pathlib import Path
 from threading import Lock
-from typing import Iterable, List, Dict, Any
+from itertools import islice
+from typing import Iterable, Iterator, List, Dict, Any
 from langchain_core.documents import Document
 
 from langchain_community.document_loaders import TextLoader
@@ -10,9 +11,23 @@ from langchain_chroma.vectorstores import Chroma
 from langchain_community.embeddings import FastEmbedEmbeddings
 from langchain_ollama import OllamaEmbeddings
 from langchain_community.vectorstores.utils import filter_complex_metadata
+from pipelines.abstract_diagnostics import AbstractDiagnostics, Diagnostic, DiagnosticKind, LogLevel
+from pipelines.abstract_embedder import AbstractEmbedder
 from pipelines.abstract_memory import AbstractMemoryPipeline
-from pipelines.detectors import CodeBoundaryDetector, PythonBoundaryDetector, RustBoundaryDetector
+from pipelines.detectors import CodeBoundaryDetector, get_boundary_detectors
+from pipelines.batching_embedder import BatchingEmbedder
 from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.context_budget import fit_documents_to_budget
+from pipelines.imports import relevant_imports
+from pipelines.progress import EmbeddingBatch, FileChunked, FileFailed, FileStarted, Finished, ProgressCallback, report
+from pipelines.langchain_embedder import EmbedderEmbeddings, LangchainEmbedder
+from pipelines.null_diagnostics import NullDiagnostics
+from pipelines.repo_walker import walk_repo
+from pipelines.retrying_embedder import RetryingEmbedder
+from pipelines.split_errors import SplitIoError
+
+# Number of chunks embedded and added to the vector store at a time during ingest
+_INGEST_BATCH_SIZE = 256
 
 class CodeMemoryPipeline(AbstractMemoryPipeline):
     """Retrieval-augmented Q&A over a local Git repository (or any code directory).
@@ -41,11 +56,16 @@ class CodeMemoryPipeline(AbstractMemoryPipeline):
         persist_directory: os.PathLike | str = "/app/data/chroma",
         ollama_url: str | None = None,
         ollama_embedding_model: str | None = None,
+        embedder: AbstractEmbedder | None = None,
+        embed_batch_size: int = 32,
+        prepend_imports: bool = False,
+        diagnostics: AbstractDiagnostics | None = None,
         retrieval_settings: Dict[str, Any] = {
             "ret_strat": "mmr",
             "k": 6,
             "fetch_k": 20,
-            "lambda_mult": 0.5
+            "lambda_mult": 0.5,
+            "context_budget": 0,
         },
     ) -> None:
         # allocate mutex
@@ -53,13 +73,17 @@ class CodeMemoryPipeline(AbstractMemoryPipeline):
 
         self.retrieval_settings = retrieval_settings
 
+        # Whether to prepend the imports used by a chunk to its content
+        self.prepend_imports = prepend_imports
+
+        # Sink for diagnostics of indexing, e.g. skipped files, which are
+        # discarded unless given
+        self.diagnostics = diagnostics if diagnostics is not None else NullDiagnostics()
+
         # Keep blocks coherent when splitting
-        self.text_splitter = CodeBoundaryTextSplitter(strip_whitespace=False)
+        self.text_splitter = CodeBoundaryTextSplitter(strip_whitespace=False, diagnostics=self.diagnostics)
 
-        self.boundary_detectors: Dict[str, CodeBoundaryDetector] = {
-            ".py": PythonBoundaryDetector(),
-            ".rs": RustBoundaryDetector()
-        }
+        self.boundary_detectors: Dict[str, CodeBoundaryDetector] = get_boundary_detectors()
 
         # Which file extensions to ingest
         self.include_ext = set(
@@ -86,10 +110,15 @@ class CodeMemoryPipeline(AbstractMemoryPipeline):
         self.retriever = None
         self._repo_root: Path | None = None
 
-        if ollama_url is not None:
-            self.embeddings = OllamaEmbeddings(model=ollama_embedding_model, base_url=ollama_url)
-        else:
-            self.embeddings = FastEmbedEmbeddings()
+        # Unless an embedder is given, use Ollama if configured, else FastEmbed
+        if embedder is None:
+            if ollama_url is not None:
+                ollama = LangchainEmbedder(OllamaEmbeddings(model=ollama_embedding_model, base_url=ollama_url))
+                embedder = RetryingEmbedder(ollama)
+            else:
+                embedder = LangchainEmbedder(FastEmbedEmbeddings())
+        self.embedder = BatchingEmbedder(embedder, embed_batch_size)
+        self.embeddings = EmbedderEmbeddings(self.embedder)
 
         # Try restoring a previously‑saved Chroma collection (if present)
         if any(self.persist_directory.iterdir()):
@@ -100,7 +129,9 @@ class CodeMemoryPipeline(AbstractMemoryPipeline):
                 )
                 self._build_chain()  # sets up self.retriever
             except Exception as ex:
-                print(f"WARNING: Could not initialize vector stores from disk: {ex}")
+                self.diagnostics.emit(Diagnostic(
+                    LogLevel.WARNING, DiagnosticKind.OTHER, f"Could not initialize vector stores from disk: {ex}",
+                ))
                 
                 # Corrupt or incompatible store – start fresh
                 self.vector_store = None
@@ -110,8 +141,14 @@ class CodeMemoryPipeline(AbstractMemoryPipeline):
     # INGESTION / INDEXING
     # --------------------------------------------------------------
 
-    def ingest(self, repo_path: str | os.PathLike) -> None:
-        """Walk *repo_path*, index eligible files, and build the retrieval chain."""
+    def ingest(self, repo_path: str | os.PathLike, progress: ProgressCallback | None = None) -> None:
+        """Walk *repo_path*, index eligible files, and build the retrieval chain.
+
+        If *progress* is given, it is called with the events of each file, an
+        ``EmbeddingBatch`` whenever a batch of chunks was added to the vector
+        store and finally ``Finished``. As files are split lazily, the total of
+        an ``EmbeddingBatch`` is the number of chunks split so far.
+        """
         with self.lock:
             repo_path = Path(repo_path).expanduser().resolve()
             if not repo_path.exists():
@@ -119,22 +156,31 @@ class CodeMemoryPipeline(AbstractMemoryPipeline):
 
             self._repo_root = repo_path  # remember for later updates
 
-            docs = self._load_repo(repo_path)
-            chunks = self.text_splitter.split_documents(docs, boundary_detectors=self.boundary_detectors)
-            chunks = filter_complex_metadata(chunks)
-
-            # Filter out chunks with content length <= 3
-            chunks = [chunk for chunk in chunks if len(chunk.page_content.strip()) > 3]
+            # Files are loaded and split lazily, and the chunks are embedded in
+            # batches, so the whole repo never has to be held in memory at once
+            stats = {"files": 0, "chunks": 0, "failures": 0}
+            docs = self._iter_repo(repo_path, progress, stats)
+            chunks = self._iter_chunks(docs, progress, stats)
 
             # (Re)‑create vector store on disk
-            self.vector_store = Chroma.from_documents(
-                documents=chunks,
-                embedding=self.embeddings,
+            self.vector_store = Chroma(
                 persist_directory=str(self.persist_directory),
+                embedding_function=self.embeddings,
             )
 
+            done = 0
+            batch = list(islice(chunks, _INGEST_BATCH_SIZE))
+            while batch:
+                done += len(batch)
+                batch = self._prepare_chunks(batch)
+                if batch:
+                    self.vector_store.add_documents(batch)
+                report(progress, EmbeddingBatch(done, stats["chunks"]))
+                batch = list(islice(chunks, _INGEST_BATCH_SIZE))
+
             # Build retriever and QA chain
             self._build_chain()
+            report(progress, Finished(stats["files"], stats["chunks"], stats["failures"]))
 
     # --------------------------------------------------------------
     # UPDATE PATH(S)
@@ -172,12 +218,10 @@ class CodeMemoryPipeline(AbstractMemoryPipeline):
 
             if docs:
                 chunks = self.text_splitter.split_documents(docs, boundary_detectors=self.boundary_detectors)
-                chunks = filter_complex_metadata(chunks)
-
-                # Filter out chunks with content length <= 3
-                chunks = [chunk for chunk in chunks if len(chunk.page_content.strip()) > 3]
+                chunks = self._prepare_chunks(chunks)
 
-                self.vector_store.add_documents(chunks)
+                if chunks:
+                    self.vector_store.add_documents(chunks)
 
             # Refresh retriever so it sees the latest state
             self._build_chain()
@@ -199,7 +243,13 @@ class CodeMemoryPipeline(AbstractMemoryPipeline):
         The final user message is treated as the question for retrieval.
         """
         with self.lock:
-            return self.retriever.invoke(input)
+            documents = self.retriever.invoke(input)
+
+            # Keep the most relevant documents within the context budget, if any
+            context_budget = self.retrieval_settings.get("context_budget")
+            if context_budget:
+                documents = fit_documents_to_budget(documents, context_budget)
+            return documents
         
     def _clear(self) -> None:
         """Clear *in-memory* state - keeps the persisted DB intact."""
@@ -243,6 +293,9 @@ class CodeMemoryPipeline(AbstractMemoryPipeline):
             "ret_strat": "similarity", "k": 4
         }
         ```
+
+        Either may also contain a ``"context_budget"``, the maximum number of
+        tokens of the retrieved documents, where 0 means there is no limit.
         """
         with self.lock:
             self.retrieval_settings = retrieval_settings
@@ -279,19 +332,80 @@ class CodeMemoryPipeline(AbstractMemoryPipeline):
             search_kwargs=search_settings,
         )
 
+    def _prepare_chunks(self, chunks: List[Document]) -> List[Document]:
+        """Make split chunks ready for insertion into the vector store."""
+        chunks = filter_complex_metadata(chunks)
+
+        # Filter out chunks with content length <= 3
+        chunks = [chunk for chunk in chunks if len(chunk.page_content.strip()) > 3]
+
+        # Prepend the enclosing definition (e.g. `impl Foo {`) to nested chunks
+        # such as methods, so that it is taken into account for retrieval
+        for chunk in chunks:
+            parent_context = chunk.metadata.get("parent_context")
+            if parent_context:
+                chunk.page_content = f"{parent_context}\n{chunk.page_content}"
+
+        # Prepend the file's imports whose names are used in the chunk, so that
+        # e.g. `SystemTime::now()` is known to be `std::time::SystemTime`
+        if self.prepend_imports:
+            for chunk in chunks:
+                imports = chunk.metadata.get("imports")
+                if imports:
+                    relevant = relevant_imports(imports.split("\n"), chunk.page_content)
+                    if relevant:
+                        chunk.page_content = "\n".join([*relevant, chunk.page_content])
+
+        return chunks
+
     # ----------------- File‑system helpers ---------------------------
 
-    def _load_repo(self, root: Path) -> List:
-        """Return LangChain Documents for every eligible file in *root*."""
-        docs = []
+    def _iter_repo(
+        self,
+        root: Path,
+        progress: ProgressCallback | None = None,
+        stats: Dict[str, int] | None = None,
+    ) -> Iterator[Document]:
+        """Lazily yield LangChain Documents for every eligible file in *root*.
+
+        Files and failures are counted in *stats*, if given.
+        """
+        stats = stats if stats is not None else {"files": 0, "failures": 0}
         ignore_dirs = {".git", ".venv", "__pycache__", "dist", "build", ".idea"}
 
-        for path in root.rglob("*"):
-            if path.is_dir() and path.name in ignore_dirs:
+        # Files ignored by .gitignore and binary files are skipped while walking
+        for rel_path in walk_repo(root):
+            if any(part in ignore_dirs for part in rel_path.parts[:-1]):
                 continue
-            if path.is_file() and path.suffix.lower() in self.include_ext:
-                docs.extend(self._load_single_file(path, path.relative_to(root)))
-        return docs
+            if rel_path.suffix.lower() in self.include_ext:
+                stats["files"] += 1
+                report(progress, FileStarted(rel_path.as_posix()))
+
+                # A single unreadable file shouldn't stop the whole repo from being indexed
+                try:
+                    docs = self._load_single_file(root / rel_path, rel_path)
+                except Exception as ex:
+                    self.diagnostics.emit(Diagnostic(
+                        LogLevel.WARNING, DiagnosticKind.SKIPPED_FILE,
+                        f"Skipping file which could not be loaded: {rel_path}: {ex}", rel_path.as_posix(),
+                    ))
+                    stats["failures"] += 1
+                    report(progress, FileFailed(rel_path.as_posix(), SplitIoError(rel_path.as_posix(), f"could not be loaded: {ex}")))
+                    continue
+                yield from docs
+
+    def _iter_chunks(
+        self,
+        docs: Iterable[Document],
+        progress: ProgressCallback | None,
+        stats: Dict[str, int],
+    ) -> Iterator[Document]:
+        """Lazily split *docs* one file at a time, counting the chunks in *stats*."""
+        for doc in docs:
+            chunks = self.text_splitter.split_documents([doc], boundary_detectors=self.boundary_detectors)
+            stats["chunks"] += len(chunks)
+            report(progress, FileChunked(Path(doc.metadata["source"]).as_posix(), len(chunks)))
+            yield from chunks
 
     def _load_single_file(self, abs_path: Path, rel_path: Path) -> List:
         """Load *abs_path* and return a list with its LangChain Document(s)."""
@@ -0,0 +1,16 @@
+from pipelines.abstract_diagnostics import AbstractDiagnostics, Diagnostic, LogLevel
+
+# The levels in order of severity
+_LEVELS = list(LogLevel)
+
+class ConsoleDiagnostics(AbstractDiagnostics):
+    """Diagnostics sink which prints the diagnostics of at least *min_level*,
+    e.g. ``WARNING: Skipping file which could not be split: ...``."""
+
+    def __init__(self, min_level: LogLevel = LogLevel.INFO):
+        self.min_level = min_level
+
+    def emit(self, diagnostic: Diagnostic) -> None:
+        if _LEVELS.index(diagnostic.level) >= _LEVELS.index(self.min_level):
+            # A single call, so that lines of several threads don't interleave
+            print(f"{diagnostic.level.name}: {diagnostic.message}")
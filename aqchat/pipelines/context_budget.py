@@ -0,0 +1,206 @@
+import dataclasses
+import re
+from typing import Callable, List, Optional, Sequence, Tuple
+from langchain_core.documents import Document
+from pipelines.abstract_tokenizer import AbstractTokenizer
+from pipelines.chunk import Chunk
+from pipelines.detectors import Language
+from pipelines.heuristic_tokenizer import HeuristicTokenizer
+
+# Separator between chunks in the assembled context
+CONTEXT_SEPARATOR = "\n\n"
+
+# Comment replacing the lines dropped from a truncated code chunk, after the
+# language's line comment token, e.g. `// …truncated (12 lines omitted)`
+TRUNCATION_MARKER = "\u2026truncated ({omitted} {lines} omitted)"
+
+# Ends of sentences, i.e. punctuation followed by whitespace or the end of text
+_SENTENCE_END = re.compile(r'[.!?](?=\s|$)')
+
+def fit_to_budget(
+    results: Sequence[Tuple[Chunk, float]],
+    budget_tokens: int,
+    tokenizer: Optional[AbstractTokenizer] = None,
+) -> List[Chunk]:
+    """Select retrieved chunks for the context of a prompt without exceeding
+    *budget_tokens* tokens once joined by ``format_context``.
+
+    Chunks are included greedily by descending score. A chunk which doesn't fit
+    into the remaining budget is truncated, keeping its ``parent_context``, or
+    dropped if it can't be truncated to fit:
+
+    - Code is cut after a statement, dropping the middle of the chunk, so that
+      the first line (e.g. a function's signature) and the last line (e.g. its
+      closing brace) are kept. The dropped lines are replaced by a comment, see
+      ``TRUNCATION_MARKER``, which counts towards the budget. If only the first
+      line fits with the marker, the last line is dropped as well.
+    - Prose, i.e. Markdown and text without a language, is cut at the end of a
+      sentence or line.
+
+    The text of a truncated chunk with a marker no longer matches its byte
+    range, which still covers the whole chunk, while a chunk cut at its end
+    ends at the end of the remaining text.
+
+    Args:
+        results: The retrieved chunks with their scores, e.g. from
+        ``AbstractVectorStore.search``.
+        budget_tokens: The maximum number of tokens of the context.
+        tokenizer: Counts the tokens, by default ``HeuristicTokenizer``.
+    """
+    ranked = sorted(results, key=lambda result: -result[1])
+    bodies = _fit([(chunk.parent_context, chunk.text, chunk.language) for chunk, _ in ranked], budget_tokens, tokenizer)
+
+    chunks = []
+    for (chunk, _), body in zip(ranked, bodies):
+        if body is None:
+            continue
+        if body != chunk.text and not chunk.text.startswith(body):
+            chunk = dataclasses.replace(chunk, text=body)
+        elif body != chunk.text:
+            chunk = dataclasses.replace(
+                chunk,
+                text=body,
+                end_byte=chunk.start_byte + len(body.encode('utf-8')),
+                end_line=chunk.start_line + body.count('\n'),
+            )
+        chunks.append(chunk)
+    return chunks
+
+def fit_documents_to_budget(
+    documents: Sequence[Document],
+    budget_tokens: int,
+    tokenizer: Optional[AbstractTokenizer] = None,
+) -> List[Document]:
+    """Like ``fit_to_budget``, but for retrieved documents in descending
+    order of relevance, as returned by a Langchain retriever.
+
+    The ``page_content`` of documents is expected to start with the
+    ``parent_context`` in their metadata, if any, and their language is taken
+    from the ``language`` in their metadata, as indexed by
+    ``CodeMemoryPipeline``.
+    """
+    items = []
+    for document in documents:
+        parent_context = document.metadata.get("parent_context")
+        body = document.page_content
+        if parent_context and body.startswith(parent_context + "\n"):
+            body = body[len(parent_context) + 1:]
+        else:
+            parent_context = None
+        items.append((parent_context, body, _language_of(document)))
+
+    fitted = []
+    for document, (parent_context, body, _), fitted_body in zip(documents, items, _fit(items, budget_tokens, tokenizer)):
+        if fitted_body is None:
+            continue
+        if fitted_body != body:
+            document = Document(page_content=_render(parent_context, fitted_body), metadata=dict(document.metadata))
+        fitted.append(document)
+    return fitted
+
+def format_context(chunks: Sequence[Chunk]) -> str:
+    """Join *chunks* into the context of a prompt, each preceded by its
+    ``parent_context``."""
+    return CONTEXT_SEPARATOR.join(chunk.embedding_text for chunk in chunks)
+
+def _render(parent_context: Optional[str], body: str) -> str:
+    return f"{parent_context}\n{body}" if parent_context else body
+
+def _language_of(document: Document) -> Optional[Language]:
+    try:
+        return Language(document.metadata.get("language"))
+    except ValueError:
+        return None
+
+def _fit(
+    items: List[Tuple[Optional[str], str, Optional[Language]]],
+    budget_tokens: int,
+    tokenizer: Optional[AbstractTokenizer],
+) -> List[Optional[str]]:
+    """Fit the ``(parent_context, body, language)`` *items* into the budget in
+    order, returning each item's possibly truncated body, or None if it was
+    dropped."""
+    tokenizer = tokenizer or HeuristicTokenizer()
+    separator_tokens = tokenizer.count_tokens(CONTEXT_SEPARATOR)
+    remaining = budget_tokens
+    included = 0
+    bodies: List[Optional[str]] = []
+
+    for parent_context, body, language in items:
+        # Every included item but the first is preceded by a separator
+        available = remaining - (separator_tokens if included else 0)
+
+        tokens = tokenizer.count_tokens(_render(parent_context, body))
+        if tokens > available:
+            def fits(text: str) -> bool:
+                return tokenizer.count_tokens(_render(parent_context, text)) <= available
+
+            if language is not None and language.line_comment is not None:
+                body = _truncate_code(body, language, fits)
+            else:
+                body = _truncate_prose(body, fits)
+            if body is None:
+                bodies.append(None)
+                continue
+            tokens = tokenizer.count_tokens(_render(parent_context, body))
+
+        remaining = available - tokens
+        included += 1
+        bodies.append(body)
+
+    return bodies
+
+def _truncate_code(body: str, language: Language, fits: Callable[[str], bool]) -> Optional[str]:
+    """Return *body* with as few lines as possible from its middle replaced by
+    a truncation marker so that it *fits*, cutting after a statement, or None
+    if not even the first line fits with the marker."""
+    lines = body.rstrip().split('\n')
+    statement_ends = language.get_boundary_detector().find_statement_ends('\n'.join(lines))
+
+    def truncated(head: int, keep_last: bool) -> str:
+        # The first *head* lines, the marker, and the last line if kept
+        tail = lines[-1:] if keep_last else []
+        omitted = len(lines) - head - len(tail)
+        indent = re.match(r'\s*', lines[head]).group()
+        marker = TRUNCATION_MARKER.format(omitted=omitted, lines="line" if omitted == 1 else "lines")
+        return '\n'.join(lines[:head] + [f"{indent}{language.line_comment} {marker}"] + tail)
+
+    # Numbers of lines to keep at the start, i.e. the first line or the lines
+    # up to the end of a statement, leaving at least one line to drop
+    heads = [
+        head for head in range(1, len(lines) - 1)
+        if head == 1 or statement_ends is None or head - 1 in statement_ends
+    ]
+    head = _last_fitting(heads, lambda head: fits(truncated(head, True)))
+    if head is not None:
+        return truncated(head, True)
+    if len(lines) > 1 and fits(truncated(1, False)):
+        return truncated(1, False)
+    return None
+
+def _truncate_prose(body: str, fits: Callable[[str], bool]) -> Optional[str]:
+    """Return the longest prefix of *body* ending at the end of a sentence or
+    line which *fits*, or None if not even the first sentence or line fits."""
+    ends = sorted(
+        {match.end() for match in _SENTENCE_END.finditer(body)}
+        | {match.start() for match in re.finditer('\n', body)}
+    )
+    prefixes = [prefix for prefix in (body[:end].rstrip() for end in ends) if prefix.strip()]
+    index = _last_fitting(list(range(len(prefixes))), lambda i: fits(prefixes[i]))
+    return prefixes[index] if index is not None else None
+
+def _last_fitting(candidates: List[int], fits: Callable[[int], bool]) -> Optional[int]:
+    """Return the last of the ascending *candidates* which *fits*, or None if
+    none does, assuming that the candidates fit up to some point.
+
+    Binary search keeps the number of token counts low, as they grow with the
+    length of the text.
+    """
+    low, high = 0, len(candidates)
+    while low < high:
+        mid = (low + high) // 2
+        if fits(candidates[mid]):
+            low = mid + 1
+        else:
+            high = mid
+    return candidates[low - 1] if low > 0 else None
@@ -0,0 +1,118 @@
+from threading import Lock
+from typing import Dict, Iterable, List, Optional
+from pipelines.abstract_tokenizer import AbstractTokenizer
+from pipelines.heuristic_tokenizer import HeuristicTokenizer
+
+# Estimated number of tokens a chat template adds to every message, e.g. for
+# the role and the delimiters
+MESSAGE_OVERHEAD_TOKENS = 4
+
+class Conversation:
+    """The message history of a multi-turn chat, which is trimmed to fit the
+    context window of the model.
+
+    A turn is a user message together with the messages following it up to the
+    next user message, e.g. the assistant's answer. Trimming evicts whole turns,
+    oldest first, but always keeps the system prompt and the latest turn.
+
+    Args:
+        system_prompt: The system prompt, if any.
+        max_tokens: The context window of the model in tokens, or None if
+        the conversation is never trimmed.
+        tokenizer: Estimates token counts, by default ``HeuristicTokenizer``.
+    """
+
+    def __init__(
+        self,
+        system_prompt: Optional[str] = None,
+        *,
+        max_tokens: Optional[int] = 8192,
+        tokenizer: Optional[AbstractTokenizer] = None,
+    ):
+        self.system_prompt = system_prompt
+        self.max_tokens = max_tokens
+        self.tokenizer = tokenizer or HeuristicTokenizer()
+        self._turns: List[List[Dict[str, str]]] = []
+        self._lock = Lock()
+
+    @classmethod
+    def from_messages(cls, messages: Iterable[Dict[str, str]], **kwargs) -> "Conversation":
+        """Create a conversation from chat messages of the form
+        ``[{"role": "user" | "assistant" | "system", "content": "..."}, ...]``,
+        where the first system message, if any, becomes the system prompt."""
+        system_prompt = kwargs.pop("system_prompt", None)
+        conversation = cls(**kwargs)
+        for message in messages:
+            if message.get("role") == "system":
+                if system_prompt is None:
+                    system_prompt = message.get("content", "")
+                continue
+            conversation.append(message["role"], message.get("content", ""))
+        conversation.system_prompt = system_prompt
+        return conversation
+
+    def append(self, role: str, content: str) -> None:
+        """Append a message, where a ``"user"`` message starts a new turn."""
+        with self._lock:
+            if role == "user" or not self._turns:
+                self._turns.append([])
+            self._turns[-1].append({"role": role, "content": content})
+
+    def add_user(self, content: str) -> None:
+        self.append("user", content)
+
+    def add_assistant(self, content: str) -> None:
+        self.append("assistant", content)
+
+    @property
+    def messages(self) -> List[Dict[str, str]]:
+        """The messages of the conversation, starting with the system prompt."""
+        with self._lock:
+            return self._messages()
+
+    @property
+    def turn_count(self) -> int:
+        with self._lock:
+            return len(self._turns)
+
+    def token_usage(self, context_tokens: int = 0) -> int:
+        """Return the estimated number of tokens of the conversation, plus
+        *context_tokens* for the retrieved context."""
+        with self._lock:
+            return self._count(self._messages()) + context_tokens
+
+    def trim(self, context_tokens: int = 0) -> int:
+        """Evict the oldest turns until the conversation plus *context_tokens*
+        fits into ``max_tokens``, or only the latest turn is left.
+
+        Returns:
+            The number of evicted turns.
+        """
+        with self._lock:
+            system_tokens = self._count(self._system_messages())
+            turn_tokens = [self._count(turn) for turn in self._turns]
+            total = system_tokens + sum(turn_tokens) + context_tokens
+
+            evicted = 0
+            while self.max_tokens is not None and total > self.max_tokens and evicted < len(self._turns) - 1:
+                total -= turn_tokens[evicted]
+                evicted += 1
+
+            del self._turns[:evicted]
+            return evicted
+
+    def prompt(self, context_tokens: int = 0) -> List[Dict[str, str]]:
+        """Trim the conversation, see ``trim``, and return its messages."""
+        self.trim(context_tokens)
+        return self.messages
+
+    def _system_messages(self) -> List[Dict[str, str]]:
+        if self.system_prompt is None:
+            return []
+        return [{"role": "system", "content": self.system_prompt}]
+
+    def _messages(self) -> List[Dict[str, str]]:
+        return self._system_messages() + [dict(message) for turn in self._turns for message in turn]
+
+    def _count(self, messages: List[Dict[str, str]]) -> int:
+        return sum(self.tokenizer.count_tokens(message["content"]) + MESSAGE_OVERHEAD_TOKENS for message in messages)
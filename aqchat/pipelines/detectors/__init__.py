@@ -1,10 +1,23 @@
 from typing import Dict
 from pipelines.detectors.boundary_detector import CodeBoundaryDetector
+from pipelines.detectors.detector_go import GoBoundaryDetector
+from pipelines.detectors.detector_javascript import JavaScriptBoundaryDetector
+from pipelines.detectors.detector_markdown import MarkdownBoundaryDetector
 from pipelines.detectors.detector_python import PythonBoundaryDetector
 from pipelines.detectors.detector_rust import RustBoundaryDetector
+from pipelines.detectors.detector_typescript import TypeScriptBoundaryDetector
+from pipelines.detectors.glue_rules import GlueRules
+from pipelines.detectors.language import Language, get_boundary_detectors
 
 __all__ = [
     "CodeBoundaryDetector",
     "PythonBoundaryDetector",
-    "RustBoundaryDetector"
+    "RustBoundaryDetector",
+    "JavaScriptBoundaryDetector",
+    "TypeScriptBoundaryDetector",
+    "GoBoundaryDetector",
+    "MarkdownBoundaryDetector",
+    "GlueRules",
+    "Language",
+    "get_boundary_detectors",
 ]
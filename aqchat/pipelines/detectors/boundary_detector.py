@@ -1,5 +1,5 @@
 from abc import ABC, abstractmethod
-from typing import List, Tuple
+from typing import Dict, List, Optional, Set, Tuple
 
 class CodeBoundaryDetector(ABC):
     """Abstract base class for detecting code boundaries in different languages."""
@@ -17,4 +17,108 @@ class CodeBoundaryDetector(ABC):
     @abstractmethod
     def get_boundary_types(self) -> List[str]:
         """Return list of boundary types this detector can identify."""
-        pass
\ No newline at end of file
+        pass
+    
+    def find_statement_ends(self, text: str) -> Optional[Set[int]]:
+        """
+        Find lines on which a statement ends, i.e. lines after which an oversized
+        chunk can be cleanly subdivided.
+
+        Returns:
+            Set of 0-based line indices, or None if the detector has no notion of
+            statements, in which case any line boundary may be used.
+        """
+        return None
+
+    
+    def extract_doc_comment(self, text: str) -> Optional[str]:
+        """
+        Extract the doc comment of the definition at the start of *text*.
+
+        Returns:
+            The doc comment with comment markers removed, or None if there is none.
+        """
+        return None
+
+    def find_heading_paths(self, text: str) -> Optional[Dict[int, List[str]]]:
+        """
+        Find the heading path of each boundary in a document, i.e. the titles of
+        the headings enclosing the boundary followed by its own heading.
+
+        Returns:
+            Dict mapping the 0-based start line of each boundary to its heading
+            path, or None if the detector has no notion of headings.
+        """
+        return None
+
+    def find_parent_contexts(self, text: str) -> Optional[Dict[int, str]]:
+        """
+        Find the context of boundaries which belong to another definition
+        without being nested inside it, e.g. Go methods, which are declared
+        apart from their receiver type.
+
+        Returns:
+            Dict mapping the 0-based start line of such boundaries to their
+            context, or None if the detector has no such boundaries.
+        """
+        return None
+
+    def extract_name(self, signature: str) -> Optional[str]:
+        """
+        Extract the name of the symbol defined by a boundary, e.g. `log_message`
+        for `pub fn log_message<B: LogBackend>(backend: &B) {`.
+
+        Args:
+            signature: The first line of the boundary's code, not counting
+            comments, attributes and decorators.
+
+        Returns:
+            The name without any generic parameters, or None if the boundary
+            does not define a single named symbol.
+        """
+        return None
+
+    def split_line_items(self, line: str) -> Optional[List[Tuple[int, int, str]]]:
+        """
+        Split a line holding several complete items, e.g. `struct A; struct B;`
+        in generated code, into the items.
+
+        Returns:
+            List of tuples (start, end, boundary_type) with the character
+            offsets of each item within *line*, or None if the line holds a
+            single item or the detector can't split lines.
+        """
+        return None
+
+    def find_unparsed_ranges(self, text: str) -> Optional[List[Tuple[int, int]]]:
+        """
+        Find the parts of malformed code which could not be parsed, e.g. a
+        function missing its closing brace, which are left out of the
+        boundaries returned by ``find_boundaries``.
+
+        Returns:
+            List of tuples (first_line, last_line) with the 0-based, inclusive
+            line range of each part, or None if the detector can't tell.
+        """
+        return None
+
+    def find_imports(self, text: str) -> Optional[List[str]]:
+        """
+        Find the top-level import statements of the file, e.g. Rust `use` or
+        Python `import` declarations.
+
+        Returns:
+            List of the import statements in source order, each normalized to a
+            single line, or None if the detector has no notion of imports.
+        """
+        return None
+
+    def find_import_lines(self, text: str) -> Optional[Set[int]]:
+        """
+        Find the lines of the top-level import statements found by ``find_imports``.
+
+        Returns:
+            Set of 0-based line indices, or None if the detector has no notion
+            of imports.
+        """
+        return None
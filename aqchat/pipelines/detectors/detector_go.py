@@ -0,0 +1,280 @@
+import re
+from typing import Dict, List, Optional, Set, Tuple
+from pipelines.detectors.boundary_detector import CodeBoundaryDetector
+from pipelines.detectors.glue_rules import GlueRules
+
+class GoBoundaryDetector(CodeBoundaryDetector):
+    """Boundary detector for Go code.
+
+    By default, the comments directly preceding a declaration are attached to
+    it. Go has no attributes, so only ``GlueRules.comments`` applies.
+    """
+
+    default_glue_rules = GlueRules(max_blank_lines=0)
+
+    def __init__(self, glue_rules: Optional[GlueRules] = None):
+        self.glue_rules = glue_rules if glue_rules is not None else self.default_glue_rules
+
+        # Functions and methods, e.g. `func (c *Client) Do(req *Request) error {`
+        self.function_pattern = re.compile(r'^func\s*(?:\((?P<receiver>[^)]*)\)\s*)?\w+')
+
+        # Struct and interface types, including generic ones
+        self.struct_pattern = re.compile(r'^type\s+(?P<name>\w+)(?:\[[^\]]*\])?\s+struct\b')
+        self.interface_pattern = re.compile(r'^type\s+(?P<name>\w+)(?:\[[^\]]*\])?\s+interface\b')
+
+        # Other type declarations and type groups, e.g. `type ID int` or `type (`
+        self.type_pattern = re.compile(r'^type\b')
+
+        # Variable and constant declarations, including `var (` groups
+        self.var_pattern = re.compile(r'^(?P<keyword>var|const)\b')
+
+        # Compiler directives such as `//go:generate`, which are not part of doc comments
+        self.directive_pattern = re.compile(r'^//(?:go:|line |export |extern )')
+
+    def find_boundaries(self, text: str) -> List[Tuple[int, int, str, int]]:
+        """Find function, method, type, var and const boundaries in Go code."""
+        return [
+            (start_line, end_line, item_type, 0)
+            for start_line, end_line, item_type, _ in self._find_declarations(text.split('\n'))
+        ]
+
+    def find_parent_contexts(self, text: str) -> Dict[int, str]:
+        """Associate methods with their receiver type.
+
+        The context of a method is the first line of the receiver type's
+        declaration (e.g. `type Client struct {`) if the type is declared in the
+        same file, or else the receiver itself (e.g. `(c *Client)`).
+        """
+        lines = text.split('\n')
+        declarations = self._find_declarations(lines)
+
+        type_lines = {}
+        for _, _, item_type, match in declarations:
+            if item_type in ('struct', 'interface'):
+                type_lines[match.group('name')] = match.string.strip()
+
+        contexts = {}
+        for start_line, _, item_type, match in declarations:
+            receiver = match.group('receiver') if item_type == 'function' else None
+            if receiver is None:
+                continue
+
+            # The receiver type is the last word, without pointer or type parameters
+            type_name = re.sub(r'\[.*\]', '', receiver).split()[-1].lstrip('*') if receiver.strip() else ''
+            contexts[start_line] = type_lines.get(type_name, f"({receiver.strip()})")
+
+        return contexts
+
+    def _find_declarations(self, lines: List[str]) -> List[Tuple[int, int, str, re.Match]]:
+        """Find the top-level declarations as (start_line, end_line, type, match)
+        tuples, where the match is for the line starting the declaration itself."""
+        codes, in_literal = self._strip_code(lines)
+        depths = self._depths(codes, '([{', ')]}')
+        declarations = []
+
+        i = 0
+        while i < len(codes):
+            if i > 0 and depths[i - 1] > 0:
+                i += 1
+                continue
+
+            match, item_type = self._match_declaration(codes[i])
+            if not match:
+                i += 1
+                continue
+
+            start_line = self._find_item_start(lines, codes, i)
+            end_line = self._find_statement_end(codes, depths, in_literal, i)
+            declarations.append((start_line, end_line, item_type, match))
+            i = end_line + 1
+
+        return declarations
+
+    def _match_declaration(self, code: str) -> Tuple[Optional[re.Match], str]:
+        """Match a top-level declaration on a line of code, returning the match and boundary type."""
+        match = self.function_pattern.match(code)
+        if match:
+            return match, 'function'
+
+        match = self.struct_pattern.match(code)
+        if match:
+            return match, 'struct'
+
+        match = self.interface_pattern.match(code)
+        if match:
+            return match, 'interface'
+
+        match = self.type_pattern.match(code)
+        if match:
+            return match, 'type'
+
+        match = self.var_pattern.match(code)
+        if match:
+            return match, match.group('keyword')
+
+        return None, ''
+
+    def extract_name(self, signature: str) -> Optional[str]:
+        """Extract the name of a function, method, type, or the single variable
+        or constant declared, e.g. `Do` for `func (c *Client) Do(req *http.Request) {`.
+        Grouped declarations such as `const (` have no name."""
+        match = re.match(r'^(?:func\s*(?:\([^)]*\)\s*)?|(?:type|var|const)\s+)(\w+)', signature)
+        return match.group(1) if match else None
+
+    def _strip_code(self, lines: List[str]) -> Tuple[List[str], List[bool]]:
+        """Remove comments and blank out the contents of string, rune and raw string literals.
+
+        Returns:
+            The lines with the same length as the input, so that column positions
+            are preserved, but with only code remaining, and whether each line
+            ends inside a raw string or block comment.
+        """
+        mode = None  # None for code, otherwise '/*' or '`'
+        codes = []
+        in_literal = []
+
+        for line in lines:
+            code = []
+            j = 0
+            while j < len(line):
+                char = line[j]
+
+                if mode == '/*':
+                    if line.startswith('*/', j):
+                        mode = None
+                        code.append('  ')
+                        j += 2
+                    else:
+                        code.append(' ')
+                        j += 1
+                    continue
+
+                if mode == '`':
+                    # Raw strings may span lines and have no escapes
+                    if char == '`':
+                        mode = None
+                        code.append(char)
+                    else:
+                        code.append(' ')
+                    j += 1
+                    continue
+
+                if line.startswith('//', j):
+                    break
+                if line.startswith('/*', j):
+                    mode = '/*'
+                    code.append('  ')
+                    j += 2
+                    continue
+                if char == '`':
+                    mode = '`'
+                    code.append(char)
+                    j += 1
+                    continue
+                if char in '"\'':
+                    # Interpreted string or rune literal, skip to the closing quote
+                    k = j + 1
+                    while k < len(line) and line[k] != char:
+                        if line[k] == '\\':
+                            k += 1
+                        k += 1
+                    code.append(char + ' ' * (min(k, len(line)) - j - 1))
+                    if k < len(line):
+                        code.append(char)
+                    j = k + 1
+                    continue
+
+                code.append(char)
+                j += 1
+
+            codes.append(''.join(code).rstrip())
+            in_literal.append(mode is not None)
+
+        return codes, in_literal
+
+    def _depths(self, codes: List[str], opening: str, closing: str) -> List[int]:
+        """Return the bracket depth at the end of each line."""
+        depth = 0
+        depths = []
+        for code in codes:
+            for char in code:
+                if char in opening:
+                    depth += 1
+                elif char in closing:
+                    depth = max(depth - 1, 0)
+            depths.append(depth)
+        return depths
+
+    def _find_item_start(self, lines: List[str], codes: List[str], item_line: int) -> int:
+        """Find the actual start of a declaration including its doc comment,
+        which by Go convention directly precedes it without a blank line."""
+        glue = self.glue_rules
+        start_line = item_line
+        blank_lines = 0
+
+        i = item_line - 1
+        while i >= 0 and glue.comments and not codes[i].strip():
+            if lines[i].strip():
+                start_line = i
+                blank_lines = 0
+            else:
+                blank_lines += 1
+                if not glue.allows_gap(blank_lines):
+                    break
+            i -= 1
+
+        return start_line
+
+    def _find_statement_end(self, codes: List[str], depths: List[int], in_literal: List[bool], start_line: int) -> int:
+        """Find the last line of the declaration starting at *start_line*, i.e.
+        the first line closing all of its brackets and literals which does not
+        end with an operator continuing the expression."""
+        for i in range(start_line, len(codes)):
+            code = codes[i].strip()
+            if not code or depths[i] > 0 or in_literal[i]:
+                continue
+            if code.endswith(('=', ',', '+', '-', '*', '/', '%', '&', '|', '^', '<', '>', '!', '.', ':')):
+                continue
+            return i
+
+        return len(codes) - 1
+
+    def find_statement_ends(self, text: str) -> Set[int]:
+        """Find lines ending a statement, block, block opener or list item
+        outside of any parentheses or brackets."""
+        lines = text.split('\n')
+        codes, in_literal = self._strip_code(lines)
+        paren_depths = self._depths(codes, '([', ')]')
+
+        statement_ends = set()
+        for i, code in enumerate(codes):
+            stripped = code.strip()
+            if in_literal[i]:
+                continue
+            if stripped == '':
+                statement_ends.add(i)
+            elif paren_depths[i] == 0 and not stripped.endswith(('=', '+', '-', '*', '/', '%', '&', '|', '^', '.', '!')):
+                statement_ends.add(i)
+
+        return statement_ends
+
+    def extract_doc_comment(self, text: str) -> Optional[str]:
+        """Extract the `//` comment lines directly preceding the declaration at
+        the start of *text*, leaving out compiler directives."""
+        doc_lines = []
+
+        for line in text.split('\n'):
+            stripped = line.strip()
+            if not stripped.startswith('//'):
+                break
+            if self.directive_pattern.match(stripped):
+                continue
+
+            content = stripped[2:]
+            doc_lines.append(content[1:] if content.startswith(' ') else content)
+
+        doc = '\n'.join(doc_lines).strip()
+        return doc or None
+
+    def get_boundary_types(self) -> List[str]:
+        return ['function', 'struct', 'interface', 'type', 'var', 'const']
@@ -0,0 +1,352 @@
+import re
+from typing import List, Optional, Set, Tuple
+from pipelines.detectors.boundary_detector import CodeBoundaryDetector
+from pipelines.detectors.glue_rules import GlueRules
+
+class JavaScriptBoundaryDetector(CodeBoundaryDetector):
+    """Boundary detector for JavaScript code, including JSX.
+
+    By default, the comments and decorators directly preceding a declaration
+    are attached to it.
+    """
+
+    # Modifiers which may precede the name of a class member
+    member_modifiers = ['static', 'async', 'get', 'set']
+
+    default_glue_rules = GlueRules(max_blank_lines=0)
+
+    def __init__(self, glue_rules: Optional[GlueRules] = None):
+        self.glue_rules = glue_rules if glue_rules is not None else self.default_glue_rules
+        export_prefix = r'^(\s*)(?:export\s+(?:default\s+)?)?'
+
+        # Function declarations, including async, generator and anonymous default exports
+        self.function_pattern = re.compile(export_prefix + r'(?:async\s+)?function\b')
+
+        # Class declarations
+        self.class_pattern = re.compile(export_prefix + r'(?:abstract\s+)?class\b')
+
+        # Variables initialized with an arrow function or function expression,
+        # e.g. `const handler = async (event) => {`
+        self.arrow_pattern = re.compile(
+            export_prefix + r'(?:const|let|var)\s+[\w$]+[^=]*=\s*(?:async\s+)?(?:function\b|\(|<|[\w$]+\s*=>)'
+        )
+
+        modifiers = r'(?:(?:' + '|'.join(self.member_modifiers) + r')\s+)*'
+
+        # Class methods, e.g. `static async load(path) {`
+        self.method_pattern = re.compile(r'^(\s*)' + modifiers + r'\*?\s*#?[\w$]+\s*(?:<[^>]*>)?\s*\(')
+
+        # Class fields initialized with an arrow function, e.g. `onClick = () => {`
+        self.field_arrow_pattern = re.compile(
+            r'^(\s*)' + modifiers + r'#?[\w$]+[^=(]*=\s*(?:async\s+)?(?:\(|<|[\w$]+\s*=>)'
+        )
+
+    def find_boundaries(self, text: str) -> List[Tuple[int, int, str, int]]:
+        """Find function, class and method boundaries in JavaScript code."""
+        lines = text.split('\n')
+        codes = self._strip_code(lines)
+        depths = self._depths(codes, '([{', ')]}')
+        boundaries = []
+
+        # Stack of (end_line, member_depth) for the classes enclosing the current line
+        classes: List[Tuple[int, int]] = []
+
+        for i, code in enumerate(codes):
+            if not code.strip():
+                continue
+
+            while classes and classes[-1][0] < i:
+                classes.pop()
+
+            depth = depths[i - 1] if i > 0 else 0
+            match = None
+
+            if classes and depth == classes[-1][1]:
+                # Class members
+                match = self.method_pattern.match(code)
+                if match:
+                    item_type = 'function'
+                else:
+                    match = self._match_arrow(self.field_arrow_pattern, codes, depths, i)
+                    item_type = 'function'
+            elif depth == 0:
+                # Top-level declarations; anything nested in a function body
+                # is part of that function's chunk
+                match, item_type = self._match_declaration(codes, depths, i)
+
+            if not match:
+                continue
+
+            indent_level = len(match.group(1))
+            start_line = self._find_item_start(lines, codes, depths, i)
+            requires_block = not self._is_arrow(codes, depths, i)
+            end_line = self._find_statement_end(codes, depths, i, requires_block)
+            boundaries.append((start_line, end_line, item_type, indent_level))
+
+            if item_type == 'class':
+                classes.append((end_line, depth + 1))
+
+        return boundaries
+
+    def _match_declaration(self, codes: List[str], depths: List[int], i: int) -> Tuple[Optional[re.Match], str]:
+        """Match a top-level declaration on line *i*, returning the match and boundary type."""
+        code = codes[i]
+
+        match = self.class_pattern.match(code)
+        if match:
+            return match, 'class'
+
+        match = self.function_pattern.match(code)
+        if match:
+            return match, 'function'
+
+        match = self._match_arrow(self.arrow_pattern, codes, depths, i)
+        return match, 'function'
+
+    def _match_arrow(self, pattern: re.Pattern, codes: List[str], depths: List[int], i: int) -> Optional[re.Match]:
+        """Match *pattern* on line *i*, but only if the statement is really a function,
+        so that e.g. `const total = (a + b) * 2;` is not mistaken for one."""
+        match = pattern.match(codes[i])
+        if match and self._is_arrow(codes, depths, i):
+            return match
+        return None
+
+    def _is_arrow(self, codes: List[str], depths: List[int], i: int) -> bool:
+        """Return True if the statement starting at line *i* is an arrow function
+        or function expression assignment."""
+        if self.function_pattern.match(codes[i]) or self.class_pattern.match(codes[i]):
+            return False
+
+        end_line = self._find_statement_end(codes, depths, i, requires_block=False)
+        statement = '\n'.join(codes[i:end_line + 1])
+        return '=>' in statement or re.search(r'=\s*(?:async\s+)?function\b', statement) is not None
+
+    def _strip_code(self, lines: List[str]) -> List[str]:
+        """Remove comments and blank out the contents of string and template literals.
+
+        Returns:
+            The lines with the same length as the input, so that column positions
+            and indentation are preserved, but with only code remaining.
+        """
+        mode = None  # None for code, otherwise '/*' or '`'
+        template_depth = 0  # brace depth inside a `${...}` template expression
+        codes = []
+
+        for line in lines:
+            code = []
+            j = 0
+            while j < len(line):
+                char = line[j]
+
+                if mode == '/*':
+                    if line.startswith('*/', j):
+                        mode = None
+                        code.append('  ')
+                        j += 2
+                    else:
+                        code.append(' ')
+                        j += 1
+                    continue
+
+                if mode == '`':
+                    if template_depth > 0:
+                        # Inside a template expression, only track braces
+                        if char == '{':
+                            template_depth += 1
+                        elif char == '}':
+                            template_depth -= 1
+                    elif char == '\\':
+                        code.append(' ')
+                        j += 1
+                    elif line.startswith('${', j):
+                        template_depth = 1
+                        code.append(' ')
+                        j += 1
+                    elif char == '`':
+                        mode = None
+                        code.append(char)
+                        j += 1
+                        continue
+                    code.append(' ')
+                    j += 1
+                    continue
+
+                if line.startswith('//', j):
+                    break
+                if line.startswith('/*', j):
+                    mode = '/*'
+                    code.append('  ')
+                    j += 2
+                    continue
+                if char == '`':
+                    mode = '`'
+                    code.append(char)
+                    j += 1
+                    continue
+                if char in '"\'':
+                    # Single-line string literal, skip to the closing quote. Unclosed
+                    # quotes (e.g. an apostrophe in JSX text) end at the end of the line.
+                    k = j + 1
+                    while k < len(line) and line[k] != char:
+                        if line[k] == '\\':
+                            k += 1
+                        k += 1
+                    code.append(char + ' ' * (min(k, len(line)) - j - 1))
+                    if k < len(line):
+                        code.append(char)
+                    j = k + 1
+                    continue
+
+                code.append(char)
+                j += 1
+
+            codes.append(''.join(code).rstrip())
+
+        return codes
+
+    def _depths(self, codes: List[str], opening: str, closing: str) -> List[int]:
+        """Return the bracket depth at the end of each line."""
+        depth = 0
+        depths = []
+        for code in codes:
+            for char in code:
+                if char in opening:
+                    depth += 1
+                elif char in closing:
+                    depth = max(depth - 1, 0)
+            depths.append(depth)
+        return depths
+
+    def _find_item_start(self, lines: List[str], codes: List[str], depths: List[int], item_line: int) -> int:
+        """Find the actual start of a declaration including its leading comments
+        and decorators, as far as ``glue_rules`` allow. By default, a blank
+        line ends the search."""
+        glue = self.glue_rules
+        start_line = item_line
+        base_depth = depths[item_line - 1] if item_line > 0 else 0
+        blank_lines = 0
+
+        i = item_line - 1
+        while i >= 0:
+            stripped = lines[i].strip()
+            if stripped == '':
+                blank_lines += 1
+                if not glue.allows_gap(blank_lines):
+                    break
+                i -= 1
+                continue
+
+            # Comment lines, including those inside a `/* */` block
+            if codes[i].strip() == '':
+                if not glue.comments:
+                    break
+                start_line = i
+                blank_lines = 0
+                i -= 1
+                continue
+
+            # Decorators, which may span several lines
+            decorator_start = i
+            while decorator_start > 0 and depths[decorator_start - 1] > base_depth:
+                decorator_start -= 1
+            if glue.attributes and codes[decorator_start].strip().startswith('@'):
+                start_line = decorator_start
+                blank_lines = 0
+                i = decorator_start - 1
+                continue
+
+            break
+
+        return start_line
+
+    def _find_statement_end(self, codes: List[str], depths: List[int], start_line: int, requires_block: bool) -> int:
+        """Find the last line of the statement or declaration starting at *start_line*.
+
+        If *requires_block* is set, the statement is only complete once it has a
+        `{ }` body or is terminated by a semicolon, e.g. an overload signature.
+        """
+        base_depth = depths[start_line - 1] if start_line > 0 else 0
+        seen_block = False
+
+        for i in range(start_line, len(codes)):
+            code = codes[i].strip()
+            if '{' in code:
+                seen_block = True
+            if not code or depths[i] > base_depth:
+                continue
+
+            if requires_block and not seen_block and not code.endswith(';'):
+                continue
+
+            # The statement continues if the line ends with an operator, or the
+            # next line starts with one (e.g. a method chain)
+            if code.endswith(('=>', '=', ',', '(', '+', '-', '*', '/', '&&', '||', '?', ':', '|', '&', '.')):
+                continue
+            next_code = next((c.strip() for c in codes[i + 1:] if c.strip()), '')
+            if next_code.startswith(('.', '?.', '?', ':', '=>', '+', '-', '*', '&&', '||', ')', ']')):
+                continue
+            if next_code.startswith(('|', '&')) and not code.endswith((';', '}')):
+                continue
+
+            return i
+
+        return len(codes) - 1
+
+    def extract_name(self, signature: str) -> Optional[str]:
+        """Extract the name of a declaration or class member, e.g. `push` for
+        `async push(task: Task): Promise<void> {`. Anonymous default exports
+        have no name."""
+        declaration = re.sub(r'^\s*(?:export\s+(?:default\s+)?)?(?:declare\s+)?', '', signature)
+
+        match = re.match(
+            r'(?:async\s+)?function\s*\*?\s*([\w$]+)'
+            r'|(?:(?:abstract\s+)?class|interface|(?:const\s+)?enum|type|const|let|var)\s+([\w$]+)',
+            declaration,
+        )
+        if match:
+            return match.group(1) or match.group(2)
+        if re.match(r'(?:async\s+)?(?:function|class)\b', declaration):
+            return None
+
+        # Class members, after any modifiers
+        modifiers = r'(?:(?:' + '|'.join(self.member_modifiers) + r')\s+)*'
+        match = re.match(modifiers + r'\*?\s*(#?[\w$]+)', declaration)
+        return match.group(1) if match else None
+
+    def find_statement_ends(self, text: str) -> Set[int]:
+        """Find lines ending a statement, block, block opener or list item
+        outside of any parentheses or brackets."""
+        lines = text.split('\n')
+        codes = self._strip_code(lines)
+        paren_depths = self._depths(codes, '([', ')]')
+
+        statement_ends = set()
+        for i, code in enumerate(codes):
+            stripped = code.strip()
+            if stripped == '' or (paren_depths[i] == 0 and stripped[-1] in ';{},'):
+                statement_ends.add(i)
+
+        return statement_ends
+
+    def extract_doc_comment(self, text: str) -> Optional[str]:
+        """Extract the last `/** */` doc comment before the declaration at the start of *text*."""
+        lines = text.split('\n')
+        codes = self._strip_code(lines)
+
+        # The doc comment must come before the first line of code, not counting decorators
+        leading = []
+        for line, code in zip(lines, codes):
+            if code.strip() and not code.strip().startswith('@'):
+                break
+            leading.append(line)
+
+        comments = re.findall(r'/\*\*(.*?)\*/', '\n'.join(leading), re.DOTALL)
+        if not comments:
+            return None
+
+        doc_lines = [re.sub(r'^\s*\*? ?', '', line) for line in comments[-1].split('\n')]
+        doc = '\n'.join(doc_lines).strip()
+        return doc or None
+
+    def get_boundary_types(self) -> List[str]:
+        return ['function', 'class']
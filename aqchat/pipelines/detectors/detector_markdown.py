@@ -0,0 +1,126 @@
+import re
+from typing import Dict, List, Optional, Set, Tuple
+from pipelines.detectors.boundary_detector import CodeBoundaryDetector
+
+class MarkdownBoundaryDetector(CodeBoundaryDetector):
+    """Boundary detector for Markdown documents, splitting on ATX headings."""
+
+    def __init__(self):
+        # ATX headings, e.g. `## Installation` or `## Installation ##`
+        self.heading_pattern = re.compile(r'^ {0,3}(#{1,6})(?:[ \t]+(.*?))?(?:[ \t]+#+)?[ \t]*$')
+
+        # Opening and closing lines of fenced code blocks
+        self.fence_pattern = re.compile(r'^ {0,3}(`{3,}|~{3,})')
+
+    def find_boundaries(self, text: str) -> List[Tuple[int, int, str, int]]:
+        """Find the sections of a Markdown document.
+
+        Each section spans its heading and everything up to the next heading of
+        the same or a higher level, so sections contain their subsections. The
+        indent level of a section is its heading level minus one.
+        """
+        lines = text.split('\n')
+        headings = self._find_headings(lines)
+        boundaries = []
+
+        for index, (line, level, _) in enumerate(headings):
+            end_line = len(lines) - 1
+            for next_line, next_level, _ in headings[index + 1:]:
+                if next_level <= level:
+                    end_line = next_line - 1
+                    break
+
+            # Leave out blank lines before the next heading
+            while end_line > line and not lines[end_line].strip():
+                end_line -= 1
+
+            boundaries.append((line, end_line, 'section', level - 1))
+
+        return boundaries
+
+    def _find_headings(self, lines: List[str]) -> List[Tuple[int, int, str]]:
+        """Find the (line, level, title) of each heading outside of fenced code blocks."""
+        in_fence = self._find_fenced_lines(lines)
+        headings = []
+
+        for i, line in enumerate(lines):
+            if in_fence[i]:
+                continue
+            match = self.heading_pattern.match(line)
+            if match:
+                headings.append((i, len(match.group(1)), (match.group(2) or '').strip()))
+
+        return headings
+
+    def _find_fenced_lines(self, lines: List[str]) -> List[bool]:
+        """Return whether each line is part of a fenced code block, including the fences."""
+        fence = None  # opening fence of the current code block
+        in_fence = []
+
+        for line in lines:
+            match = self.fence_pattern.match(line)
+            if fence is None:
+                if match:
+                    fence = match.group(1)
+                in_fence.append(fence is not None)
+            else:
+                in_fence.append(True)
+                # The closing fence uses the same character and is at least as long
+                if match and match.group(1)[0] == fence[0] and len(match.group(1)) >= len(fence) \
+                        and not line.strip()[len(match.group(1)):].strip():
+                    fence = None
+
+        return in_fence
+
+    def find_statement_ends(self, text: str) -> Set[int]:
+        """Find lines after which a section can be cut, i.e. the ends of
+        paragraphs and other blocks. Fenced code blocks are never cut, and
+        headings are never separated from the text following them."""
+        lines = text.split('\n')
+        in_fence = self._find_fenced_lines(lines)
+
+        statement_ends = set()
+        after_heading = False  # whether the last non-blank line was a heading
+        for i, line in enumerate(lines):
+            is_last = i + 1 == len(lines)
+            if in_fence[i]:
+                after_heading = False
+                # Only after the closing fence
+                if is_last or not in_fence[i + 1]:
+                    statement_ends.add(i)
+            elif self.heading_pattern.match(line):
+                # Keep headings together with the text following them
+                after_heading = True
+            elif not line.strip():
+                if not after_heading:
+                    statement_ends.add(i)
+            else:
+                after_heading = False
+                if is_last or not lines[i + 1].strip() or in_fence[i + 1] or self.heading_pattern.match(lines[i + 1]):
+                    statement_ends.add(i)
+
+        return statement_ends
+
+    def find_heading_paths(self, text: str) -> Dict[int, List[str]]:
+        """Find the heading path of each section, e.g. `["Installation", "From source"]`."""
+        lines = text.split('\n')
+        heading_paths = {}
+        enclosing: List[Tuple[int, str]] = []  # (level, title) of the enclosing headings
+
+        for line, level, title in self._find_headings(lines):
+            while enclosing and enclosing[-1][0] >= level:
+                enclosing.pop()
+            enclosing.append((level, title))
+            heading_paths[line] = [title for _, title in enclosing]
+
+        return heading_paths
+
+    def extract_name(self, signature: str) -> Optional[str]:
+        """Extract the title of a section's heading."""
+        match = self.heading_pattern.match(signature)
+        if not match:
+            return None
+        return (match.group(2) or '').strip() or None
+
+    def get_boundary_types(self) -> List[str]:
+        return ['section']
@@ -1,53 +1,294 @@
+import inspect
 import re
-from typing import List, Tuple
+from typing import List, Optional, Set, Tuple
 from pipelines.detectors.boundary_detector import CodeBoundaryDetector
+from pipelines.detectors.glue_rules import GlueRules
 
 class PythonBoundaryDetector(CodeBoundaryDetector):
-    """Boundary detector for Python code."""
-    
-    def __init__(self):
-        self.class_pattern = re.compile(r'^(\s*)class\s+\w+.*?:')
-        self.function_pattern = re.compile(r'^(\s*)def\s+\w+.*?:')
-        self.async_function_pattern = re.compile(r'^(\s*)async\s+def\s+\w+.*?:')
-    
+    """Boundary detector for Python code.
+
+    By default, only decorators directly preceding a definition are attached
+    to it, as Python documents definitions with docstrings rather than comments.
+    """
+
+    default_glue_rules = GlueRules(comments=False, max_blank_lines=0)
+
+    def __init__(self, glue_rules: Optional[GlueRules] = None):
+        self.glue_rules = glue_rules if glue_rules is not None else self.default_glue_rules
+        self.class_pattern = re.compile(r'^(\s*)class\s+\w+')
+        self.function_pattern = re.compile(r'^(\s*)(?:async\s+)?def\s+\w+')
+        self.import_pattern = re.compile(r'^(?:import|from)\s+')
+        self.name_pattern = re.compile(r'^\s*(?:(?:async\s+)?def|class)\s+(\w+)|^([A-Za-z_]\w*)')
+
+        # Module-level assignments, including annotated and tuple assignments.
+        # The negative lookahead excludes comparisons such as `x == y`.
+        self.assignment_pattern = re.compile(r'^[A-Za-z_]\w*(?:\s*,\s*[A-Za-z_]\w*)*\s*(?::[^=]+)?=(?!=)')
+
     def find_boundaries(self, text: str) -> List[Tuple[int, int, str, int]]:
-        """Find class and function boundaries in Python code."""
+        """Find class, function and module-level assignment boundaries in Python code."""
         lines = text.split('\n')
+        starts_in_string, depths = self._scan(lines)
         boundaries = []
-        
+
+        # Stack of (end_line, boundary_type) for the blocks enclosing the current line
+        scopes: List[Tuple[int, str]] = []
+
         for i, line in enumerate(lines):
-            # Check for class definition
+            # Skip anything inside a multi-line string or bracket continuation,
+            # e.g. a `def` mentioned in a docstring
+            if starts_in_string[i] or (i > 0 and depths[i - 1] > 0):
+                continue
+
+            while scopes and scopes[-1][0] < i:
+                scopes.pop()
+
             class_match = self.class_pattern.match(line)
-            if class_match:
-                indent_level = len(class_match.group(1))
-                end_line = self._find_block_end(lines, i, indent_level)
-                boundaries.append((i, end_line, 'class', indent_level))
-            
-            # Check for function definition (including async)
-            func_match = self.function_pattern.match(line) or self.async_function_pattern.match(line)
-            if func_match:
-                indent_level = len(func_match.group(1))
-                end_line = self._find_block_end(lines, i, indent_level)
-                boundaries.append((i, end_line, 'function', indent_level))
-        
+            func_match = self.function_pattern.match(line)
+            assign_match = self.assignment_pattern.match(line)
+
+            if class_match or func_match:
+                # A def or class nested inside a function is part of that
+                # function's body, so it is never promoted to its own chunk.
+                if any(scope_type == 'function' for _, scope_type in scopes):
+                    continue
+
+                item_type = 'class' if class_match else 'function'
+                indent_level = len((class_match or func_match).group(1))
+                start_line = self._find_item_start(lines, i, indent_level, depths)
+                header_end = self._find_statement_end(lines, i, starts_in_string, depths)
+                end_line = self._find_block_end(lines, header_end, indent_level, starts_in_string, depths)
+                boundaries.append((start_line, end_line, item_type, indent_level))
+                scopes.append((end_line, item_type))
+
+            elif assign_match and not scopes:
+                end_line = self._find_statement_end(lines, i, starts_in_string, depths)
+                boundaries.append((i, end_line, 'assignment', 0))
+
         return boundaries
-    
-    def _find_block_end(self, lines: List[str], start_line: int, base_indent: int) -> int:
-        """Find the end of a code block based on indentation."""
-        for i in range(start_line + 1, len(lines)):
+
+    def _scan(self, lines: List[str]) -> Tuple[List[bool], List[int]]:
+        """Scan the source for string literals and brackets.
+
+        Returns:
+            Two lists with one entry per line: whether the line starts inside a
+            triple-quoted string, and the bracket depth at the end of the line.
+        """
+        in_string = None  # delimiter of the currently open triple-quoted string
+        depth = 0
+        starts_in_string = []
+        depths = []
+
+        for line in lines:
+            starts_in_string.append(in_string is not None)
+            j = 0
+            while j < len(line):
+                if in_string:
+                    if line.startswith(in_string, j):
+                        j += 3
+                        in_string = None
+                    elif line[j] == '\\':
+                        j += 2
+                    else:
+                        j += 1
+                    continue
+
+                char = line[j]
+                if char == '#':
+                    break
+                if line.startswith('"""', j) or line.startswith("'''", j):
+                    in_string = line[j:j + 3]
+                    j += 3
+                    continue
+                if char in '"\'':
+                    # Single-line string literal, skip to the closing quote
+                    k = j + 1
+                    while k < len(line) and line[k] != char:
+                        if line[k] == '\\':
+                            k += 1
+                        k += 1
+                    j = k + 1
+                    continue
+                if char in '([{':
+                    depth += 1
+                elif char in ')]}':
+                    depth = max(depth - 1, 0)
+                j += 1
+
+            depths.append(depth)
+
+        return starts_in_string, depths
+
+    def _find_item_start(self, lines: List[str], item_line: int, indent_level: int, depths: List[int]) -> int:
+        """Find the actual start of a definition including its decorators, which
+        may span several lines, and depending on ``glue_rules``, its leading
+        comments."""
+        glue = self.glue_rules
+        start_line = item_line
+        blank_lines = 0
+
+        i = item_line - 1
+        while i >= 0:
+            stripped = lines[i].strip()
+            if stripped == '':
+                blank_lines += 1
+                if not glue.allows_gap(blank_lines):
+                    break
+                i -= 1
+                continue
+
+            # The first line of a decorator continued in brackets, e.g. `@app.route(`
+            decorator_start = i
+            while decorator_start > 0 and depths[decorator_start - 1] > 0:
+                decorator_start -= 1
+
+            line = lines[decorator_start]
+            stripped = line.strip()
+            same_indent = len(line) - len(line.lstrip()) == indent_level
+            if same_indent and ((glue.attributes and stripped.startswith('@')) or (glue.comments and stripped.startswith('#'))):
+                start_line = decorator_start
+                blank_lines = 0
+                i = decorator_start - 1
+                continue
+            break
+
+        return start_line
+
+    def _find_statement_end(self, lines: List[str], start_line: int, starts_in_string: List[bool], depths: List[int]) -> int:
+        """Find the last line of the (possibly multi-line) statement starting at *start_line*."""
+        for i in range(start_line, len(lines)):
+            continued = (
+                depths[i] > 0
+                or lines[i].rstrip().endswith('\\')
+                or (i + 1 < len(lines) and starts_in_string[i + 1])
+            )
+            if not continued:
+                return i
+
+        return len(lines) - 1
+
+    def _find_block_end(self, lines: List[str], header_end: int, base_indent: int, starts_in_string: List[bool], depths: List[int]) -> int:
+        """Find the end of a code block based on indentation.
+
+        Trailing blank lines are not included in the block. Comments that are
+        dedented to the level of the block header only end the block if no
+        further body lines follow them.
+        """
+        last_line = header_end
+
+        for i in range(header_end + 1, len(lines)):
             line = lines[i]
-            if line.strip() == '':  # Skip empty lines
+
+            # Lines inside a string literal or bracket continuation belong to
+            # the block regardless of their indentation
+            if starts_in_string[i] or depths[i - 1] > 0:
+                last_line = i
+                continue
+
+            stripped = line.strip()
+            if stripped == '':  # Skip empty lines
                 continue
-            
+
             # Calculate current line's indentation
             current_indent = len(line) - len(line.lstrip())
-            
+
             # If we find a line with same or less indentation, the block has ended
             if current_indent <= base_indent:
-                return i - 1
-        
-        # If we reach the end of file, return the last line
-        return len(lines) - 1
-    
+                if stripped.startswith('#'):
+                    continue
+                break
+
+            last_line = i
+
+        return last_line
+
+    def find_statement_ends(self, text: str) -> Set[int]:
+        """Find lines which are not continued by a bracket, backslash or string literal."""
+        lines = text.split('\n')
+        starts_in_string, depths = self._scan(lines)
+        return {
+            i for i in range(len(lines))
+            if self._find_statement_end(lines, i, starts_in_string, depths) == i
+        }
+
+    def extract_name(self, signature: str) -> Optional[str]:
+        """Extract the name of a class, function or the first target of an assignment."""
+        match = self.name_pattern.match(signature)
+        if not match:
+            return None
+        return match.group(1) or match.group(2)
+
+    def find_imports(self, text: str) -> List[str]:
+        """Find the unindented `import` and `from ... import` statements,
+        including ones continued by parentheses or backslashes."""
+        lines = text.split('\n')
+        imports = []
+
+        for first, last in self._find_import_ranges(lines):
+            parts = [line.split('#', 1)[0].strip().rstrip('\\').strip() for line in lines[first:last + 1]]
+            statement = ' '.join(part for part in parts if part)
+            statement = re.sub(r'\(\s+', '(', statement)
+            statement = re.sub(r',?\s+\)', ')', statement)
+            imports.append(statement)
+
+        return imports
+
+    def find_import_lines(self, text: str) -> Set[int]:
+        return {
+            i for first, last in self._find_import_ranges(text.split('\n'))
+            for i in range(first, last + 1)
+        }
+
+    def _find_import_ranges(self, lines: List[str]) -> List[Tuple[int, int]]:
+        """Find the (first, last) lines of each unindented import statement."""
+        starts_in_string, depths = self._scan(lines)
+        ranges = []
+        i = 0
+
+        while i < len(lines):
+            if starts_in_string[i] or not self.import_pattern.match(lines[i]):
+                i += 1
+                continue
+
+            end_line = self._find_statement_end(lines, i, starts_in_string, depths)
+            ranges.append((i, end_line))
+            i = end_line + 1
+
+        return ranges
+
+    def extract_doc_comment(self, text: str) -> Optional[str]:
+        """Extract the docstring of the class or function at the start of *text*,
+        or the module docstring if *text* does not start with a definition."""
+        lines = text.split('\n')
+        starts_in_string, depths = self._scan(lines)
+
+        # Skip decorators, comments and blank lines to find the first statement
+        i = 0
+        while i < len(lines) and (not lines[i].strip() or lines[i].strip().startswith(('@', '#'))):
+            i += 1
+        if i == len(lines):
+            return None
+
+        if self.class_pattern.match(lines[i]) or self.function_pattern.match(lines[i]):
+            # The docstring is the first statement after the header
+            i = self._find_statement_end(lines, i, starts_in_string, depths) + 1
+            while i < len(lines) and not lines[i].strip():
+                i += 1
+            if i == len(lines):
+                return None
+
+        match = re.match(r'^\s*[rRuU]?("""|\'\'\')', lines[i])
+        if not match:
+            return None
+
+        quote = match.group(1)
+        end_line = self._find_statement_end(lines, i, starts_in_string, depths)
+        literal = '\n'.join(lines[i:end_line + 1]).strip()
+
+        # Remove the opening and closing quotes
+        body = literal[literal.index(quote) + 3:]
+        if body.endswith(quote):
+            body = body[:-3]
+        return inspect.cleandoc(body)
+
     def get_boundary_types(self) -> List[str]:
-        return ['class', 'function']
\ No newline at end of file
+        return ['class', 'function', 'assignment']
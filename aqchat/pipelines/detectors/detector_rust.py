@@ -1,45 +1,196 @@
 import re
-from typing import List, Tuple
+from typing import List, Optional, Set, Tuple
 from pipelines.detectors.boundary_detector import CodeBoundaryDetector
+from pipelines.detectors.glue_rules import GlueRules
+
+# Opening and closing delimiters of block comments
+_BLOCK_COMMENT_DELIMITER = re.compile(r'/\*|\*/')
+
+# Character literals, unlike lifetimes such as `'a`
+_CHAR_LITERAL = re.compile(r"'(?:\\u\{[0-9a-fA-F_]*\}|\\x[0-9a-fA-F]{2}|\\.|[^\\'])'")
+
+# Start of raw string literals, e.g. `r#"` or `br"`, with the hashes to close them
+_RAW_STRING_START = re.compile(r'[bc]?r(#*)"')
+
+def _mask_comments(lines: List[str]) -> Tuple[List[str], List[int]]:
+    """Replace the line comments and (possibly nested) block comments in
+    *lines* with spaces, keeping the columns of the code.
+
+    The contents of string, raw string and character literals are replaced
+    with spaces as well, keeping their delimiters, so that e.g. `"{"` doesn't
+    open a block and `"/*"` doesn't start a comment. String literals may span
+    several lines.
+
+    Returns:
+        The masked lines, and the nesting depth of block comments at the end
+        of each line, which is 0 outside of block comments.
+    """
+    masked = []
+    depths = []
+    depth = 0
+    string_end = None  # The closing delimiter of the string literal being masked
+
+    for line in lines:
+        chars = list(line)
+        j = 0
+        while j < len(line):
+            if string_end is not None:
+                if string_end == '"' and line[j] == '\\':
+                    # Escaped character, e.g. `\"`
+                    chars[j:j + 2] = ' ' * len(chars[j:j + 2])
+                    j += 2
+                elif line.startswith(string_end, j):
+                    j += len(string_end)
+                    string_end = None
+                else:
+                    chars[j] = ' '
+                    j += 1
+            elif depth > 0:
+                if line.startswith('/*', j):
+                    depth += 1
+                elif line.startswith('*/', j):
+                    depth -= 1
+                else:
+                    chars[j] = ' '
+                    j += 1
+                    continue
+                chars[j] = chars[j + 1] = ' '
+                j += 2
+            elif line.startswith('//', j):
+                chars[j:] = ' ' * (len(line) - j)
+                break
+            elif line.startswith('/*', j):
+                depth += 1
+                chars[j] = chars[j + 1] = ' '
+                j += 2
+            else:
+                # Raw strings start with a prefix, unless it ends an identifier
+                raw = _RAW_STRING_START.match(line, j) if j == 0 or not _is_identifier_char(line[j - 1]) else None
+                char = _CHAR_LITERAL.match(line, j) if line[j] == "'" else None
+                if raw:
+                    string_end = '"' + raw.group(1)
+                    j = raw.end()
+                elif line[j] == '"':
+                    string_end = '"'
+                    j += 1
+                elif char:
+                    chars[j + 1:char.end() - 1] = ' ' * (char.end() - j - 2)
+                    j = char.end()
+                else:
+                    j += 1
+
+        masked.append(''.join(chars))
+        depths.append(depth)
+
+    return masked, depths
+
+def _is_identifier_char(char: str) -> bool:
+    return char.isalnum() or char == '_'
 
 class RustBoundaryDetector(CodeBoundaryDetector):
-    """Boundary detector for Rust code."""
-    
-    def __init__(self):
+    """Boundary detector for Rust code.
+
+    By default, outer doc comments and attributes are attached to the item
+    they precede, even across blank lines.
+    """
+
+    default_glue_rules = GlueRules()
+
+    def __init__(self, glue_rules: Optional[GlueRules] = None):
+        self.glue_rules = glue_rules if glue_rules is not None else self.default_glue_rules
+        self._last_scan: Optional[Tuple[str, Tuple[List[Tuple[int, int, str, int]], List[Tuple[int, int]]]]] = None
+
         # Function patterns (pub/private, async, unsafe, const, etc.)
         self.function_pattern = re.compile(r'^(\s*)(?:pub\s+)?(?:async\s+)?(?:unsafe\s+)?(?:const\s+)?fn\s+\w+')
         
         # Struct patterns (pub/private, with generics)
-        self.struct_pattern = re.compile(r'^(\s*)(?:pub\s+)?struct\s+\w+')
+        self.struct_pattern = re.compile(r'^(\s*)(?:pub(?:\([^)]*\))?\s+)?struct\s+\w+')
         
         # Trait patterns (pub/private, with generics)
         self.trait_pattern = re.compile(r'^(\s*)(?:pub\s+)?trait\s+\w+')
         
+        # Enum patterns (pub/private, with generics)
+        self.enum_pattern = re.compile(r'^(\s*)(?:pub(?:\([^)]*\))?\s+)?enum\s+\w+')
+
         # Impl patterns (with generics, trait impls)
         self.impl_pattern = re.compile(r'^(\s*)impl\s+')
+
+        # Declarative macro definitions
+        self.macro_pattern = re.compile(r'^(\s*)macro_rules!\s*\w+')
+
+        # Names of items, e.g. `fn log_message<B>(` or `macro_rules! hashmap {`
+        self.name_pattern = re.compile(r'\b(?:fn|struct|enum|trait|union|type|mod|macro_rules!)\s*(?:r#)?(\w+)')
+
+        # Attributes preceding an item on the same line, e.g. `#[derive(Debug)] `
+        self.attributes_pattern = re.compile(r'^(?:#!?\[[^\]]*\]\s*)+')
+
+        # Top-level use declarations (pub/private)
+        self.use_pattern = re.compile(r'^(?:pub(?:\([^)]*\))?\s+)?use\s+')
     
     def find_boundaries(self, text: str) -> List[Tuple[int, int, str, int]]:
-        """Find function, struct, trait, and impl boundaries in Rust code."""
+        """Find function, struct, enum, trait, and impl boundaries in Rust code."""
+        boundaries, _ = self._scan(text)
+        return list(boundaries)
+
+    def find_unparsed_ranges(self, text: str) -> Optional[List[Tuple[int, int]]]:
+        _, unparsed = self._scan(text)
+        return list(unparsed)
+
+    def _scan(self, text: str) -> Tuple[List[Tuple[int, int, str, int]], List[Tuple[int, int]]]:
+        """Find the boundaries of the items in Rust code, and the line ranges
+        of items which are never closed, e.g. a function missing its closing
+        brace. Such an item ends before the next item at the same or a lower
+        indentation, from which on items are found as usual.
+
+        The result for the last text is kept, as the splitter asks for both
+        the boundaries and the unparsed ranges of each text. It is replaced at
+        once, so that the detector can still be shared by threads.
+        """
+        last_scan = self._last_scan
+        if last_scan is not None and last_scan[0] is text:
+            return last_scan[1]
+
+        result = self._scan_uncached(text)
+        self._last_scan = (text, result)
+        return result
+
+    def _scan_uncached(self, text: str) -> Tuple[List[Tuple[int, int, str, int]], List[Tuple[int, int]]]:
         lines = text.split('\n')
+        # The code of each line, so that items and braces in comments are ignored
+        code, _ = _mask_comments(lines)
         boundaries = []
+        unparsed = []
         
         i = 0
         while i < len(lines):
-            line = lines[i]
+            line = code[i]
             stripped = line.strip()
             
-            # Skip regular comments and empty lines
-            if stripped.startswith('//') and not stripped.startswith('///') and not stripped.startswith('//!') or stripped == '':
+            # Skip comments and empty lines
+            if stripped == '':
                 i += 1
                 continue
             
-            # Check if this line starts a code item (function, struct, trait, impl)
+            # Check if this line starts a code item (function, struct, enum, trait, impl)
             func_match = self.function_pattern.match(line)
             struct_match = self.struct_pattern.match(line)
+            enum_match = self.enum_pattern.match(line)
             trait_match = self.trait_pattern.match(line)
             impl_match = self.impl_pattern.match(line)
-            
-            if func_match or struct_match or trait_match or impl_match:
+            macro_match = self.macro_pattern.match(line)
+
+            if macro_match:
+                # Macro bodies may contain any tokens, so they need their own matching
+                start_line = self._find_item_start(lines, i)
+                end_line = self._find_macro_end(code, i)
+                if end_line is None:
+                    i = self._recover(lines, code, i, len(macro_match.group(1)), start_line, unparsed)
+                    continue
+                boundaries.append((start_line, end_line, 'macro', len(macro_match.group(1))))
+                i = end_line + 1
+                continue
+
+            if func_match or struct_match or enum_match or trait_match or impl_match:
                 # Found a code item, now find its actual start including attributes and docs
                 start_line = self._find_item_start(lines, i)
                 
@@ -50,6 +201,9 @@ class RustBoundaryDetector(CodeBoundaryDetector):
                 elif struct_match:
                     item_type = 'struct'
                     indent_level = len(struct_match.group(1))
+                elif enum_match:
+                    item_type = 'enum'
+                    indent_level = len(enum_match.group(1))
                 elif trait_match:
                     item_type = 'trait'
                     indent_level = len(trait_match.group(1))
@@ -58,54 +212,167 @@ class RustBoundaryDetector(CodeBoundaryDetector):
                     indent_level = len(impl_match.group(1))
                 
                 # Find the end of the code block
-                end_line = self._find_rust_block_end(lines, i, indent_level)
+                end_line = self._find_rust_block_end(code, i, indent_level)
+                if end_line is None:
+                    i = self._recover(lines, code, i, indent_level, start_line, unparsed)
+                    continue
                 boundaries.append((start_line, end_line, item_type, indent_level))
             
             i += 1
         
-        return boundaries
+        return boundaries, unparsed
+
+    def _recover(
+        self,
+        lines: List[str],
+        code: List[str],
+        item_line: int,
+        base_indent: int,
+        start_line: int,
+        unparsed: List[Tuple[int, int]],
+    ) -> int:
+        """Record the item on *item_line*, which is never closed, as unparsed
+        from *start_line* up to the next item at *base_indent* or less, and
+        return the line of that item, or the number of lines if there is none."""
+        next_item = len(lines)
+        for i in range(item_line + 1, len(lines)):
+            indent = self._item_indent(code[i])
+            if indent is not None and indent <= base_indent:
+                next_item = i
+                break
+
+        # The comments and attributes of the next item belong to it
+        end_line = next_item - 1
+        if next_item < len(lines):
+            end_line = max(self._find_item_start(lines, next_item), item_line + 1) - 1
+        while end_line > item_line and not lines[end_line].strip():
+            end_line -= 1
+        unparsed.append((start_line, end_line))
+        return next_item
+
+    def _item_indent(self, code: str) -> Optional[int]:
+        """Return the indentation of the item starting on a line of code, or
+        None if no item starts on it."""
+        for pattern in [self.function_pattern, self.struct_pattern, self.enum_pattern,
+                        self.trait_pattern, self.impl_pattern, self.macro_pattern]:
+            match = pattern.match(code)
+            if match:
+                return len(match.group(1))
+        return None
     
     def _find_item_start(self, lines: List[str], item_line: int) -> int:
-        """Find the actual start of a code item including attributes and doc comments."""
+        """Find the actual start of a code item including outer attributes and doc comments.
+
+        Inner attributes (`#![...]`) and inner doc comments (`//!`) belong to the
+        enclosing module rather than the item, so they are not included.
+        Which lines are attached is further restricted by ``glue_rules``.
+        """
+        glue = self.glue_rules
         start_line = item_line
+        blank_lines = 0
         
         # Look backwards for attributes and doc comments
-        for i in range(item_line - 1, -1, -1):
+        i = item_line - 1
+        while i >= 0:
             line = lines[i]
             stripped = line.strip()
             
-            # Check if this line is an attribute
-            if stripped.startswith('#[') or stripped.startswith('#!'):
+            # Check if this line is an outer attribute
+            if glue.attributes and stripped.startswith('#['):
                 start_line = i
+                blank_lines = 0
+                i -= 1
                 continue
+
+            # Check if this line ends an attribute spanning several lines
+            if glue.attributes and stripped.endswith(']'):
+                attribute_start = self._find_attribute_start(lines, i)
+                if attribute_start is not None:
+                    start_line = attribute_start
+                    blank_lines = 0
+                    i = attribute_start - 1
+                    continue
             
-            # Check if this line is a doc comment
-            if stripped.startswith('///') or stripped.startswith('//!'):
+            # Check if this line is an outer doc comment
+            if glue.comments and stripped.startswith('///'):
                 start_line = i
+                blank_lines = 0
+                i -= 1
                 continue
+
+            # Check if this line ends an outer block doc comment, e.g. `/** ... */`,
+            # which is attached as a whole
+            if glue.comments and stripped.endswith('*/'):
+                comment_start = self._find_block_comment_start(lines, i)
+                if comment_start is not None and _is_block_doc_comment(lines[comment_start]):
+                    start_line = comment_start
+                    blank_lines = 0
+                    i = comment_start - 1
+                    continue
             
-            # If we find an empty line, continue looking (attributes/docs can be separated)
+            # If we find an empty line, continue looking (attributes/docs can be
+            # separated) unless the gap gets too large
             if stripped == '':
+                blank_lines += 1
+                if not glue.allows_gap(blank_lines):
+                    break
+                i -= 1
                 continue
             
             # If we find anything else, stop looking
             break
         
         return start_line
+
+    def _find_block_comment_start(self, lines: List[str], end_line: int) -> Optional[int]:
+        """Find the first line of the block comment ending on *end_line*,
+        including nested comments, or None if its start is not at the start of
+        a line, e.g. because there is code before it."""
+        depth = 0
+
+        for i in range(end_line, -1, -1):
+            for match in _BLOCK_COMMENT_DELIMITER.finditer(lines[i]):
+                depth += 1 if match.group() == '*/' else -1
+            if depth <= 0:
+                return i if depth == 0 and lines[i].strip().startswith('/*') else None
+
+        return None
+
+    def _find_attribute_start(self, lines: List[str], end_line: int) -> Optional[int]:
+        """Find the first line of an outer attribute spanning several lines and
+        ending on *end_line*, or None if *end_line* does not end an attribute."""
+        depth = 0
+
+        for i in range(end_line, -1, -1):
+            stripped = lines[i].strip()
+            if stripped.startswith('//'):
+                continue
+
+            # Brackets are masked in comments and literals
+            for char in reversed(stripped):
+                if char in ')]}':
+                    depth += 1
+                elif char in '([{':
+                    depth -= 1
+
+            if depth == 0:
+                return i if stripped.startswith('#[') else None
+            if depth < 0 or stripped == '':
+                return None
+
+        return None
     
-    def _find_rust_block_end(self, lines: List[str], start_line: int, base_indent: int) -> int:
-        """Find the end of a Rust code block using brace matching."""
+    def _find_rust_block_end(self, lines: List[str], start_line: int, base_indent: int) -> Optional[int]:
+        """Find the end of a Rust code block using brace matching, where
+        *lines* are the lines with comments masked, or None if the block is
+        never closed."""
         brace_count = 0
         found_opening_brace = False
         
         for i in range(start_line, len(lines)):
             line = lines[i]
             
-            # Skip comments
-            if line.strip().startswith('//'):
-                continue
-            
-            # Count braces (simple approach - doesn't handle strings/comments perfectly)
+            # Count braces, which are masked in comments and literals
             for char in line:
                 if char == '{':
                     brace_count += 1
@@ -117,12 +384,254 @@ class RustBoundaryDetector(CodeBoundaryDetector):
                     if found_opening_brace and brace_count == 0:
                         return i
             
-            # Handle single-line items without braces (like struct declarations)
-            if i == start_line and ';' in line:
+            # Handle items without braces (like struct declarations), which
+            # may span several lines, e.g. a trait method's signature
+            if not found_opening_brace and line.rstrip().endswith(';'):
                 return i
         
-        # If we reach the end of file, return the last line
-        return len(lines) - 1
+        # The end of file was reached without closing the block
+        return None
     
+    def _find_macro_end(self, lines: List[str], start_line: int) -> Optional[int]:
+        """Find the end of a `macro_rules!` definition by matching all kinds of
+        delimiters, skipping string and character literals, where *lines* are
+        the lines with comments masked, or None if it is never closed."""
+        depth = 0
+        opened = False
+
+        for i in range(start_line, len(lines)):
+            line = lines[i]
+            j = line.index('!') + 1 if i == start_line else 0
+            while j < len(line):
+                char = line[j]
+                if line.startswith('//', j):
+                    break
+                if char == '"':
+                    # Skip to the closing quote of the string literal
+                    j += 1
+                    while j < len(line) and line[j] != '"':
+                        j += 2 if line[j] == '\\' else 1
+                elif char == "'" and re.match(r"'(?:\\.|[^\\'])'", line[j:]):
+                    # Character literal, unlike a lifetime such as `'a`
+                    j = line.index("'", j + 2 if line[j + 1] == '\\' else j + 1)
+                elif char in '([{':
+                    opened = True
+                    depth += 1
+                elif char in ')]}' and opened:
+                    depth -= 1
+                    if depth == 0:
+                        return i
+                j += 1
+
+        return None
+
+    def find_statement_ends(self, text: str) -> Set[int]:
+        """Find lines ending a statement, block, block opener or list item
+        outside of any parentheses or brackets. Lines inside a block comment
+        never end a statement, so that comments are not cut."""
+        statement_ends = set()
+        paren_count = 0
+        code, depths = _mask_comments(text.split('\n'))
+
+        for i, line in enumerate(code):
+            if depths[i] > 0:
+                continue
+
+            stripped = line.strip()
+            if stripped == '':
+                statement_ends.add(i)
+                continue
+
+            # Brackets are masked in comments and literals
+            for char in line:
+                if char in '([':
+                    paren_count += 1
+                elif char in ')]':
+                    paren_count = max(paren_count - 1, 0)
+
+            if paren_count == 0 and stripped[-1] in ';{},':
+                statement_ends.add(i)
+
+        return statement_ends
+
+    def split_line_items(self, line: str) -> Optional[List[Tuple[int, int, str]]]:
+        """Split a line holding several items, e.g. `struct A; struct B;`, after
+        the `;` or `}` ending each item outside of any delimiters. The line is
+        only split if every piece is an item, possibly preceded by attributes."""
+        (code,), _ = _mask_comments([line])
+        pieces = []
+        depth = 0
+        start = None
+
+        j = 0
+        while j < len(code):
+            char = code[j]
+            if start is None and not char.isspace():
+                start = j
+            if char == '"':
+                # Skip to the closing quote of the string literal
+                j += 1
+                while j < len(code) and code[j] != '"':
+                    j += 2 if code[j] == '\\' else 1
+            elif char == "'" and re.match(r"'(?:\\.|[^\\'])'", code[j:]):
+                # Character literal, unlike a lifetime such as `'a`
+                j = code.index("'", j + 2 if code[j + 1] == '\\' else j + 1)
+            elif char in '([{':
+                depth += 1
+            elif char in ')]}':
+                depth -= 1
+                if depth < 0:
+                    return None
+
+            if depth == 0 and start is not None and char in ';}':
+                pieces.append((start, j + 1))
+                start = None
+            j += 1
+
+        if start is not None:
+            pieces.append((start, len(code.rstrip())))
+        if len(pieces) < 2:
+            return None
+
+        items = []
+        for start, end in pieces:
+            item_type = self._item_type(self.attributes_pattern.sub('', code[start:end]))
+            if item_type is None:
+                return None
+            items.append((start, end, item_type))
+        return items
+
+    def _item_type(self, code: str) -> Optional[str]:
+        """Return the boundary type of the item at the start of *code*."""
+        for item_type, pattern in [
+            ('function', self.function_pattern),
+            ('struct', self.struct_pattern),
+            ('enum', self.enum_pattern),
+            ('trait', self.trait_pattern),
+            ('impl', self.impl_pattern),
+            ('macro', self.macro_pattern),
+        ]:
+            if pattern.match(code):
+                return item_type
+        return None
+
+    def extract_name(self, signature: str) -> Optional[str]:
+        """Extract the name of an item, or the implementing type of an impl block,
+        e.g. `LogMessage` for `impl<T> fmt::Display for LogMessage<T> {`."""
+        if re.match(r'^\s*(?:unsafe\s+)?impl\b', signature):
+            # Remove generic parameters and arguments, including nested ones
+            header = signature
+            while True:
+                stripped = re.sub(r'<[^<>]*>', '', header)
+                if stripped == header:
+                    break
+                header = stripped
+
+            header = re.sub(r"'\w+", '', re.split(r'\bwhere\b|\{', header)[0])
+            header = header.split(' for ', 1)[-1] if ' for ' in header else re.sub(r'^\s*(?:unsafe\s+)?impl', '', header)
+            paths = re.findall(r'[\w:]+', header.replace('dyn ', ''))
+            return paths[0].split('::')[-1] if paths else None
+
+        match = self.name_pattern.search(signature)
+        return match.group(1) if match else None
+
+    def find_imports(self, text: str) -> List[str]:
+        """Find the unindented `use` declarations, which may span several lines
+        up to the terminating `;`."""
+        lines = text.split('\n')
+        imports = []
+
+        for first, last in self._find_use_ranges(lines):
+            parts = [line.split('//', 1)[0].strip() for line in lines[first:last + 1]]
+            statement = ' '.join(part for part in parts if part)
+            statement = re.sub(r'\{\s+', '{', statement)
+            statement = re.sub(r',?\s+\}', '}', statement)
+            imports.append(statement)
+
+        return imports
+
+    def find_import_lines(self, text: str) -> Set[int]:
+        return {
+            i for first, last in self._find_use_ranges(text.split('\n'))
+            for i in range(first, last + 1)
+        }
+
+    def _find_use_ranges(self, lines: List[str]) -> List[Tuple[int, int]]:
+        """Find the (first, last) lines of each unindented `use` declaration."""
+        ranges = []
+        i = 0
+
+        while i < len(lines):
+            if not self.use_pattern.match(lines[i]):
+                i += 1
+                continue
+
+            first = i
+            while i < len(lines) - 1 and not lines[i].split('//', 1)[0].strip().endswith(';'):
+                i += 1
+            ranges.append((first, i))
+            i += 1
+
+        return ranges
+
+    def extract_doc_comment(self, text: str) -> Optional[str]:
+        """Extract the leading `///` or `//!` doc comment lines, or `/** */` or
+        `/*! */` doc blocks, skipping attributes."""
+        doc_lines = []
+        lines = text.split('\n')
+        i = 0
+
+        while i < len(lines):
+            stripped = lines[i].strip()
+            i += 1
+
+            if _is_block_doc_comment(stripped) or stripped.startswith('/*!'):
+                i = self._extract_block_doc_comment(lines, i - 1, doc_lines)
+                continue
+
+            if stripped.startswith('///') or stripped.startswith('//!'):
+                content = stripped[3:]
+                doc_lines.append(content[1:] if content.startswith(' ') else content)
+                continue
+
+            # Attributes and empty lines may be mixed in with doc comments
+            if stripped.startswith('#[') or stripped.startswith('#!') or stripped == '':
+                continue
+
+            break
+
+        if not doc_lines:
+            return None
+        return '\n'.join(doc_lines)
+
+    def _extract_block_doc_comment(self, lines: List[str], first: int, doc_lines: List[str]) -> int:
+        """Append the lines of the block doc comment starting on line *first*
+        to *doc_lines*, without the delimiters and the leading `*` of each
+        line, and return the index of the line after the comment."""
+        _, depths = _mask_comments(lines[first:])
+        last = first + next((j for j, depth in enumerate(depths) if depth == 0), len(depths) - 1)
+
+        for i in range(first, last + 1):
+            content = lines[i].strip()
+            if i == first:
+                content = content[3:]
+            if i == last and content.endswith('*/'):
+                content = content[:-2].rstrip()
+            if i > first and content.startswith('*'):
+                content = content[1:]
+            content = content[1:] if content.startswith(' ') else content
+
+            # Leave out the lines holding only a delimiter, e.g. `/**` and ` */`
+            if content or first < i < last:
+                doc_lines.append(content)
+
+        return last + 1
+
     def get_boundary_types(self) -> List[str]:
-        return ['function', 'struct', 'trait', 'impl']
\ No newline at end of file
+        return ['function', 'struct', 'enum', 'trait', 'impl', 'macro']
+
+def _is_block_doc_comment(line: str) -> bool:
+    """Return True if *line* starts an outer block doc comment, i.e. `/**`
+    which is not `/***` or the empty comment `/**/`."""
+    stripped = line.strip()
+    return stripped.startswith('/**') and not stripped.startswith('/***') and not stripped.startswith('/**/')
@@ -0,0 +1,50 @@
+import re
+from typing import List, Optional, Tuple
+from pipelines.detectors.detector_javascript import JavaScriptBoundaryDetector
+from pipelines.detectors.glue_rules import GlueRules
+
+class TypeScriptBoundaryDetector(JavaScriptBoundaryDetector):
+    """Boundary detector for TypeScript code, including TSX."""
+
+    member_modifiers = [
+        'public', 'private', 'protected', 'readonly', 'abstract', 'override', 'declare',
+        *JavaScriptBoundaryDetector.member_modifiers,
+    ]
+
+    def __init__(self, glue_rules: Optional[GlueRules] = None):
+        super().__init__(glue_rules)
+        export_prefix = r'^(\s*)(?:export\s+(?:default\s+)?)?(?:declare\s+)?'
+
+        self.function_pattern = re.compile(export_prefix + r'(?:async\s+)?function\b')
+        self.class_pattern = re.compile(export_prefix + r'(?:abstract\s+)?class\b')
+
+        # Interface declarations
+        self.interface_pattern = re.compile(export_prefix + r'interface\s+[\w$]+')
+
+        # Enum declarations, including const enums
+        self.enum_pattern = re.compile(export_prefix + r'(?:const\s+)?enum\s+[\w$]+')
+
+        # Type aliases
+        self.type_pattern = re.compile(export_prefix + r'type\s+[\w$]+\s*(?:<[^=]*>)?\s*=')
+
+    def _match_declaration(self, codes: List[str], depths: List[int], i: int) -> Tuple[Optional[re.Match], str]:
+        """Match a top-level declaration on line *i*, returning the match and boundary type."""
+        for pattern, item_type in [
+            (self.interface_pattern, 'interface'),
+            (self.enum_pattern, 'enum'),
+            (self.type_pattern, 'type'),
+        ]:
+            match = pattern.match(codes[i])
+            if match:
+                return match, item_type
+
+        return super()._match_declaration(codes, depths, i)
+
+    def _is_arrow(self, codes: List[str], depths: List[int], i: int) -> bool:
+        # Type aliases are complete without a block, e.g. `type Id = string;`
+        if self.type_pattern.match(codes[i]):
+            return True
+        return super()._is_arrow(codes, depths, i)
+
+    def get_boundary_types(self) -> List[str]:
+        return ['function', 'class', 'interface', 'enum', 'type']
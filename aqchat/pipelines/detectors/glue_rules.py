@@ -0,0 +1,30 @@
+from dataclasses import dataclass
+from typing import Optional
+
+@dataclass(frozen=True)
+class GlueRules:
+    """Rules for which lines preceding a definition are attached ("glued") to
+    its chunk, rather than being left to the code before it.
+
+    Attributes:
+        comments: Whether the comments preceding a definition are attached,
+        i.e. doc comments in languages which have them, e.g. `///` in Rust.
+        attributes: Whether attributes and decorators preceding a definition
+        are attached, e.g. `#[derive(Debug)]` or `@dataclass`.
+        max_blank_lines: The maximum number of blank lines between a definition
+        and the comment or attribute above it, or between two attached lines.
+        A comment after a larger gap is detached from the definition. None
+        means any number of blank lines.
+    """
+
+    comments: bool = True
+    attributes: bool = True
+    max_blank_lines: Optional[int] = None
+
+    def __post_init__(self):
+        if self.max_blank_lines is not None and self.max_blank_lines < 0:
+            raise ValueError(f"max_blank_lines must not be negative, got {self.max_blank_lines}")
+
+    def allows_gap(self, blank_lines: int) -> bool:
+        """Return True if a gap of *blank_lines* blank lines may be bridged."""
+        return self.max_blank_lines is None or blank_lines <= self.max_blank_lines
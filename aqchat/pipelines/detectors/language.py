@@ -0,0 +1,142 @@
+import os
+import re
+from enum import Enum
+from pathlib import Path
+from typing import Dict, List, Optional, Union
+from pipelines.detectors.boundary_detector import CodeBoundaryDetector
+from pipelines.detectors.detector_go import GoBoundaryDetector
+from pipelines.detectors.detector_javascript import JavaScriptBoundaryDetector
+from pipelines.detectors.detector_markdown import MarkdownBoundaryDetector
+from pipelines.detectors.detector_python import PythonBoundaryDetector
+from pipelines.detectors.detector_rust import RustBoundaryDetector
+from pipelines.detectors.detector_typescript import TypeScriptBoundaryDetector
+from pipelines.detectors.glue_rules import GlueRules
+
+class Language(Enum):
+    """Source languages which the code boundary splitter understands."""
+
+    PYTHON = "python"
+    RUST = "rust"
+    TYPESCRIPT = "typescript"
+    JAVASCRIPT = "javascript"
+    GO = "go"
+    MARKDOWN = "markdown"
+
+    @property
+    def extensions(self) -> List[str]:
+        """File extensions (including the leading dot) used by this language."""
+        return _EXTENSIONS[self]
+
+    @property
+    def line_comment(self) -> Optional[str]:
+        """The token starting a line comment, e.g. `//`, or None for languages
+        of prose, i.e. Markdown."""
+        return _LINE_COMMENTS[self]
+
+    def get_boundary_detector(self, glue_rules: Optional[GlueRules] = None) -> CodeBoundaryDetector:
+        """Return a new boundary detector for this language, attaching comments
+        and attributes to definitions according to *glue_rules* if given, or
+        else the language's ``default_glue_rules``.
+
+        Raises:
+            ValueError: If *glue_rules* are given for a language without
+            comments or attributes to attach, i.e. Markdown.
+        """
+        if glue_rules is None:
+            return _DETECTORS[self]()
+        if self.default_glue_rules is None:
+            raise ValueError(f"{self.value} has no glue rules")
+        return _DETECTORS[self](glue_rules)
+
+    @property
+    def default_glue_rules(self) -> Optional[GlueRules]:
+        """The rules for attaching comments and attributes to definitions used
+        by default, or None if the language has no such rules."""
+        return getattr(_DETECTORS[self], "default_glue_rules", None)
+
+    @classmethod
+    def detect(cls, path: Union[str, os.PathLike], contents: str) -> Optional["Language"]:
+        """Detect the language of a file from its extension.
+
+        The extension is matched case-insensitively. If the extension is missing
+        or not recognized, the interpreter named in a leading shebang line such as
+        `#!/usr/bin/env python3` is used instead.
+
+        Returns:
+            The detected language, or None if the language is not supported.
+        """
+        ext = Path(path).suffix.lower()
+        for language in cls:
+            if ext in language.extensions:
+                return language
+
+        return _detect_shebang(contents)
+
+    @classmethod
+    def from_detector(cls, detector: CodeBoundaryDetector) -> Optional["Language"]:
+        """Return the language whose default boundary detector is *detector*,
+        or None for any other (e.g. custom) detector."""
+        for language, detector_type in _DETECTORS.items():
+            if type(detector) is detector_type:
+                return language
+        return None
+
+_EXTENSIONS = {
+    Language.PYTHON: [".py"],
+    Language.RUST: [".rs"],
+    Language.TYPESCRIPT: [".ts", ".tsx", ".mts", ".cts"],
+    Language.JAVASCRIPT: [".js", ".jsx", ".mjs", ".cjs"],
+    Language.GO: [".go"],
+    Language.MARKDOWN: [".md", ".markdown"],
+}
+
+_LINE_COMMENTS = {
+    Language.PYTHON: "#",
+    Language.RUST: "//",
+    Language.TYPESCRIPT: "//",
+    Language.JAVASCRIPT: "//",
+    Language.GO: "//",
+    Language.MARKDOWN: None,
+}
+
+_DETECTORS = {
+    Language.PYTHON: PythonBoundaryDetector,
+    Language.RUST: RustBoundaryDetector,
+    Language.TYPESCRIPT: TypeScriptBoundaryDetector,
+    Language.JAVASCRIPT: JavaScriptBoundaryDetector,
+    Language.GO: GoBoundaryDetector,
+    Language.MARKDOWN: MarkdownBoundaryDetector,
+}
+
+# Interpreters named in shebang lines, with any version suffix removed
+_INTERPRETERS = {
+    "python": Language.PYTHON,
+    "pypy": Language.PYTHON,
+    "node": Language.JAVASCRIPT,
+    "nodejs": Language.JAVASCRIPT,
+    "ts-node": Language.TYPESCRIPT,
+}
+
+def _detect_shebang(contents: str) -> Optional[Language]:
+    """Detect the language from the interpreter in a leading shebang line."""
+    if not contents.startswith("#!"):
+        return None
+
+    words = contents[2:].split("\n", 1)[0].split()
+    if words and os.path.basename(words[0]) == "env":
+        # `#!/usr/bin/env [-S] interpreter`, skip env's own options
+        words = [word for word in words[1:] if not word.startswith("-")]
+    if not words:
+        return None
+
+    interpreter = re.sub(r"[\d.]+$", "", os.path.basename(words[0]))
+    return _INTERPRETERS.get(interpreter)
+
+def get_boundary_detectors() -> Dict[str, CodeBoundaryDetector]:
+    """Return a dict mapping every supported file extension to a boundary detector."""
+    detectors = {}
+    for language in Language:
+        detector = language.get_boundary_detector()
+        for ext in language.extensions:
+            detectors[ext] = detector
+    return detectors
@@ -0,0 +1,53 @@
+import re
+from typing import Optional
+from pipelines.chunk import DocMeta
+
+_TAG_PATTERN = re.compile(r'^@(\w+)(.*)$')
+
+def parse_doc_comment(raw: Optional[str]) -> Optional[DocMeta]:
+    """Parse Doxygen-style tags out of a doc comment.
+
+    *raw* is the text of the doc comment with comment markers removed, as
+    returned by ``CodeBoundaryDetector.extract_doc_comment``. A tag's value
+    continues onto the following lines until the next tag or a blank line.
+
+    Returns None if *raw* is None.
+    """
+    if raw is None:
+        return None
+
+    doc = DocMeta(raw=raw)
+
+    # Collect (tag, value) pairs, joining continuation lines
+    entries = []
+    for line in raw.split('\n'):
+        stripped = line.strip()
+        match = _TAG_PATTERN.match(stripped)
+        if match:
+            entries.append([match.group(1), match.group(2).strip()])
+        elif stripped and entries and entries[-1] is not None:
+            entries[-1][1] = f"{entries[-1][1]} {stripped}".strip()
+        else:
+            # untagged text or a blank line ends the current tag
+            entries.append(None)
+
+    for entry in entries:
+        if entry is None:
+            continue
+        tag, value = entry
+
+        if tag == 'brief':
+            doc.brief = value
+        elif tag == 'allow':
+            # both `@allow(dead_code)` and `@allow dead_code` are accepted
+            names = value.strip('()')
+            doc.allow.extend(name.strip() for name in names.split(',') if name.strip())
+        elif tag == 'param':
+            name, _, description = value.partition(' ')
+            doc.params[name] = description.strip()
+        elif tag in ('return', 'returns'):
+            doc.returns = value
+        else:
+            doc.tags.setdefault(tag, []).append(value)
+
+    return doc
@@ -0,0 +1,146 @@
+from dataclasses import dataclass, field
+from typing import Any, Callable, Dict, List, Optional, Sequence, Tuple
+from pipelines.abstract_chat_provider import AbstractChatProvider
+from pipelines.abstract_embedder import AbstractEmbedder
+from pipelines.abstract_reranker import AbstractReranker
+from pipelines.abstract_tokenizer import AbstractTokenizer
+from pipelines.abstract_vector_store import AbstractVectorStore
+from pipelines.chunk import Chunk
+from pipelines.citations import Citation, cited_messages, parse_citations
+from pipelines.context_budget import fit_to_budget
+from pipelines.prompt_template import PromptTemplate
+from pipelines.query_expansion import expand_query
+from pipelines.retrieval import retrieve
+
+@dataclass
+class Answer:
+    """The answer to a question asked with ``Engine.ask``.
+
+    ``used_chunks`` are the chunks placed in the context, where the chunk cited
+    as ``[i]`` is ``used_chunks[i - 1]``, and ``citations`` are the chunks the
+    model cited inline, in order of first use.
+    """
+
+    text: str
+    citations: List[Citation] = field(default_factory=list)
+    used_chunks: List[Chunk] = field(default_factory=list)
+
+class Engine:
+    """Answers questions about an indexed code base, by embedding the question,
+    retrieving the most relevant chunks, fitting them into the context and
+    asking the chat model to answer with citations.
+
+    Each step is a method which subclasses may override, e.g. ``build_context``
+    to order the chunks differently.
+
+    Args:
+        embedder: Embeds the question, which must be the embedder that the
+        chunks in *store* were embedded with.
+        store: The vector store holding the indexed chunks.
+        provider: The chat model answering the question.
+        k: The number of chunks to retrieve.
+        context_budget: The maximum number of tokens of the chunks placed in
+        the context, where 0 means there is no limit.
+        tokenizer: Counts the tokens for *context_budget*, by default
+        ``HeuristicTokenizer``.
+        reranker: Reranks the retrieved candidates, if given, see ``retrieve``.
+        candidates: The number of candidates fetched for reranking.
+        min_score: The minimum similarity of retrieved chunks to the question,
+        if any, see ``AbstractVectorStore.search``. If no chunk reaches it,
+        the question is asked without context.
+        system_prompt: Instructions preceding the citation instructions, if any.
+        template: The template of the messages, by default ``PromptTemplate()``.
+        options: Generation parameters passed to *provider*, if any.
+        query_expansion: Whether to embed the question together with a
+        hypothetical answer generated by *provider* instead of the question
+        alone, see ``expand_query``. The answer is still generated for the
+        question itself. Disabled by default.
+    """
+
+    def __init__(
+        self,
+        embedder: AbstractEmbedder,
+        store: AbstractVectorStore,
+        provider: AbstractChatProvider,
+        *,
+        k: int = 6,
+        context_budget: int = 4096,
+        tokenizer: Optional[AbstractTokenizer] = None,
+        reranker: Optional[AbstractReranker] = None,
+        candidates: Optional[int] = None,
+        min_score: Optional[float] = None,
+        system_prompt: Optional[str] = None,
+        template: Optional[PromptTemplate] = None,
+        options: Optional[Dict[str, Any]] = None,
+        query_expansion: bool = False,
+    ):
+        self.embedder = embedder
+        self.store = store
+        self.provider = provider
+        self.k = k
+        self.context_budget = context_budget
+        self.tokenizer = tokenizer
+        self.reranker = reranker
+        self.candidates = candidates
+        self.min_score = min_score
+        self.system_prompt = system_prompt
+        self.template = template
+        self.options = options
+        self.query_expansion = query_expansion
+
+    async def ask(self, question: str, *, where: Optional[Callable[[Chunk], bool]] = None) -> Answer:
+        """Answer *question* from the chunks in the store, only considering
+        the chunks for which *where* returns True if given, e.g. the
+        ``matches`` of a ``SearchQuery`` scoped to a directory.
+
+        Raises:
+            EmbedError: If the question could not be embedded.
+            VectorStoreError: If the store could not be searched.
+            ChatError: If the chat model failed to answer.
+        """
+        query = await self.expand_query(question) if self.query_expansion else question
+        query_vector = await self.embed_query(query)
+        results = await self.retrieve(question, query_vector, where)
+        chunks = self.build_context(results)
+        messages, citations = self.build_messages(question, chunks)
+        text = await self.complete(messages)
+        return Answer(text=text, citations=parse_citations(text, citations), used_chunks=chunks)
+
+    async def expand_query(self, question: str) -> str:
+        """Return the text embedded for *question* with query expansion, or
+        the question itself if the expansion failed."""
+        return await expand_query(self.provider, question, self.options)
+
+    async def embed_query(self, question: str) -> List[float]:
+        """Embed *question* for searching the store."""
+        vectors = await self.embedder.embed([question])
+        return vectors[0]
+
+    async def retrieve(
+        self,
+        question: str,
+        query_vector: Sequence[float],
+        where: Optional[Callable[[Chunk], bool]] = None,
+    ) -> List[Tuple[Chunk, float]]:
+        """Retrieve the chunks most relevant to *question*, with their scores."""
+        return await retrieve(
+            self.store, query_vector, question, self.k,
+            reranker=self.reranker, candidates=self.candidates, where=where, min_score=self.min_score,
+        )
+
+    def build_context(self, results: Sequence[Tuple[Chunk, float]]) -> List[Chunk]:
+        """Select the chunks placed in the context from the retrieved *results*,
+        most relevant first and within the context budget."""
+        if not self.context_budget:
+            return [chunk for chunk, _ in sorted(results, key=lambda result: -result[1])]
+        return fit_to_budget(results, self.context_budget, self.tokenizer)
+
+    def build_messages(self, question: str, chunks: Sequence[Chunk]) -> Tuple[List[Dict[str, str]], List[Citation]]:
+        """Build the chat messages asking *question* with *chunks* as numbered
+        context, returning them with a citation for each chunk."""
+        return cited_messages(question, chunks, self.system_prompt, self.template)
+
+    async def complete(self, messages: List[Dict[str, str]]) -> str:
+        """Return the answer of the chat model to *messages*."""
+        completion = await self.provider.complete(messages, self.options)
+        return completion.text
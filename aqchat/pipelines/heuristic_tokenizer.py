@@ -0,0 +1,23 @@
+import re
+from typing import Hashable
+from pipelines.abstract_tokenizer import AbstractTokenizer
+
+class HeuristicTokenizer(AbstractTokenizer):
+    """A tokenizer which estimates token counts without a vocabulary.
+
+    Text is counted as the larger of its number of words and punctuation
+    marks, and its number of characters divided by `chars_per_token`. This is
+    a rough approximation of BPE tokenizers, which split long identifiers into
+    several tokens and usually give punctuation its own token.
+    """
+
+    def __init__(self, chars_per_token: int = 4):
+        self.chars_per_token = chars_per_token
+        self.token_pattern = re.compile(r'\w+|[^\w\s]')
+
+    def count_tokens(self, text: str) -> int:
+        words = len(self.token_pattern.findall(text))
+        return max(words, -(-len(text) // self.chars_per_token))
+
+    def cache_key(self) -> Hashable:
+        return (type(self), self.chars_per_token)
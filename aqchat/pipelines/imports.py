@@ -0,0 +1,93 @@
+import re
+from typing import Iterable, List
+
+_RUST_USE = re.compile(r'^(?:pub(?:\([^)]*\))?\s+)?use\s+(.*?);?$')
+_PYTHON_FROM = re.compile(r'^from\s+\S+\s+import\s+(.*)$')
+_PYTHON_IMPORT = re.compile(r'^import\s+(.*)$')
+
+def _split_top_level(text: str) -> List[str]:
+    """Split *text* on the commas which are not nested inside braces."""
+    items = []
+    depth = 0
+    start = 0
+    for i, char in enumerate(text):
+        if char == '{':
+            depth += 1
+        elif char == '}':
+            depth -= 1
+        elif char == ',' and depth == 0:
+            items.append(text[start:i])
+            start = i + 1
+    items.append(text[start:])
+    return [item.strip() for item in items if item.strip()]
+
+def _rust_names(tree: str) -> List[str]:
+    """Names bound by the use tree *tree*, e.g. ``["SystemTime", "UNIX_EPOCH"]``
+    for ``std::time::{SystemTime, UNIX_EPOCH}``."""
+    brace = tree.find('{')
+    if brace != -1:
+        prefix = tree[:brace]
+        inner = tree[brace + 1:tree.rfind('}')]
+        names = []
+        for item in _split_top_level(inner):
+            names.extend(_rust_names(prefix + item))
+        return names
+
+    if ' as ' in tree:
+        return [tree.rsplit(' as ', 1)[1].strip()]
+
+    segments = [segment.strip() for segment in tree.split('::')]
+    if segments[-1] == '*':
+        return []
+    if segments[-1] == 'self' and len(segments) > 1:
+        return [segments[-2]]
+    return [segments[-1]]
+
+def _python_names(names: str, from_import: bool) -> List[str]:
+    """Names bound by the comma separated *names* of an import statement."""
+    result = []
+    for name in names.strip('()').split(','):
+        name = name.strip()
+        if not name or name == '*':
+            continue
+        if ' as ' in name:
+            result.append(name.rsplit(' as ', 1)[1].strip())
+        elif from_import:
+            result.append(name)
+        else:
+            # `import a.b` binds `a`
+            result.append(name.split('.', 1)[0])
+    return result
+
+def import_names(statement: str) -> List[str]:
+    """Return the names bound by a Rust `use` or Python `import` statement,
+    e.g. ``["SystemTime", "UNIX_EPOCH"]`` for
+    ``use std::time::{SystemTime, UNIX_EPOCH};``.
+
+    Glob imports bind no names that can be known from the statement alone.
+    """
+    statement = statement.strip()
+
+    match = _PYTHON_FROM.match(statement)
+    if match:
+        return _python_names(match.group(1), from_import=True)
+    match = _PYTHON_IMPORT.match(statement)
+    if match:
+        return _python_names(match.group(1), from_import=False)
+    match = _RUST_USE.match(statement)
+    if match:
+        return [name for name in _rust_names(match.group(1).strip()) if name]
+    return []
+
+def relevant_imports(imports: Iterable[str], text: str) -> List[str]:
+    """Return the *imports* which bind a name occurring in *text* as a whole word.
+
+    Import statements which are themselves part of *text* are left out.
+    """
+    relevant = []
+    for statement in imports:
+        if statement in text:
+            continue
+        if any(re.search(rf'\b{re.escape(name)}\b', text) for name in import_names(statement)):
+            relevant.append(statement)
+    return relevant
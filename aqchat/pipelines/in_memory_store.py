@@ -0,0 +1,205 @@
+import json
+import math
+import os
+import struct
+import threading
+from typing import BinaryIO, Callable, List, Optional, Sequence, Tuple
+from pipelines.abstract_vector_store import AbstractVectorStore, VectorStoreError
+from pipelines.chunk import Chunk
+
+# Header of files written by `InMemoryStore.save`: magic, format version,
+# vector dimension and number of entries, followed by the flags of the store
+# since version 2
+_MAGIC = b'AQVS'
+_FORMAT_VERSION = 2
+_HEADER = struct.Struct('<4sIII')
+_FLAGS = struct.Struct('<I')
+_LENGTH = struct.Struct('<I')
+
+# Flag of stores which resize vectors
+_FLAG_RESIZE = 1
+
+class InMemoryStore(AbstractVectorStore):
+    """Vector store which keeps all vectors in memory and searches them
+    exhaustively by cosine similarity.
+
+    The dimension of the store is given on construction, or otherwise taken
+    from the first vector added.
+
+    If *resize* is set, vectors and queries of another dimension than the
+    given *dimension* are truncated or padded with zeros to it instead of
+    being rejected, for models which emit vectors of varying sizes.
+    """
+
+    def __init__(self, dimension: Optional[int] = None, *, resize: bool = False):
+        if resize and dimension is None:
+            raise ValueError("resize requires a dimension")
+
+        self.dimension = dimension
+        self.resize = resize
+        self._entries: List[Tuple[Chunk, List[float], float]] = []  # (chunk, vector, norm)
+        self._lock = threading.Lock()
+
+    def add(self, chunks: Sequence[Chunk], vectors: Sequence[Sequence[float]]) -> None:
+        with self._lock:
+            self.dimension, entries = self._entries_of(chunks, vectors, self.dimension)
+            self._entries.extend(entries)
+
+    def search(
+        self,
+        query: Sequence[float],
+        k: int,
+        where: Optional[Callable[[Chunk], bool]] = None,
+        min_score: Optional[float] = None,
+    ) -> List[Tuple[Chunk, float]]:
+        with self._lock:
+            if self.dimension is not None:
+                query = self._fit(query, self.dimension, "query")
+            if not self._entries or k <= 0:
+                return []
+
+            query_norm = _norm(query)
+            results = []
+            for chunk, vector, norm in self._entries:
+                if where is not None and not where(chunk):
+                    continue
+                if norm == 0.0 or query_norm == 0.0:
+                    score = 0.0
+                else:
+                    score = sum(a * b for a, b in zip(query, vector)) / (norm * query_norm)
+                if min_score is None or score >= min_score:
+                    results.append((chunk, score))
+
+        results.sort(key=lambda result: (-result[1], result[0].id()))
+        return results[:k]
+
+    def clear(self, dimension: Optional[int] = None) -> None:
+        if self.resize and dimension is None:
+            raise ValueError("resize requires a dimension")
+
+        with self._lock:
+            self.dimension = dimension
+            self._entries = []
+
+    def migrate(self, chunks: Sequence[Chunk], vectors: Sequence[Sequence[float]]) -> None:
+        """Replace all chunks of the store with *chunks* and their *vectors*,
+        see ``AbstractVectorStore.migrate``. The store is left unchanged if
+        the vectors are invalid. If the store resizes vectors, they are
+        resized to its dimension instead."""
+        with self._lock:
+            dimension, entries = self._entries_of(chunks, vectors, self.dimension if self.resize else None)
+            self.dimension = dimension
+            self._entries = entries
+
+    def __len__(self) -> int:
+        with self._lock:
+            return len(self._entries)
+
+    def save(self, path: str | os.PathLike) -> None:
+        """Save the store to the file at *path*, see ``load``.
+
+        The file starts with a header holding a format version and whether
+        the store resizes vectors, followed by each chunk's metadata as
+        length-prefixed JSON (see ``write_jsonl``) and its vector as
+        little-endian 64-bit floats.
+        """
+        with self._lock:
+            entries = list(self._entries)
+            dimension = self.dimension or 0
+
+        with open(path, 'wb') as file:
+            file.write(_HEADER.pack(_MAGIC, _FORMAT_VERSION, dimension, len(entries)))
+            file.write(_FLAGS.pack(_FLAG_RESIZE if self.resize else 0))
+            for chunk, vector, _ in entries:
+                metadata = json.dumps(chunk.to_dict(), ensure_ascii=False).encode('utf-8')
+                file.write(_LENGTH.pack(len(metadata)))
+                file.write(metadata)
+                file.write(_pack_vector(vector))
+
+    @classmethod
+    def load(cls, path: str | os.PathLike) -> "InMemoryStore":
+        """Load a store saved by ``save`` from the file at *path*, which
+        resizes vectors if the saved store did.
+
+        Raises:
+            VectorStoreError: If the file is not a saved store, was written by
+            an incompatible version, or is truncated.
+            OSError: If the file could not be read.
+        """
+        with open(path, 'rb') as file:
+            magic, version, dimension, count = _HEADER.unpack(_read_exact(file, _HEADER.size, path))
+            if magic != _MAGIC:
+                raise VectorStoreError(f"{path} is not a saved vector store")
+            if not 1 <= version <= _FORMAT_VERSION:
+                raise VectorStoreError(
+                    f"{path} has format version {version}, but only versions up to {_FORMAT_VERSION} are supported"
+                )
+            flags = 0
+            if version >= 2:
+                (flags,) = _FLAGS.unpack(_read_exact(file, _FLAGS.size, path))
+
+            chunks = []
+            vectors = []
+            for _ in range(count):
+                (length,) = _LENGTH.unpack(_read_exact(file, _LENGTH.size, path))
+                try:
+                    chunk = Chunk.from_dict(json.loads(_read_exact(file, length, path).decode('utf-8')))
+                except (ValueError, KeyError) as ex:
+                    raise VectorStoreError(f"{path} contains invalid chunk metadata: {ex}") from ex
+                chunks.append(chunk)
+                vectors.append(_unpack_vector(_read_exact(file, dimension * 8, path)))
+
+            if file.read(1):
+                raise VectorStoreError(f"{path} has unexpected data after {count} entries")
+
+        store = cls(dimension or None, resize=bool(flags & _FLAG_RESIZE))
+        store.add(chunks, vectors)
+        return store
+
+    def _entries_of(
+        self,
+        chunks: Sequence[Chunk],
+        vectors: Sequence[Sequence[float]],
+        dimension: Optional[int],
+    ) -> Tuple[Optional[int], List[Tuple[Chunk, List[float], float]]]:
+        """Validate *chunks* and *vectors* for a store of *dimension* and
+        return the dimension of the store after adding them with their entries,
+        so that the store is only changed once all vectors are known to be valid."""
+        if len(chunks) != len(vectors):
+            raise VectorStoreError(f"Got {len(vectors)} vectors for {len(chunks)} chunks")
+
+        entries = []
+        for chunk, vector in zip(chunks, vectors):
+            if dimension is None:
+                dimension = len(vector)
+            vector = [float(value) for value in self._fit(vector, dimension, "vector")]
+            entries.append((chunk, vector, _norm(vector)))
+        return dimension, entries
+
+    def _fit(self, vector: Sequence[float], dimension: int, what: str) -> Sequence[float]:
+        """Return *vector* resized to *dimension* if the store resizes vectors,
+        or else check that it has the dimension."""
+        if len(vector) == dimension:
+            return vector
+        if self.resize:
+            return list(vector[:dimension]) + [0.0] * (dimension - len(vector))
+
+        raise VectorStoreError(
+            f"Expected a {what} of dimension {dimension}, got {len(vector)}; vectors of another "
+            f"embedding model can't be mixed with the store's, use clear or migrate to switch models"
+        )
+
+def _norm(vector: Sequence[float]) -> float:
+    return math.sqrt(sum(value * value for value in vector))
+
+def _pack_vector(vector: Sequence[float]) -> bytes:
+    return struct.pack(f'<{len(vector)}d', *vector)
+
+def _unpack_vector(data: bytes) -> List[float]:
+    return list(struct.unpack(f'<{len(data) // 8}d', data))
+
+def _read_exact(file: BinaryIO, size: int, path: str | os.PathLike) -> bytes:
+    data = file.read(size)
+    if len(data) != size:
+        raise VectorStoreError(f"{path} is truncated")
+    return data
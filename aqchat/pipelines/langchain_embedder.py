@@ -0,0 +1,46 @@
+import asyncio
+from typing import List
+from langchain_core.embeddings import Embeddings
+from pipelines.abstract_embedder import AbstractEmbedder, EmbedError
+from pipelines.retry import parse_retry_after
+
+class LangchainEmbedder(AbstractEmbedder):
+    """Embedder backed by any Langchain embeddings model, e.g.
+    ``OllamaEmbeddings`` or ``FastEmbedEmbeddings``."""
+
+    def __init__(self, embeddings: Embeddings):
+        self.embeddings = embeddings
+
+    async def embed(self, texts: List[str]) -> List[List[float]]:
+        try:
+            # Not every Langchain model implements async embedding, so run the
+            # synchronous version on a worker thread
+            return await asyncio.to_thread(self.embeddings.embed_documents, texts)
+        except Exception as ex:
+            # HTTP clients report the status in different places, e.g. Ollama's
+            # `ResponseError.status_code` or httpx's `HTTPStatusError.response`
+            response = getattr(ex, "response", None)
+            status = getattr(ex, "status_code", None) or getattr(response, "status_code", None)
+            headers = getattr(response, "headers", None) or {}
+            raise EmbedError(
+                f"Could not embed {len(texts)} texts: {ex}",
+                status=status if isinstance(status, int) else None,
+                retry_after=parse_retry_after(headers.get("Retry-After")),
+            ) from ex
+
+class EmbedderEmbeddings(Embeddings):
+    """Adapter which exposes an embedder as Langchain embeddings, so it can be
+    used with Langchain vector stores such as Chroma.
+
+    NOTE: The methods of this class block until the embedder is done, so they
+    must not be called from within a running event loop.
+    """
+
+    def __init__(self, embedder: AbstractEmbedder):
+        self.embedder = embedder
+
+    def embed_documents(self, texts: List[str]) -> List[List[float]]:
+        return asyncio.run(self.embedder.embed(list(texts)))
+
+    def embed_query(self, text: str) -> List[float]:
+        return self.embed_documents([text])[0]
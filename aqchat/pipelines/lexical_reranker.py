@@ -0,0 +1,63 @@
+import math
+import re
+from collections import Counter
+from typing import List, Sequence, Tuple
+from pipelines.abstract_reranker import AbstractReranker
+from pipelines.chunk import Chunk
+
+# Words, split further at camelCase humps so that e.g. `parseConfig` matches "config"
+_WORD_PATTERN = re.compile(r'[A-Z]+(?![a-z])|[A-Z]?[a-z]+|\d+')
+
+def tokenize_terms(text: str) -> List[str]:
+    """Split *text* into lowercase terms, splitting identifiers such as
+    ``parse_config`` or ``parseConfig`` into their words, followed by the
+    whole identifiers so that exact identifier matches score higher."""
+    terms = [word.lower() for word in _WORD_PATTERN.findall(text)]
+    terms.extend(identifier.lower() for identifier in re.findall(r'\w*[_A-Z]\w*', text) if len(identifier) > 1)
+    return terms
+
+class LexicalReranker(AbstractReranker):
+    """Reranker scoring candidates with BM25 over the candidates themselves,
+    which favors exact matches of the query terms and needs no model.
+
+    Args:
+        k1: Controls how quickly repeated terms saturate.
+        b: Controls how strongly scores are normalized by chunk length.
+    """
+
+    def __init__(self, k1: float = 1.2, b: float = 0.75):
+        self.k1 = k1
+        self.b = b
+
+    async def rerank(self, query: str, candidates: Sequence[Chunk]) -> List[Tuple[int, float]]:
+        return self.score(query, [chunk.embedding_text for chunk in candidates])
+
+    def score(self, query: str, texts: Sequence[str]) -> List[Tuple[int, float]]:
+        """Score *texts* for *query*, see ``rerank``."""
+        if not texts:
+            return []
+
+        documents = [Counter(tokenize_terms(text)) for text in texts]
+        lengths = [sum(document.values()) for document in documents]
+        average_length = sum(lengths) / len(lengths) or 1.0
+        query_terms = set(tokenize_terms(query))
+
+        # Inverse document frequency within the candidates
+        idf = {}
+        for term in query_terms:
+            frequency = sum(1 for document in documents if term in document)
+            idf[term] = math.log(1 + (len(documents) - frequency + 0.5) / (frequency + 0.5))
+
+        scores = []
+        for index, (document, length) in enumerate(zip(documents, lengths)):
+            score = 0.0
+            for term in query_terms:
+                count = document.get(term, 0)
+                if count:
+                    norm = self.k1 * (1 - self.b + self.b * length / average_length)
+                    score += idf[term] * count * (self.k1 + 1) / (count + norm)
+            scores.append((index, score))
+
+        # Stable, so candidates with equal scores keep their vector order
+        scores.sort(key=lambda item: -item[1])
+        return scores
@@ -0,0 +1,8 @@
+from pipelines.abstract_diagnostics import AbstractDiagnostics, Diagnostic
+
+class NullDiagnostics(AbstractDiagnostics):
+    """Diagnostics sink which discards all diagnostics, which is the default
+    so that nothing is printed unless a sink is given."""
+
+    def emit(self, diagnostic: Diagnostic) -> None:
+        pass
@@ -0,0 +1,93 @@
+import asyncio
+import codecs
+import json
+from typing import Any, Dict, Iterable, Iterator, List, Optional
+from pipelines.abstract_chat import ChatError
+from pipelines.abstract_chat_provider import AbstractChatProvider, Completion
+from pipelines.chat_stream import Transport, urllib_transport
+from pipelines.retry import RetryPolicy, retrying_transport
+
+class OllamaChatProvider(AbstractChatProvider):
+    """Chat provider using the native chat API of an Ollama server, which
+    streams newline-delimited JSON objects.
+
+    Args:
+        base_url: The URL of the server, e.g. ``http://localhost:11434``.
+        model: The name of the model, e.g. ``qwen3:32B``.
+        api_key: Sent as bearer token, e.g. for a server behind a proxy.
+        transport: Sends requests, by default using ``urllib``.
+        retry_policy: Retries requests which failed transiently, by default
+        with the default ``RetryPolicy``.
+    """
+
+    def __init__(
+        self,
+        base_url: str = "http://localhost:11434",
+        model: str = "qwen3:32B",
+        *,
+        api_key: Optional[str] = None,
+        transport: Optional[Transport] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+    ):
+        self.base_url = base_url
+        self.model = model
+        self.api_key = api_key
+        self.transport = retrying_transport(transport or urllib_transport, retry_policy or RetryPolicy())
+
+    async def complete(self, messages: List[Dict[str, str]], options: Optional[Dict[str, Any]] = None) -> Completion:
+        def collect() -> Completion:
+            completion = Completion(text="", model=self.model)
+            parts = []
+            for response in self._request(messages, options):
+                parts.append(response.get("message", {}).get("content", ""))
+                if response.get("done"):
+                    completion.finish_reason = response.get("done_reason")
+            completion.text = "".join(parts)
+            return completion
+
+        return await asyncio.to_thread(collect)
+
+    def stream(self, messages: List[Dict[str, str]], options: Optional[Dict[str, Any]] = None) -> Iterator[str]:
+        for response in self._request(messages, options):
+            content = response.get("message", {}).get("content")
+            if content:
+                yield content
+
+    def _request(self, messages: List[Dict[str, str]], options: Optional[Dict[str, Any]]) -> Iterator[Dict[str, Any]]:
+        """Send a chat request, yielding the response objects until ``done``."""
+        body = {"model": self.model, "messages": messages, "stream": True}
+        if options:
+            body["options"] = options
+
+        headers = {"Content-Type": "application/json"}
+        if self.api_key:
+            headers["Authorization"] = f"Bearer {self.api_key}"
+
+        url = self.base_url.rstrip("/") + "/api/chat"
+        for line in _iter_lines(self.transport(url, json.dumps(body).encode("utf-8"), headers)):
+            if not line.strip():
+                continue
+            try:
+                response = json.loads(line)
+            except json.JSONDecodeError as ex:
+                raise ChatError(f"Malformed Ollama response: {line[:100]!r}") from ex
+
+            if "error" in response:
+                raise ChatError(f"Ollama chat failed: {response['error']}")
+            yield response
+            if response.get("done"):
+                return
+
+        raise ChatError("Ollama response ended before it was done")
+
+def _iter_lines(chunks: Iterable[bytes]) -> Iterator[str]:
+    """Reassemble the lines of a response body arriving in arbitrary *chunks*."""
+    decoder = codecs.getincrementaldecoder("utf-8")()
+    buffer = ""
+    for chunk in chunks:
+        buffer += decoder.decode(chunk)
+        *lines, buffer = buffer.split("\n")
+        yield from lines
+    buffer += decoder.decode(b"", final=True)
+    if buffer:
+        yield buffer
@@ -0,0 +1,47 @@
+import asyncio
+from typing import Any, Dict, Iterator, List, Optional
+from pipelines.abstract_chat_provider import AbstractChatProvider, Completion
+from pipelines.chat_stream import Transport, stream_chat_completion, urllib_transport
+from pipelines.retry import RetryPolicy, retrying_transport
+
+class OpenAIChatProvider(AbstractChatProvider):
+    """Chat provider for OpenAI-compatible HTTP endpoints, which includes
+    OpenAI itself as well as local servers such as Ollama (at ``/v1``),
+    llama.cpp or vLLM.
+
+    Args:
+        base_url: The base URL of the API, e.g. ``https://api.openai.com/v1``.
+        model: The name of the model.
+        api_key: The API key, if the endpoint requires one.
+        transport: Sends requests, by default using ``urllib``.
+        retry_policy: Retries requests which failed transiently, by default
+        with the default ``RetryPolicy``.
+    """
+
+    def __init__(
+        self,
+        base_url: str,
+        model: str,
+        *,
+        api_key: Optional[str] = None,
+        transport: Optional[Transport] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+    ):
+        self.base_url = base_url
+        self.model = model
+        self.api_key = api_key
+        self.transport = retrying_transport(transport or urllib_transport, retry_policy or RetryPolicy())
+
+    async def complete(self, messages: List[Dict[str, str]], options: Optional[Dict[str, Any]] = None) -> Completion:
+        text = await asyncio.to_thread(lambda: "".join(self.stream(messages, options)))
+        return Completion(text=text, model=self.model)
+
+    def stream(self, messages: List[Dict[str, str]], options: Optional[Dict[str, Any]] = None) -> Iterator[str]:
+        return stream_chat_completion(
+            self.base_url,
+            self.model,
+            messages,
+            api_key=self.api_key,
+            options=options,
+            transport=self.transport,
+        )
@@ -0,0 +1,62 @@
+import copy
+from collections import OrderedDict
+from threading import Lock
+from typing import Hashable, List, Optional
+from pipelines.chunk import Chunk
+
+class ParseCache:
+    """Bounded cache of the chunks split from texts, keyed by a hash of their
+    content, so that splitting an unchanged file again skips parsing it.
+
+    The least recently used entries are evicted once the cache holds
+    *capacity* entries. The cache is thread-safe, so it may be shared by
+    splitters splitting files in parallel, and counts its ``hits`` and
+    ``misses`` for tuning the capacity.
+
+    Chunks are copied when they are added and retrieved, so that callers may
+    modify them, e.g. to set their ``path``.
+    """
+
+    def __init__(self, capacity: int = 256):
+        if capacity < 1:
+            raise ValueError("capacity must be at least 1")
+
+        self.capacity = capacity
+        self.hits = 0
+        self.misses = 0
+        self._entries: "OrderedDict[Hashable, List[Chunk]]" = OrderedDict()
+        self._lock = Lock()
+
+    def get(self, key: Hashable) -> Optional[List[Chunk]]:
+        """Return copies of the chunks cached for *key*, or None if there are
+        none, counting a hit or a miss."""
+        with self._lock:
+            chunks = self._entries.get(key)
+            if chunks is None:
+                self.misses += 1
+                return None
+
+            self.hits += 1
+            self._entries.move_to_end(key)
+        return copy.deepcopy(chunks)
+
+    def put(self, key: Hashable, chunks: List[Chunk]) -> None:
+        """Cache copies of *chunks* for *key*, evicting the least recently
+        used entry if the cache is full."""
+        chunks = copy.deepcopy(chunks)
+        with self._lock:
+            self._entries[key] = chunks
+            self._entries.move_to_end(key)
+            while len(self._entries) > self.capacity:
+                self._entries.popitem(last=False)
+
+    def clear(self) -> None:
+        """Remove all entries and reset the counters."""
+        with self._lock:
+            self._entries.clear()
+            self.hits = 0
+            self.misses = 0
+
+    def __len__(self) -> int:
+        with self._lock:
+            return len(self._entries)
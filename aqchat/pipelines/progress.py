@@ -0,0 +1,52 @@
+from dataclasses import dataclass
+from typing import Callable, Optional, Union
+from pipelines.split_errors import SplitError
+
+@dataclass(frozen=True)
+class FileStarted:
+    """A file is about to be read and split."""
+
+    path: str
+
+@dataclass(frozen=True)
+class FileChunked:
+    """A file was split into *chunk_count* chunks."""
+
+    path: str
+    chunk_count: int
+
+@dataclass(frozen=True)
+class FileFailed:
+    """A file could not be read or split and was skipped."""
+
+    path: str
+    error: SplitError
+
+@dataclass(frozen=True)
+class EmbeddingBatch:
+    """A batch of chunks was embedded, *done* of the *total* chunks so far."""
+
+    done: int
+    total: int
+
+@dataclass(frozen=True)
+class Finished:
+    """Indexing finished, with the number of files attempted, chunks produced
+    and files which failed."""
+
+    files: int
+    chunks: int
+    failures: int
+
+ProgressEvent = Union[FileStarted, FileChunked, FileFailed, EmbeddingBatch, Finished]
+
+# Called with each event as indexing progresses. Under the concurrent code
+# paths (e.g. ``par_index_dir``), the file events are reported from the worker
+# threads, so the callback may be called from several threads at once and must
+# be thread-safe.
+ProgressCallback = Callable[[ProgressEvent], None]
+
+def report(progress: Optional[ProgressCallback], event: ProgressEvent) -> None:
+    """Report *event* to *progress*, if given."""
+    if progress is not None:
+        progress(event)
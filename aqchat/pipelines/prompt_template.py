@@ -0,0 +1,77 @@
+from string import Formatter
+from typing import Dict, List, Optional, Set
+
+# The placeholders which may be used in a template
+PLACEHOLDERS = {"context", "question", "citations_instruction"}
+
+# The placeholders which every template must use, in either message
+REQUIRED_PLACEHOLDERS = {"context", "question"}
+
+DEFAULT_SYSTEM_TEMPLATE = "{citations_instruction}\n\nContext:\n\n{context}"
+DEFAULT_USER_TEMPLATE = "{question}"
+
+class PromptTemplate:
+    """Template of the chat messages asking a question with retrieved context.
+
+    The *system* and *user* templates are rendered into the system message and
+    the user message, substituting the named placeholders:
+
+    - ``{context}``: the numbered context passages
+    - ``{question}``: the question of the user
+    - ``{citations_instruction}``: the instructions on how to cite the passages
+
+    Literal braces are written as ``{{`` and ``}}``. The default templates
+    give the prompt used by ``answer_with_citations``.
+
+    Raises:
+        ValueError: If a template uses an unknown placeholder, or if neither
+        template uses ``{context}`` or ``{question}``.
+    """
+
+    def __init__(self, system: str = DEFAULT_SYSTEM_TEMPLATE, user: str = DEFAULT_USER_TEMPLATE):
+        used = _placeholders(system) | _placeholders(user)
+
+        unknown = used - PLACEHOLDERS
+        if unknown:
+            raise ValueError(f"unknown placeholders in prompt template: {', '.join(sorted(unknown))}")
+        missing = REQUIRED_PLACEHOLDERS - used
+        if missing:
+            raise ValueError(f"prompt template is missing placeholders: {', '.join(sorted(missing))}")
+
+        self.system = system
+        self.user = user
+
+    def render(
+        self,
+        question: str,
+        context: str,
+        citations_instruction: str = "",
+        system_prompt: Optional[str] = None,
+    ) -> List[Dict[str, str]]:
+        """Return the system and user message asking *question* with *context*,
+        where the system message starts with *system_prompt* if given."""
+        values = {"context": context, "question": question, "citations_instruction": citations_instruction}
+        system = self.system.format(**values)
+        if system_prompt:
+            system = f"{system_prompt}\n\n{system}"
+
+        return [
+            {"role": "system", "content": system},
+            {"role": "user", "content": self.user.format(**values)},
+        ]
+
+def _placeholders(template: str) -> Set[str]:
+    """Return the names of the placeholders in *template*.
+
+    Raises:
+        ValueError: If *template* is not a valid format string, or uses
+        positional, indexed or attribute placeholders.
+    """
+    names = set()
+    for _, name, _, _ in Formatter().parse(template):
+        if name is None:
+            continue
+        if not name.isidentifier():
+            raise ValueError(f"invalid placeholder in prompt template: {{{name}}}")
+        names.add(name)
+    return names
@@ -0,0 +1,92 @@
+from threading import Lock
+from typing import Any, Dict, Iterator, List
+
+from langchain_core.messages import AIMessageChunk
+from langchain_core.messages.base import BaseMessageChunk
+from pipelines.abstract_chat import AbstractChatPipeline
+from pipelines.abstract_chat_provider import AbstractChatProvider
+from pipelines.abstract_memory import AbstractMemoryPipeline
+from pipelines.conversation import Conversation
+
+# Generation parameters among the chat settings which are passed to the provider
+_OPTION_KEYS = ("temperature", "top_k", "top_p", "min_p", "num_ctx", "repeat_last_n", "repeat_penalty")
+
+class ProviderChatPipeline(AbstractChatPipeline):
+    """Chat implementation which answers through any chat provider, with the
+    documents retrieved for the latest question placed in the system prompt.
+
+    NOTE: This class MUST be thread-safe as streamlit runs multiple worker threads
+    and the pipeline for a given repo will be cached, meaning several users
+    having sessions may result in concurrent accesses.
+    """
+
+    # --------------------------------------------------------------
+    # INITIALISATION
+    # --------------------------------------------------------------
+
+    def __init__(
+        self,
+        provider: AbstractChatProvider,
+        memory: AbstractMemoryPipeline = None,
+        *,
+        chat_settings: Dict[str, Any] = None,
+        option_keys: tuple = _OPTION_KEYS,
+    ) -> None:
+        # allocate mutex
+        self.lock = Lock()
+
+        self.provider = provider
+        self.memory = memory
+
+        chat_settings = chat_settings or {}
+        self.system_prompt = chat_settings.get("system_prompt")
+        self.max_tokens = chat_settings.get("num_ctx")
+        self.options = {key: chat_settings[key] for key in option_keys if key in chat_settings}
+
+    def query(self, messages: List[Dict[str, str]]) -> Iterator[BaseMessageChunk]:
+        """Stream an answer for *messages*.
+
+        ``messages`` must be a list of chat messages of the form
+        ``[{"role": "user" | "assistant" | "system", "content": "..."}, ...]``.
+        The final user message is treated as the question for retrieval.
+        """
+        with self.lock:
+            if self.memory and not self.memory.ready_for_retrieval():
+                raise RuntimeError("Call .ingest(<path>) before querying.")
+
+            question = self._extract_latest_user_message(messages)
+            if question is None:
+                raise ValueError("No user message found in conversation history.")
+
+            context = None
+            if self.memory:
+                docs = self.memory.invoke(question)
+                context = "Context:\n\n" + "\n\n".join(
+                    f"{doc.metadata.get('source', '')}\n{doc.page_content}" for doc in docs
+                )
+
+            # Drop the oldest turns which don't fit into the context window
+            # together with the retrieved context
+            conversation = Conversation.from_messages(
+                messages, system_prompt=self.system_prompt, max_tokens=self.max_tokens
+            )
+            conversation.trim(conversation.tokenizer.count_tokens(context) if context else 0)
+
+            prompt = conversation.messages
+            if context:
+                if prompt and prompt[0]["role"] == "system":
+                    prompt[0]["content"] += "\n\n" + context
+                else:
+                    prompt.insert(0, {"role": "system", "content": context})
+
+            # Stream outside of the lock, so that a slow model doesn't block other sessions
+            stream = self.provider.stream(prompt, self.options or None)
+
+        return (AIMessageChunk(content=delta) for delta in stream)
+
+    def _extract_latest_user_message(self, messages: List[Dict[str, str]]) -> str | None:
+        """Return the content of the *most recent* user message, or ``None``."""
+        for msg in reversed(messages):
+            if msg.get("role") == "user":
+                return msg.get("content", "")
+        return None
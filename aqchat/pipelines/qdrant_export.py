@@ -0,0 +1,152 @@
+import json
+import urllib.error
+import urllib.parse
+import urllib.request
+import uuid
+from typing import Any, Callable, Dict, List, Optional, Sequence, TextIO, Tuple
+from pipelines.abstract_vector_store import VectorStoreError
+from pipelines.chunk import Chunk
+
+# Sends an HTTP request given the method, URL, body and headers, returning
+# the status code and body of the response
+HttpTransport = Callable[[str, str, Optional[bytes], Dict[str, str]], Tuple[int, bytes]]
+
+def qdrant_point_id(chunk: Chunk) -> str:
+    """The ID of the Qdrant point of *chunk*, which is ``Chunk.id`` as a UUID,
+    as Qdrant only accepts integer and UUID point IDs. Exporting a chunk again
+    therefore overwrites its point."""
+    return str(uuid.UUID(hex=chunk.id()))
+
+def to_qdrant_point(chunk: Chunk, vector: Sequence[float]) -> Dict[str, Any]:
+    """Convert *chunk* with its embedding *vector* into a Qdrant point.
+
+    The payload holds the fields of ``Chunk.to_dict`` (see ``write_jsonl``),
+    e.g. ``path``, ``start_line``, ``end_line``, ``kind`` and ``name``, so
+    ``Chunk.from_dict`` recreates the chunk from it, and ``chunk_id``, the
+    ``Chunk.id`` of the chunk.
+    """
+    payload = chunk.to_dict()
+    payload["chunk_id"] = chunk.id()
+    return {"id": qdrant_point_id(chunk), "vector": [float(value) for value in vector], "payload": payload}
+
+def qdrant_batch(chunks: Sequence[Chunk], vectors: Sequence[Sequence[float]]) -> Dict[str, Any]:
+    """Convert *chunks* with their *vectors* into the body of a request to
+    Qdrant's upsert points API, i.e. ``{"points": [...]}``.
+
+    Raises:
+        VectorStoreError: If the number of vectors doesn't match the number of
+        chunks, or the vectors have different dimensions.
+    """
+    if len(chunks) != len(vectors):
+        raise VectorStoreError(f"Got {len(vectors)} vectors for {len(chunks)} chunks")
+    if len({len(vector) for vector in vectors}) > 1:
+        raise VectorStoreError("All vectors of a batch must have the same dimension")
+
+    return {"points": [to_qdrant_point(chunk, vector) for chunk, vector in zip(chunks, vectors)]}
+
+def write_qdrant_batch(chunks: Sequence[Chunk], vectors: Sequence[Sequence[float]], writer: TextIO) -> None:
+    """Write the batch of *chunks* and *vectors* to *writer* as JSON, which
+    can be sent to Qdrant's upsert points API as it is, see ``qdrant_batch``."""
+    json.dump(qdrant_batch(chunks, vectors), writer, ensure_ascii=False)
+
+def urllib_http_transport(method: str, url: str, body: Optional[bytes], headers: Dict[str, str]) -> Tuple[int, bytes]:
+    """Transport which sends the request with ``urllib``.
+
+    Raises:
+        VectorStoreError: If the server could not be reached.
+    """
+    request = urllib.request.Request(url, data=body, headers=headers, method=method)
+    try:
+        with urllib.request.urlopen(request) as response:
+            return response.status, response.read()
+    except urllib.error.HTTPError as ex:
+        return ex.code, ex.read()
+    except urllib.error.URLError as ex:
+        raise VectorStoreError(f"Could not reach {url}: {ex.reason}") from ex
+
+class QdrantUploader:
+    """Uploads chunks and their vectors to a collection of a Qdrant server via
+    its HTTP API, creating the collection if it doesn't exist.
+
+    Uploading is opt-in: nothing is sent to a server unless an uploader is
+    created, otherwise use ``write_qdrant_batch`` to export a file.
+
+    Args:
+        url: The URL of the server, e.g. ``http://localhost:6333``.
+        collection: The name of the collection.
+        api_key: The API key, if the server requires one.
+        batch_size: The maximum number of points per upsert request.
+        transport: Sends requests, by default using ``urllib``.
+    """
+
+    def __init__(
+        self,
+        url: str,
+        collection: str,
+        *,
+        api_key: Optional[str] = None,
+        batch_size: int = 256,
+        transport: Optional[HttpTransport] = None,
+    ):
+        if batch_size < 1:
+            raise ValueError("batch_size must be at least 1")
+
+        self.url = url.rstrip("/")
+        self.collection = collection
+        self.api_key = api_key
+        self.batch_size = batch_size
+        self.transport = transport or urllib_http_transport
+
+    def ensure_collection(self, dimension: int) -> None:
+        """Create the collection for vectors of *dimension* with cosine
+        distance, unless it exists.
+
+        Raises:
+            VectorStoreError: If the collection exists with another vector
+            dimension, or a request failed.
+        """
+        status, body = self._request("GET", "")
+        if status == 404:
+            self._check(*self._request("PUT", "", {"vectors": {"size": dimension, "distance": "Cosine"}}))
+            return
+        self._check(status, body)
+
+        vectors = json.loads(body)["result"]["config"]["params"]["vectors"]
+        size = vectors.get("size") if isinstance(vectors, dict) else None
+        if size != dimension:
+            raise VectorStoreError(
+                f"Qdrant collection {self.collection!r} has vectors of dimension {size}, "
+                f"but the vectors to upload have dimension {dimension}"
+            )
+
+    def upload(self, chunks: Sequence[Chunk], vectors: Sequence[Sequence[float]]) -> None:
+        """Upsert *chunks* with their *vectors* into the collection, creating
+        it first if needed, see ``ensure_collection``.
+
+        Raises:
+            VectorStoreError: If the vectors don't match the chunks or the
+            collection, or a request failed.
+        """
+        batch = qdrant_batch(chunks, vectors)
+        points: List[Dict[str, Any]] = batch["points"]
+        if not points:
+            return
+
+        self.ensure_collection(len(points[0]["vector"]))
+        for start in range(0, len(points), self.batch_size):
+            body = {"points": points[start:start + self.batch_size]}
+            self._check(*self._request("PUT", "/points?wait=true", body))
+
+    def _request(self, method: str, path: str, body: Optional[Dict[str, Any]] = None) -> Tuple[int, bytes]:
+        headers = {"Content-Type": "application/json"}
+        if self.api_key:
+            headers["api-key"] = self.api_key
+
+        url = f"{self.url}/collections/{urllib.parse.quote(self.collection, safe='')}{path}"
+        data = json.dumps(body).encode("utf-8") if body is not None else None
+        return self.transport(method, url, data, headers)
+
+    def _check(self, status: int, body: bytes) -> None:
+        if not 200 <= status < 300:
+            detail = body.decode("utf-8", errors="replace")[:200]
+            raise VectorStoreError(f"Qdrant request for collection {self.collection!r} failed with status {status}: {detail}")
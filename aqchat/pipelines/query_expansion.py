@@ -0,0 +1,37 @@
+from typing import Any, Dict, List, Optional
+from pipelines.abstract_chat import ChatError
+from pipelines.abstract_chat_provider import AbstractChatProvider
+
+# Asks for a hypothetical answer, which is embedded instead of the bare
+# question, as it looks more like the code it should be retrieved with
+HYPOTHESIS_PROMPT = (
+    "Write a short hypothetical answer to the question about a code base, "
+    "including a code snippet that could be part of the answer. It doesn't "
+    "need to be correct, only plausible. Don't explain that it is hypothetical."
+)
+
+def hypothesis_messages(question: str) -> List[Dict[str, str]]:
+    """Build the chat messages asking for a hypothetical answer to *question*."""
+    return [
+        {"role": "system", "content": HYPOTHESIS_PROMPT},
+        {"role": "user", "content": question},
+    ]
+
+async def expand_query(
+    provider: AbstractChatProvider,
+    question: str,
+    options: Optional[Dict[str, Any]] = None,
+) -> str:
+    """Expand *question* for retrieval with a hypothetical answer generated by
+    *provider* (HyDE), returning the question followed by the hypothesis.
+
+    If the hypothesis could not be generated or is empty, the question is
+    returned as it is, so retrieval never fails because of the expansion.
+    """
+    try:
+        completion = await provider.complete(hypothesis_messages(question), options)
+    except ChatError:
+        return question
+
+    hypothesis = completion.text.strip()
+    return f"{question}\n\n{hypothesis}" if hypothesis else question
@@ -0,0 +1,96 @@
+import unicodedata
+from typing import Callable, List, Sequence
+
+# Separators of prose, from paragraphs down to words
+DEFAULT_SEPARATORS = ("\n\n", "\n", ". ", " ")
+
+def recursive_split(
+    text: str,
+    max_size: int,
+    separators: Sequence[str] = DEFAULT_SEPARATORS,
+    size: Callable[[str], int] = len,
+) -> List[str]:
+    """Split *text* into pieces of at most *max_size*, as measured by *size*.
+
+    The text is cut after the first of *separators* that occurs in it, and the
+    pieces between cuts are packed into as few pieces as the limit allows.
+    Pieces which are still too large are split by the next separators in
+    turn, and as a last resort between characters, where possible without
+    splitting a character from the combining marks or joiners following it.
+
+    Separators are kept at the end of the piece before them, so joining the
+    pieces gives exactly *text*.
+    """
+    if size(text) <= max_size:
+        return [text] if text else []
+
+    for index, separator in enumerate(separators):
+        if separator not in text:
+            continue
+
+        pieces = []
+        current = ""
+        for part in _split_after(text, separator):
+            if size(current + part) <= max_size:
+                current += part
+                continue
+
+            if current:
+                pieces.append(current)
+            if size(part) <= max_size:
+                current = part
+            else:
+                pieces.extend(recursive_split(part, max_size, separators[index + 1:], size))
+                current = ""
+
+        if current:
+            pieces.append(current)
+        return pieces
+
+    return _split_characters(text, max_size, size)
+
+def _split_after(text: str, separator: str) -> List[str]:
+    """Split *text* after each occurrence of *separator*."""
+    parts = text.split(separator)
+    return [part + separator for part in parts[:-1]] + ([parts[-1]] if parts[-1] else [])
+
+def _split_characters(text: str, max_size: int, size: Callable[[str], int]) -> List[str]:
+    """Split *text* into the longest prefixes within *max_size* (of at least
+    one character each)."""
+    pieces = []
+
+    while text:
+        # Find the longest prefix that still fits (at least one character)
+        lo, hi = 1, len(text)
+        while lo < hi:
+            mid = (lo + hi + 1) // 2
+            if size(text[:mid]) <= max_size:
+                lo = mid
+            else:
+                hi = mid - 1
+
+        # Move the cut back before any sequence of joined characters, unless
+        # that leaves nothing, in which case the sequence has to be split
+        cut = lo
+        while 0 < cut < len(text) and _joins_previous(text, cut):
+            cut -= 1
+        if cut > 0:
+            lo = cut
+
+        pieces.append(text[:lo])
+        text = text[lo:]
+
+    return pieces
+
+def _joins_previous(text: str, i: int) -> bool:
+    """Return True if the character at index *i* of *text* belongs to the same
+    user-perceived character as the one before it, e.g. a combining accent."""
+    char = text[i]
+    return (
+        unicodedata.combining(char) != 0
+        or unicodedata.category(char) in ('Mn', 'Me')
+        or '\ufe00' <= char <= '\ufe0f'  # variation selectors
+        or '\U0001f3fb' <= char <= '\U0001f3ff'  # emoji skin tone modifiers
+        or char == '\u200d'  # zero width joiner
+        or text[i - 1] == '\u200d'
+    )
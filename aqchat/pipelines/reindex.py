@@ -0,0 +1,40 @@
+import hashlib
+from dataclasses import dataclass, field
+from enum import Enum
+from typing import Dict, List
+from pipelines.chunk import Chunk, SplitFailure
+
+class FileStatus(Enum):
+    """How a file changed since it was last indexed."""
+
+    ADDED = "added"
+    MODIFIED = "modified"
+    UNCHANGED = "unchanged"
+    DELETED = "deleted"
+
+@dataclass
+class ReindexResult:
+    """The result of incrementally reindexing a directory.
+
+    To update a vector store with a minimal diff, delete the chunks of every
+    file whose status is MODIFIED or DELETED, then add ``chunks``, which hold
+    the chunks of the ADDED and MODIFIED files only.
+
+    ``hashes`` maps the path of every file which was indexed successfully to
+    its content hash, and should be passed to the next reindex. Files which
+    failed to split are reported in ``failures`` and left out of ``hashes``,
+    so that they are retried next time.
+    """
+
+    statuses: Dict[str, FileStatus] = field(default_factory=dict)
+    hashes: Dict[str, str] = field(default_factory=dict)
+    chunks: List[Chunk] = field(default_factory=list)
+    failures: List[SplitFailure] = field(default_factory=list)
+
+    def paths_with_status(self, *statuses: FileStatus) -> List[str]:
+        """Return the sorted paths of the files with any of *statuses*."""
+        return sorted(path for path, status in self.statuses.items() if status in statuses)
+
+def content_hash(data: bytes) -> str:
+    """Return the hash of the contents of a file, as a hex string."""
+    return hashlib.sha256(data).hexdigest()
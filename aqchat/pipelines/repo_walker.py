@@ -0,0 +1,188 @@
+import os
+import re
+from pathlib import Path
+from typing import Iterable, Iterator, List, Optional, Tuple
+
+# Number of bytes at the start of a file which are scanned for NUL bytes
+_BINARY_SCAN_BYTES = 8192
+
+def glob_to_regex(pattern: str) -> str:
+    """Translate a glob *pattern* over `/`-separated paths into a regex.
+
+    `*` and `?` don't match `/`, while `**` matches any number of directories,
+    so e.g. `src/**/*.rs` matches both `src/main.rs` and `src/a/b/lib.rs`.
+    """
+    regex = []
+    i = 0
+    while i < len(pattern):
+        char = pattern[i]
+        if pattern.startswith('**/', i):
+            regex.append('(?:.*/)?')
+            i += 3
+        elif pattern.startswith('**', i):
+            regex.append('.*')
+            i += 2
+        elif char == '*':
+            regex.append('[^/]*')
+            i += 1
+        elif char == '?':
+            regex.append('[^/]')
+            i += 1
+        elif char == '[':
+            end = pattern.find(']', i + 2)
+            if end < 0:
+                regex.append(re.escape(char))
+                i += 1
+                continue
+            chars = pattern[i + 1:end]
+            if chars.startswith('!'):
+                chars = '^' + chars[1:]
+            regex.append('[' + chars.replace('\\', '\\\\') + ']')
+            i = end + 1
+        elif char == '\\' and i + 1 < len(pattern):
+            regex.append(re.escape(pattern[i + 1]))
+            i += 2
+        else:
+            regex.append(re.escape(char))
+            i += 1
+    return ''.join(regex)
+
+class _IgnoreFile:
+    """The patterns of one ignore file, e.g. a `.gitignore`, which apply to
+    paths below the directory *base* (relative to the repository root)."""
+
+    def __init__(self, base: str, text: str):
+        self.base = base
+        self.rules: List[Tuple[re.Pattern, bool, bool]] = []  # (regex, negated, dir_only)
+
+        for line in text.splitlines():
+            # Trailing spaces are ignored unless escaped
+            if not line.endswith('\\ '):
+                line = line.rstrip()
+            if not line or line.startswith('#'):
+                continue
+
+            negated = line.startswith('!')
+            if negated:
+                line = line[1:]
+            elif line.startswith(('\\!', '\\#')):
+                line = line[1:]
+
+            dir_only = line.endswith('/')
+            line = line.rstrip('/')
+            if not line:
+                continue
+
+            # Patterns containing a slash are relative to the ignore file's
+            # directory, others match at any depth below it
+            if '/' in line:
+                regex = glob_to_regex(line.lstrip('/'))
+            else:
+                regex = '(?:.*/)?' + glob_to_regex(line)
+
+            self.rules.append((re.compile(regex + '$'), negated, dir_only))
+
+    def match(self, path: str, is_dir: bool) -> Optional[bool]:
+        """Return whether *path* (relative to the repository root) is ignored,
+        or None if no pattern of this file matches it."""
+        if self.base:
+            if not path.startswith(self.base + '/'):
+                return None
+            path = path[len(self.base) + 1:]
+
+        result = None
+        for regex, negated, dir_only in self.rules:
+            if dir_only and not is_dir:
+                continue
+            if regex.match(path):
+                result = not negated
+        return result
+
+def _read_ignore_file(path: Path) -> Optional[str]:
+    try:
+        return path.read_text('utf-8', errors='replace')
+    except OSError:
+        return None
+
+def is_binary_file(path: str | os.PathLike) -> bool:
+    """Return True if the file at *path* looks binary, i.e. contains a NUL byte
+    in its first few KB."""
+    with open(path, 'rb') as file:
+        return b'\0' in file.read(_BINARY_SCAN_BYTES)
+
+def walk_repo(
+    root: str | os.PathLike,
+    include: Optional[Iterable[str]] = None,
+    exclude: Optional[Iterable[str]] = None,
+) -> Iterator[Path]:
+    """Walk the files of the repository at *root* in sorted order (see
+    ``path_sort_key``), yielding their paths relative to *root*.
+
+    Files ignored by `.gitignore` files (including nested ones) or by
+    `.git/info/exclude` are skipped, as are the `.git` directory and binary files.
+
+    Args:
+        root: The root directory of the repository.
+        include: If given, only files whose relative path matches one of these
+        globs are yielded, e.g. `src/**/*.rs`.
+        exclude: Files whose relative path matches one of these globs are skipped.
+    """
+    root = Path(root)
+    include_regex = _compile_globs(include)
+    exclude_regex = _compile_globs(exclude)
+
+    ignore_files = []
+    exclude_text = _read_ignore_file(root / '.git' / 'info' / 'exclude')
+    if exclude_text is not None:
+        ignore_files.append(_IgnoreFile('', exclude_text))
+
+    def is_ignored(path: str, is_dir: bool, ignore_files: List[_IgnoreFile]) -> bool:
+        # Later (i.e. deeper) ignore files take precedence
+        for ignore_file in reversed(ignore_files):
+            result = ignore_file.match(path, is_dir)
+            if result is not None:
+                return result
+        return False
+
+    def walk(directory: Path, relative: str, ignore_files: List[_IgnoreFile]) -> Iterator[Path]:
+        gitignore_text = _read_ignore_file(directory / '.gitignore')
+        if gitignore_text is not None:
+            ignore_files = ignore_files + [_IgnoreFile(relative, gitignore_text)]
+
+        try:
+            entries = sorted(os.scandir(directory), key=lambda entry: entry.name)
+        except OSError:
+            return
+
+        for entry in entries:
+            path = f"{relative}/{entry.name}" if relative else entry.name
+            is_dir = entry.is_dir(follow_symlinks=False)
+
+            if is_dir and entry.name == '.git':
+                continue
+            if is_ignored(path, is_dir, ignore_files):
+                continue
+
+            if is_dir:
+                yield from walk(Path(entry.path), path, ignore_files)
+            elif entry.is_file():
+                if include_regex is not None and not include_regex.match(path):
+                    continue
+                if exclude_regex is not None and exclude_regex.match(path):
+                    continue
+                if is_binary_file(entry.path):
+                    continue
+                yield Path(path)
+
+    yield from walk(root, '', ignore_files)
+
+def path_sort_key(path: str | os.PathLike) -> Tuple[str, ...]:
+    """Sort key of relative paths in the order ``walk_repo`` yields them, i.e.
+    by their components, so that e.g. `a/b.rs` comes before `a.rs`."""
+    return Path(path).parts
+
+def _compile_globs(globs: Optional[Iterable[str]]) -> Optional[re.Pattern]:
+    """Compile *globs* into one regex matching any of them, or None if not given."""
+    if globs is None:
+        return None
+    return re.compile('(?:' + '|'.join(glob_to_regex(glob) for glob in globs) + ')$')
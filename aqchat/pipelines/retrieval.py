@@ -0,0 +1,43 @@
+from typing import Callable, List, Optional, Sequence, Tuple
+from pipelines.abstract_reranker import AbstractReranker
+from pipelines.abstract_vector_store import AbstractVectorStore
+from pipelines.chunk import Chunk
+
+async def retrieve(
+    store: AbstractVectorStore,
+    query_vector: Sequence[float],
+    query: str,
+    k: int,
+    *,
+    reranker: Optional[AbstractReranker] = None,
+    candidates: Optional[int] = None,
+    where: Optional[Callable[[Chunk], bool]] = None,
+    min_score: Optional[float] = None,
+) -> List[Tuple[Chunk, float]]:
+    """Retrieve the *k* chunks most relevant to a query.
+
+    Without a *reranker*, these are the top *k* chunks by vector similarity.
+    Otherwise, the top *candidates* chunks (by default ``4 * k``) are fetched
+    from the store, and the top *k* after reranking them are kept, with the
+    reranker's scores.
+
+    Args:
+        store: The vector store to search.
+        query_vector: The embedding of the query.
+        query: The text of the query, for the reranker.
+        k: The number of chunks to return.
+        reranker: Reranks the candidates, if given.
+        candidates: The number of candidates fetched for reranking.
+        where: Only chunks for which it returns True are retrieved, if given,
+        e.g. ``SearchQuery.matches``.
+        min_score: Chunks less similar to the query vector are not retrieved,
+        if given, see ``AbstractVectorStore.search``. With a reranker, the
+        candidates are filtered before reranking.
+    """
+    if reranker is None:
+        return store.search(query_vector, k, where=where, min_score=min_score)
+
+    fetched = store.search(query_vector, max(candidates or 4 * k, k), where=where, min_score=min_score)
+    chunks = [chunk for chunk, _ in fetched]
+    ranking = await reranker.rerank(query, chunks)
+    return [(chunks[index], score) for index, score in ranking[:k]]
@@ -0,0 +1,130 @@
+import asyncio
+import random
+import time
+from datetime import datetime, timezone
+from email.utils import parsedate_to_datetime
+from typing import Any, Awaitable, Callable, Collection, Dict, Iterable, Iterator, Optional, TypeVar
+
+T = TypeVar("T")
+
+# See `chat_stream.Transport`
+Transport = Callable[[str, bytes, Dict[str, str]], Iterable[bytes]]
+
+# HTTP status codes of transient failures: request timeout, rate limiting, and
+# server errors which usually go away on their own
+DEFAULT_RETRYABLE_STATUSES = frozenset({408, 429, 500, 502, 503, 504})
+
+class RetryPolicy:
+    """Policy for retrying calls which failed transiently, with exponential
+    backoff and jitter.
+
+    An error is retried if it has a ``status`` (e.g. ``ChatError.status``) among
+    *retryable_statuses*, or has no status and was caused by an ``OSError``,
+    e.g. a refused connection or a timeout. Any other error, e.g. a 400 or 401,
+    is raised immediately.
+
+    The delay before the n-th retry is ``base_delay * 2 ** (n - 1)``, at most
+    *max_delay*, of which a random fraction of up to *jitter* is subtracted so
+    that clients don't retry in lockstep. If the error has a ``retry_after``
+    (e.g. from a ``Retry-After`` header), that delay is used instead.
+
+    Args:
+        max_attempts: The maximum number of attempts, including the first.
+        base_delay: The delay before the first retry in seconds.
+        max_delay: The maximum backoff delay in seconds.
+        jitter: The maximum fraction of the delay which is randomized.
+        retryable_statuses: The status codes which are retried.
+        sleep: Waits for the given number of seconds, for retries of
+        synchronous calls.
+    """
+
+    def __init__(
+        self,
+        max_attempts: int = 4,
+        base_delay: float = 0.5,
+        max_delay: float = 30.0,
+        jitter: float = 0.5,
+        retryable_statuses: Collection[int] = DEFAULT_RETRYABLE_STATUSES,
+        sleep: Callable[[float], Any] = time.sleep,
+    ):
+        if max_attempts < 1:
+            raise ValueError("max_attempts must be at least 1")
+        self.max_attempts = max_attempts
+        self.base_delay = base_delay
+        self.max_delay = max_delay
+        self.jitter = jitter
+        self.retryable_statuses = frozenset(retryable_statuses)
+        self.sleep = sleep
+
+    def is_retryable(self, error: BaseException) -> bool:
+        """Return True if *error* is a transient failure worth retrying."""
+        status = getattr(error, "status", None)
+        if status is not None:
+            return status in self.retryable_statuses
+        return isinstance(error, OSError) or isinstance(error.__cause__, OSError)
+
+    def delay(self, retry: int, error: BaseException) -> float:
+        """Return the delay in seconds before the *retry*-th retry after *error*."""
+        retry_after = getattr(error, "retry_after", None)
+        if retry_after is not None:
+            return max(retry_after, 0.0)
+
+        delay = min(self.base_delay * 2 ** (retry - 1), self.max_delay)
+        return delay * (1.0 - self.jitter * random.random())
+
+    def call(self, fn: Callable[[], T]) -> T:
+        """Call *fn* until it succeeds, its error is not retryable, or the
+        attempts are exhausted, in which case the last error is raised."""
+        for attempt in range(1, self.max_attempts + 1):
+            try:
+                return fn()
+            except Exception as ex:
+                if attempt == self.max_attempts or not self.is_retryable(ex):
+                    raise
+                self.sleep(self.delay(attempt, ex))
+
+    async def acall(self, fn: Callable[[], Awaitable[T]]) -> T:
+        """Like ``call``, but for coroutine functions, waiting with ``asyncio.sleep``."""
+        for attempt in range(1, self.max_attempts + 1):
+            try:
+                return await fn()
+            except Exception as ex:
+                if attempt == self.max_attempts or not self.is_retryable(ex):
+                    raise
+                await asyncio.sleep(self.delay(attempt, ex))
+
+def retrying_transport(transport: Transport, policy: RetryPolicy) -> Transport:
+    """Wrap *transport* so that requests are retried according to *policy*.
+
+    A request is only retried if it fails before the first chunk of the
+    response arrives, since a partially consumed stream can't be replayed.
+    """
+    def send(url: str, body: bytes, headers: Dict[str, str]) -> Iterator[bytes]:
+        def attempt():
+            chunks = iter(transport(url, body, headers))
+            return next(chunks, None), chunks
+
+        first, chunks = policy.call(attempt)
+        if first is not None:
+            yield first
+            yield from chunks
+
+    return send
+
+def parse_retry_after(value: Optional[str]) -> Optional[float]:
+    """Parse the value of a ``Retry-After`` header, which is either a number
+    of seconds or an HTTP date, into a delay in seconds."""
+    if not value:
+        return None
+    try:
+        return max(float(value), 0.0)
+    except ValueError:
+        pass
+
+    try:
+        date = parsedate_to_datetime(value)
+    except (TypeError, ValueError):
+        return None
+    if date.tzinfo is None:
+        date = date.replace(tzinfo=timezone.utc)
+    return max((date - datetime.now(timezone.utc)).total_seconds(), 0.0)
@@ -0,0 +1,14 @@
+from typing import List, Optional
+from pipelines.abstract_embedder import AbstractEmbedder
+from pipelines.retry import RetryPolicy
+
+class RetryingEmbedder(AbstractEmbedder):
+    """Embedder which retries transient failures of another embedder, e.g.
+    rate limiting by the embedding endpoint, according to a retry policy."""
+
+    def __init__(self, embedder: AbstractEmbedder, policy: Optional[RetryPolicy] = None):
+        self.embedder = embedder
+        self.policy = policy or RetryPolicy()
+
+    async def embed(self, texts: List[str]) -> List[List[float]]:
+        return await self.policy.acall(lambda: self.embedder.embed(texts))
@@ -0,0 +1,84 @@
+from typing import Callable, List, Optional, Sequence, Set
+from pipelines.chunk import Chunk, ChunkKind
+from pipelines.detectors import Language
+
+class SearchQuery:
+    """Builder for a vector store search restricted by the chunks' metadata.
+
+    Example:
+    ```
+    query = SearchQuery(vector).filter_language(Language.RUST).filter_path_prefix("src/api").k(8)
+    results = store.search_query(query)
+    ```
+
+    Each kind of filter restricts the results further, and a filter given
+    several values matches any of them.
+    """
+
+    def __init__(self, vector: Sequence[float], k: int = 6):
+        self.vector = list(vector)
+        self.top_k = k
+        self.score_threshold: Optional[float] = None
+        self.languages: Optional[Set[Language]] = None
+        self.path_prefixes: Optional[List[str]] = None
+        self.kinds: Optional[Set[ChunkKind]] = None
+        self.predicates: List[Callable[[Chunk], bool]] = []
+
+    def k(self, k: int) -> "SearchQuery":
+        """Return up to *k* chunks."""
+        self.top_k = k
+        return self
+
+    def min_score(self, min_score: float) -> "SearchQuery":
+        """Only return chunks scoring at least *min_score*, see
+        ``AbstractVectorStore.search``."""
+        self.score_threshold = min_score
+        return self
+
+    def filter_language(self, *languages: Language) -> "SearchQuery":
+        """Only match chunks split as one of *languages*."""
+        self.languages = set(languages)
+        return self
+
+    def filter_path_prefix(self, *prefixes: str) -> "SearchQuery":
+        """Only match chunks of files in one of the directories *prefixes*,
+        e.g. `src/api` matches `src/api/routes.rs` but not `src/apis.rs`."""
+        self.path_prefixes = [prefix.rstrip("/") for prefix in prefixes]
+        return self
+
+    def filter_kind(self, *kinds: ChunkKind) -> "SearchQuery":
+        """Only match chunks of one of *kinds*, e.g. ``ChunkKind.FUNCTION``."""
+        self.kinds = set(kinds)
+        return self
+
+    def filter(self, predicate: Callable[[Chunk], bool]) -> "SearchQuery":
+        """Only match chunks for which *predicate* returns True."""
+        self.predicates.append(predicate)
+        return self
+
+    @property
+    def has_filters(self) -> bool:
+        """True if any filter was given."""
+        return (
+            self.languages is not None
+            or self.path_prefixes is not None
+            or self.kinds is not None
+            or bool(self.predicates)
+        )
+
+    def matches(self, chunk: Chunk) -> bool:
+        """Return True if *chunk* passes all filters."""
+        if self.languages is not None and chunk.language not in self.languages:
+            return False
+        if self.path_prefixes is not None and not any(_in_directory(chunk.path, prefix) for prefix in self.path_prefixes):
+            return False
+        if self.kinds is not None and chunk.kind not in self.kinds:
+            return False
+        return all(predicate(chunk) for predicate in self.predicates)
+
+def _in_directory(path: Optional[str], directory: str) -> bool:
+    """Return True if *path* is *directory* or inside it. Any path is inside
+    the empty directory."""
+    if path is None:
+        return False
+    return not directory or path == directory or path.startswith(f"{directory}/")
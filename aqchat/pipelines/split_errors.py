@@ -0,0 +1,22 @@
+class SplitError(Exception):
+    """Base class for errors raised when a file can't be split.
+
+    The path of the file is available as ``path``, and the underlying
+    exception, if any, as ``__cause__``.
+    """
+
+    def __init__(self, path: str, message: str):
+        super().__init__(f"{path}: {message}")
+        self.path = path
+
+class SplitIoError(SplitError):
+    """The file could not be read, e.g. because it doesn't exist."""
+
+class SplitEncodingError(SplitError):
+    """The file is not valid UTF-8."""
+
+class UnsupportedLanguageError(SplitError):
+    """The language of the file is not supported, and no fallback is configured."""
+
+class SplitParseError(SplitError):
+    """The file was read, but splitting its contents failed."""
@@ -0,0 +1,33 @@
+from dataclasses import dataclass, field
+from typing import List
+from pipelines.chunk import Chunk, SplitFailure
+
+@dataclass
+class SplitReport:
+    """The result of splitting the files of a directory, with a summary of
+    how each file was split.
+
+    Every file is counted once: in ``files_parsed`` if it was split
+    structurally, in ``files_fallback`` if its language is not supported and
+    it was split with the fallback splitter, or in ``files_skipped`` if it
+    could not be split, in which case ``errors`` holds its failure.
+    """
+
+    chunks: List[Chunk] = field(default_factory=list)
+    files_parsed: int = 0
+    files_fallback: int = 0
+    files_skipped: int = 0
+    errors: List[SplitFailure] = field(default_factory=list)
+
+    @property
+    def files(self) -> int:
+        """The number of files which were split or skipped."""
+        return self.files_parsed + self.files_fallback + self.files_skipped
+
+    def summary(self) -> str:
+        """A one-line summary for logging, e.g. ``12 files: 10 parsed,
+        1 fallback, 1 skipped, 57 chunks``."""
+        return (
+            f"{self.files} files: {self.files_parsed} parsed, {self.files_fallback} fallback, "
+            f"{self.files_skipped} skipped, {len(self.chunks)} chunks"
+        )
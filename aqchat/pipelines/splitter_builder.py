@@ -0,0 +1,120 @@
+from typing import Any, Dict, Optional
+from pipelines.abstract_diagnostics import AbstractDiagnostics
+from pipelines.abstract_tokenizer import AbstractTokenizer
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter, FallbackMode
+from pipelines.detectors import GlueRules, Language
+from pipelines.parse_cache import ParseCache
+
+class SplitterBuilder:
+    """Builder for a configured CodeBoundaryTextSplitter.
+
+    Each setter returns the builder, so calls can be chained:
+
+    ```
+    splitter = SplitterBuilder().max_chunk_bytes(2000).overlap(3).build()
+    ```
+
+    Settings which are not set keep the defaults of CodeBoundaryTextSplitter,
+    so ``SplitterBuilder().build()`` is equivalent to ``CodeBoundaryTextSplitter()``.
+    See its constructor for a description of each setting.
+    """
+
+    def __init__(self):
+        self._settings: Dict[str, Any] = {}
+
+    def chunk_size(self, chunk_size: int) -> "SplitterBuilder":
+        self._settings["chunk_size"] = chunk_size
+        return self
+
+    def strip_whitespace(self, strip_whitespace: bool) -> "SplitterBuilder":
+        self._settings["strip_whitespace"] = strip_whitespace
+        return self
+
+    def max_chunk_bytes(self, max_chunk_bytes: int) -> "SplitterBuilder":
+        self._settings["max_chunk_bytes"] = max_chunk_bytes
+        return self
+
+    def max_chunk_tokens(self, max_chunk_tokens: int) -> "SplitterBuilder":
+        self._settings["max_chunk_tokens"] = max_chunk_tokens
+        return self
+
+    def min_chunk_bytes(self, min_chunk_bytes: int) -> "SplitterBuilder":
+        self._settings["min_chunk_bytes"] = min_chunk_bytes
+        return self
+
+    def overlap(self, overlap_lines: int) -> "SplitterBuilder":
+        self._settings["overlap_lines"] = overlap_lines
+        return self
+
+    def tokenizer(self, tokenizer: AbstractTokenizer) -> "SplitterBuilder":
+        self._settings["tokenizer"] = tokenizer
+        return self
+
+    def fallback(self, fallback: FallbackMode) -> "SplitterBuilder":
+        self._settings["fallback"] = fallback
+        return self
+
+    def language(self, language: Language) -> "SplitterBuilder":
+        self._settings["language"] = language
+        return self
+
+    def normalize_newlines(self, normalize_newlines: bool) -> "SplitterBuilder":
+        self._settings["normalize_newlines"] = normalize_newlines
+        return self
+
+    def parse_cache(self, parse_cache: ParseCache) -> "SplitterBuilder":
+        self._settings["parse_cache"] = parse_cache
+        return self
+
+    def mmap_min_bytes(self, mmap_min_bytes: int) -> "SplitterBuilder":
+        self._settings["mmap_min_bytes"] = mmap_min_bytes
+        return self
+
+    def diagnostics(self, diagnostics: AbstractDiagnostics) -> "SplitterBuilder":
+        self._settings["diagnostics"] = diagnostics
+        return self
+
+    def glue_rules(self, language: Language, glue_rules: GlueRules) -> "SplitterBuilder":
+        """Set the glue rules of *language*, keeping those of other languages."""
+        self._settings.setdefault("glue_rules", {})[language] = glue_rules
+        return self
+
+    def build(self) -> CodeBoundaryTextSplitter:
+        """Build the splitter.
+
+        Raises:
+            ValueError: If the settings are invalid or conflict with each other.
+        """
+        self._validate()
+        return CodeBoundaryTextSplitter(**self._settings)
+
+    def _validate(self):
+        settings = self._settings
+
+        for name in ["chunk_size", "max_chunk_bytes", "max_chunk_tokens", "mmap_min_bytes"]:
+            if settings.get(name) is not None and settings[name] <= 0:
+                raise ValueError(f"{name} must be positive, got {settings[name]}")
+        for name in ["min_chunk_bytes", "overlap_lines"]:
+            if settings.get(name, 0) < 0:
+                raise ValueError(f"{name} must not be negative, got {settings[name]}")
+
+        for language in settings.get("glue_rules", {}):
+            if language.default_glue_rules is None:
+                raise ValueError(f"{language.value} has no glue rules")
+
+        if settings.get("max_chunk_tokens") is not None and settings.get("tokenizer") is None:
+            raise ValueError("max_chunk_tokens requires a tokenizer")
+
+        max_chunk_bytes = settings.get("max_chunk_bytes")
+        min_chunk_bytes = settings.get("min_chunk_bytes", 0)
+        if max_chunk_bytes is not None and min_chunk_bytes > max_chunk_bytes:
+            raise ValueError(
+                f"min_chunk_bytes ({min_chunk_bytes}) must not exceed max_chunk_bytes ({max_chunk_bytes})"
+            )
+
+        # Every overlap line takes at least one byte or token (its line break),
+        # so an overlap of at least the size limit could never fit into a piece
+        limit = settings.get("max_chunk_tokens") or max_chunk_bytes or settings.get("chunk_size")
+        overlap_lines = settings.get("overlap_lines", 0)
+        if limit is not None and overlap_lines >= limit:
+            raise ValueError(f"overlap of {overlap_lines} lines must be smaller than the chunk size limit ({limit})")
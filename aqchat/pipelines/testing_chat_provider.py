@@ -0,0 +1,39 @@
+from threading import Lock
+from typing import Any, Callable, Dict, Iterator, List, Optional
+from pipelines.abstract_chat_provider import AbstractChatProvider, Completion
+
+class TestingChatProvider(AbstractChatProvider):
+    """Chat provider which answers without a model, for development and tests.
+
+    The answer is produced by *respond* from the messages, by default echoing
+    the last user message. Every request is recorded in ``requests`` as a
+    ``(messages, options)`` tuple.
+    """
+
+    def __init__(self, respond: Optional[Callable[[List[Dict[str, str]]], str]] = None):
+        self.respond = respond or self._echo
+        self.requests: List[tuple] = []
+        self.lock = Lock()
+
+    async def complete(self, messages: List[Dict[str, str]], options: Optional[Dict[str, Any]] = None) -> Completion:
+        with self.lock:
+            self.requests.append((messages, options))
+        return Completion(text=self.respond(messages), model="testing", finish_reason="stop")
+
+    def stream(self, messages: List[Dict[str, str]], options: Optional[Dict[str, Any]] = None) -> Iterator[str]:
+        with self.lock:
+            self.requests.append((messages, options))
+
+        # Yield word by word, like a model streaming tokens
+        text = self.respond(messages)
+        start = 0
+        for end in range(1, len(text) + 1):
+            if end == len(text) or (text[end] == " " and text[end - 1] != " "):
+                yield text[start:end]
+                start = end
+
+    def _echo(self, messages: List[Dict[str, str]]) -> str:
+        for message in reversed(messages):
+            if message.get("role") == "user":
+                return message.get("content", "")
+        return ""
@@ -0,0 +1,20 @@
+from threading import Lock
+from typing import List
+from pipelines.abstract_diagnostics import AbstractDiagnostics, Diagnostic, DiagnosticKind
+
+class TestingDiagnostics(AbstractDiagnostics):
+    """Diagnostics sink which collects the diagnostics in ``diagnostics``, in
+    the order they were emitted, e.g. to check them in tests."""
+
+    def __init__(self):
+        self.diagnostics: List[Diagnostic] = []
+        self._lock = Lock()
+
+    def emit(self, diagnostic: Diagnostic) -> None:
+        with self._lock:
+            self.diagnostics.append(diagnostic)
+
+    def of_kind(self, kind: DiagnosticKind) -> List[Diagnostic]:
+        """Return the collected diagnostics of *kind*."""
+        with self._lock:
+            return [diagnostic for diagnostic in self.diagnostics if diagnostic.kind == kind]
@@ -0,0 +1,33 @@
+import hashlib
+import math
+from typing import List
+from pipelines.abstract_embedder import AbstractEmbedder
+
+class TestingEmbedder(AbstractEmbedder):
+    """Embedder which returns deterministic pseudo-random unit vectors derived
+    from a hash of each text. During development and in tests, this allows
+    indexing without an embedding model.
+
+    Identical texts get identical vectors, but the vectors carry no meaning.
+    """
+
+    def __init__(self, dimension: int = 16):
+        self.dimension = dimension
+        self.calls = 0  # number of calls to embed, e.g. to check batching
+
+    async def embed(self, texts: List[str]) -> List[List[float]]:
+        self.calls += 1
+        return [self.embed_text(text) for text in texts]
+
+    def embed_text(self, text: str) -> List[float]:
+        """Return the vector for *text*."""
+        values = []
+        counter = 0
+        while len(values) < self.dimension:
+            digest = hashlib.sha256(f"{counter}:{text}".encode("utf-8")).digest()
+            values.extend(byte / 127.5 - 1.0 for byte in digest)
+            counter += 1
+
+        vector = values[:self.dimension]
+        norm = math.sqrt(sum(value * value for value in vector)) or 1.0
+        return [value / norm for value in vector]
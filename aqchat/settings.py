@@ -58,7 +58,8 @@ def get_memory_defaults():
     return {"ret_strat": "mmr", 
             "k": 6, 
             "fetch_k": 20, 
-            "lambda_mult": 0.5}
+            "lambda_mult": 0.5,
+            "context_budget": 0}
 
 def save_config():
     """Persist configuration atomically."""
@@ -132,12 +133,14 @@ def memory_settings():
     disable_widget = ret_strat != "MMR"
     fetch_k = st.number_input("Fetch k", 10, 100, value=int(config["memory"]["fetch_k"]) , disabled=disable_widget)
     lambda_mult = st.number_input("Lambda mult", 0.0, 1.0, value=float(config["memory"]["lambda_mult"]), disabled=disable_widget)
+    context_budget = st.number_input("Context budget (tokens, 0 for no limit)", 0, 131072, value=int(config["memory"]["context_budget"]))
     saved = st.button("Save")
     if saved:
         config["memory"]["ret_strat"] = dict_options[current_index]
         config["memory"]["k"] = k_int
         config["memory"]["fetch_k"] = fetch_k
         config["memory"]["lambda_mult"] = lambda_mult
+        config["memory"]["context_budget"] = context_budget
         st.success("Settings saved! Please refresh the app to fully apply changes.")
         save_config()
 
@@ -0,0 +1,41 @@
+/*
+ * Parsing of configuration files.
+ *
+ * /* Nested comments are allowed in Rust: */
+ * fn commented_out() {
+ *     let braces = "{{{";
+ * }
+ */
+
+use std::collections::HashMap;
+
+/**
+ * Parses `key = value` lines into a map.
+ *
+ * /* The current format has no sections. */
+ *
+ * @param text the contents of the file
+ */
+pub fn parse(text: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for line in text.lines() {
+        /* Comments in the file start with `#` { */
+        if let Some((key, value)) = line.split_once('=') {
+            map.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+    map
+}
+
+/* A plain block comment /* with a } brace */ which is not a doc comment. */
+pub struct Config {
+    /** The parsed entries. */
+    pub entries: HashMap<String, String>,
+}
+
+/** Loads the configuration. */
+#[inline]
+pub fn load(path: &str) -> Config {
+    let text = std::fs::read_to_string(path).unwrap_or_default(); /* a /* } */ trailing comment */
+    Config { entries: parse(&text) }
+}
@@ -0,0 +1,23 @@
+use std::collections::HashMap;
+
+/// Counts the words of a text.
+pub fn count_words(text: &str) -> HashMap<&str, usize> {
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Returns the most frequent word, work in progress.
+pub fn most_frequent(counts: &HashMap<&str, usize>) -> Option<&str> {
+    let mut best = None;
+    for (word, count) in counts {
+        if best.map_or(true, |(_, c)| count > c {
+            best = Some((word, count));
+    best.map(|(word, _)| *word)
+
+/// Options of the word counter.
+pub struct Options {
+    pub ignore_case: bool,
+}
@@ -0,0 +1,8 @@
+fn first() -> u32 {
+    1
+}
+
+/// @brief Second function.
+fn second() -> u32 {
+    2
+}
@@ -0,0 +1,15 @@
+use std::fmt;
+
+/// Formats a greeting.
+#[inline]
+pub fn greet(name: &str) -> String {
+    format!("Hello, {name}!")
+}
+
+/// Greets people by name.
+
+
+#[derive(Debug)]
+pub struct Greeter {
+    name: String,
+}
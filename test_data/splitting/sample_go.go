@@ -0,0 +1,56 @@
+// Package client implements a minimal HTTP API client.
+package client
+
+import (
+	"fmt"
+	"net/http"
+)
+
+// Default settings for new clients.
+const (
+	DefaultTimeout = 30
+	DefaultRetries = 3
+)
+
+var usage = `client {
+	base: "https://example.com"
+}`
+
+// Doer sends HTTP requests.
+// It is implemented by *Client.
+type Doer interface {
+	Do(req *http.Request) (*http.Response, error)
+}
+
+// Client is an API client.
+type Client struct {
+	BaseURL string
+	http    *http.Client
+}
+
+// Do sends req, retrying up to DefaultRetries times.
+//
+//go:noinline
+func (c *Client) Do(req *http.Request) (*http.Response, error) {
+	var lastErr error
+	for i := 0; i < DefaultRetries; i++ {
+		resp, err := c.http.Do(req)
+		if err == nil {
+			return resp, nil
+		}
+		lastErr = err
+	}
+	return nil, fmt.Errorf("request failed: %w", lastErr)
+}
+
+func (c Client) String() string {
+	return "client{" + c.BaseURL + "}"
+}
+
+// New creates a client for baseURL.
+func New(baseURL string) *Client {
+	return &Client{
+		BaseURL: baseURL,
+		http:    &http.Client{},
+	}
+}
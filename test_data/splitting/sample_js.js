@@ -0,0 +1,42 @@
+// Helpers for formatting values in the UI.
+
+const DEFAULT_LOCALE = "en-US";
+
+export const formatDate = (date, locale = DEFAULT_LOCALE) => {
+  return new Intl.DateTimeFormat(locale, {
+    year: "numeric",
+    month: "short",
+    day: "numeric",
+  }).format(date);
+};
+
+/**
+ * @brief Format a number of bytes as a human readable size.
+ * @param bytes Number of bytes.
+ * @return The formatted size, e.g. `1.5 KB`.
+ */
+export const formatBytes = (bytes) => {
+  const units = ["B", "KB", "MB", "GB"];
+  let unit = 0;
+  while (bytes >= 1024 && unit < units.length - 1) {
+    bytes /= 1024;
+    unit++;
+  }
+  return `${bytes.toFixed(1)} ${units[unit]}`;
+};
+
+export const capitalize = (text) =>
+  text.charAt(0).toUpperCase() + text.slice(1);
+
+export const fetchJson = async function (url) {
+  const response = await fetch(url);
+  return response.json();
+};
+
+export function debounce(fn, delay) {
+  let timer = null;
+  return (...args) => {
+    clearTimeout(timer);
+    timer = setTimeout(() => fn(...args), delay);
+  };
+}
@@ -0,0 +1,20 @@
+import React, { useState } from "react";
+
+// A button which counts how often it's been clicked
+export const Counter = ({ label }) => {
+  const [count, setCount] = useState(0);
+  return (
+    <button onClick={() => setCount(count + 1)}>
+      {label}: {count} {count === 1 ? "click" : "clicks"}
+    </button>
+  );
+};
+
+export default function App() {
+  return (
+    <div className="app">
+      <p>Don't click too fast!</p>
+      <Counter label="Clicks" />
+    </div>
+  );
+}
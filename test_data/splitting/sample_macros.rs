@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+/// Builds a `HashMap` from `key => value` pairs.
+///
+/// Trailing commas are allowed.
+#[macro_export]
+macro_rules! hashmap {
+    () => {
+        HashMap::new()
+    };
+    ($($key:expr => $value:expr),+ $(,)?) => {{
+        let mut map = HashMap::new();
+        $(
+            map.insert($key, $value);
+        )+
+        map
+    }};
+}
+
+// Generates a getter for each field, e.g. `fn name(&self) -> &str { ... }`
+macro_rules! getters {
+    ($type:ident { $($field:ident: $ty:ty),* }) => {
+        impl $type {
+            $(
+                pub fn $field(&self) -> &$ty {
+                    &self.$field
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! square_brackets [
+    ($x:expr) => { [$x, '}', '{'] };
+];
+
+pub fn counts(words: &[&str]) -> HashMap<&str, usize> {
+    let mut counts = hashmap!();
+    for word in words {
+        *counts.entry(*word).or_insert(0) += 1;
+    }
+    counts
+}
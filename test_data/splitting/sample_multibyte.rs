@@ -0,0 +1,26 @@
+//! Ünïcödé fixture: emoji 🦀, CJK 漢字, combining é and ZWJ 👩‍👩‍👧‍👦 sequences.
+
+/// Grüße an alle — 日本語のドキュメント。
+pub fn grüße(名前: &str) -> String {
+    let 変数_0 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 0";
+    let 変数_1 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 1";
+    let 変数_2 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 2";
+    let 変数_3 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 3";
+    let 変数_4 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 4";
+    let 変数_5 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 5";
+    let 変数_6 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 6";
+    let 変数_7 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 7";
+    let 変数_8 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 8";
+    let 変数_9 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 9";
+    let 変数_10 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 10";
+    let 変数_11 = "🦀✨漢字é🦀✨漢字é🦀✨漢字é é 👨‍💻 11";
+    format!("こんにちは、{名前}! 🎉")
+}
+
+pub struct Ωmega {
+    /// Ηλεκτρονική — 電子
+    pub поле: String,
+}
+const THUMBS: &str = "👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽👍🏽ñ";
+const FAMILIES: &str = "👩‍👩‍👧👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦👨‍👩‍👦";
+
@@ -0,0 +1,6 @@
+def first():
+    return 1
+
+
+def second():
+    return "café"
\ No newline at end of file
@@ -1,7 +1,16 @@
 import datetime
+import functools
 import random
 from typing import List
 
+DEFAULT_PRIORITY = "normal"
+
+PRIORITY_LEVELS = {
+    "low": 0,
+    "normal": 1,
+    "high": 2,
+}
+
 def generate_task_id() -> str:
     """Utility function to generate a random task ID."""
     timestamp = datetime.datetime.now().strftime("%Y%m%d%H%M%S")
@@ -12,6 +21,13 @@ def is_valid_description(desc: str) -> bool:
     """Utility function to validate a task description."""
     return len(desc.strip()) > 0
 
+def summarize_tasks(tasks: List["Task"]) -> str:
+    """Utility function to summarize a list of tasks."""
+    def format_task(task: "Task") -> str:
+        return f"- {task}"
+
+    return "\n".join(format_task(task) for task in tasks)
+
 class Task:
     """
     Represents a single task in a task manager.
@@ -42,6 +58,10 @@ class Task:
     def is_completed(self) -> bool:
         return self._completed
 
+    @staticmethod
+    def from_description(description: str) -> "Task":
+        return Task(description.strip())
+
     def mark_completed(self):
         self._completed = True
 
@@ -75,6 +95,13 @@ class TaskManager:
                 return True
         return False
 
+@functools.lru_cache(
+    maxsize=None,
+)
+def default_manager() -> TaskManager:
+    """Utility function to get the task manager shared by all callers."""
+    return TaskManager()
+
 # Run if script is executed directly
 if __name__ == "__main__":
     manager = TaskManager()
@@ -0,0 +1,7 @@
+// Generated code, do not edit
+struct Meters; struct Seconds; #[derive(Debug)] pub struct Kilograms;
+pub struct Point { x: i32, y: i32 } impl Point { fn origin() -> Self { Point { x: 0, y: 0 } } } fn label() -> &'static str { "}; struct Fake;" }
+
+fn main() {
+    let _ = label();
+}
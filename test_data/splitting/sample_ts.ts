@@ -0,0 +1,57 @@
+/**
+ * @brief A small task queue with priorities.
+ */
+
+import { EventEmitter } from "events";
+
+// Priority of a task, higher runs first
+export enum Priority {
+  Low = 1,
+  Medium = 2,
+  High = 3,
+}
+
+export type TaskId = string;
+
+export interface Task {
+  id: TaskId;
+  description: string;
+  priority: Priority;
+}
+
+/**
+ * @brief Queue of pending tasks, ordered by priority.
+ * @param name Name used in log messages.
+ */
+export class TaskQueue extends EventEmitter {
+  private tasks: Task[] = [];
+
+  constructor(private readonly name: string) {
+    super();
+  }
+
+  // Add a task to the queue
+  public push(task: Task): void {
+    this.tasks.push(task);
+    this.tasks.sort((a, b) => b.priority - a.priority);
+    this.emit("push", task);
+  }
+
+  /** Remove and return the task with the highest priority. */
+  pop(): Task | undefined {
+    return this.tasks.shift();
+  }
+
+  describe(): string {
+    const lines = this.tasks.map((task) => `${task.id}: ${task.description}`);
+    return `${this.name} {\n${lines.join("\n")}\n}`;
+  }
+
+  onDrain = (callback: () => void) => {
+    this.on("drain", callback);
+  };
+}
+
+export default function createQueue(name: string): TaskQueue {
+  return new TaskQueue(name);
+}
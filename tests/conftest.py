@@ -0,0 +1,17 @@
+from typing import List
+import pytest
+from pipelines.chunk import Chunk
+
+def _chunk(path: str, start_line: int, text: str) -> Chunk:
+    return Chunk(text=text, start_byte=0, end_byte=len(text), start_line=start_line,
+                 end_line=start_line + text.count("\n"), path=path)
+
+@pytest.fixture
+def log_chunks() -> List[Chunk]:
+    """The chunks of a small logging crate: a function, the enum of its log
+    levels and a passage of its README."""
+    return [
+        _chunk("src/log.rs", 10, "pub fn log_message(level: LogLevel, msg: &str) {\n    println!(\"{msg}\");\n}"),
+        _chunk("src/level.rs", 1, "pub enum LogLevel {\n    Info,\n    Error,\n}"),
+        _chunk("README.md", 3, "Logging is configured with LOG_LEVEL."),
+    ]
@@ -0,0 +1,81 @@
+import asyncio
+import threading
+import time
+from pathlib import Path
+from pipelines import TestingEmbedder, index_dir_async
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.progress import EmbeddingBatch, Finished
+
+def _make_files(root):
+    for i in range(12):
+        (root / f"mod_{i}.rs").write_text(
+            "".join(f"fn f_{i}_{j}() {{\n    call({j});\n}}\n\n" for j in range(i + 1)), "utf-8"
+        )
+    (root / "notes.txt").write_text("Some notes.\n", "utf-8")
+    (root / "broken.rs").write_bytes(b"fn broken() {\xff}\n")
+
+def test_parity_with_sync_path(tmp_path):
+    """This tests if the async index has the chunks and failures of
+    par_index_dir, with the vectors of embedding the chunks one by one."""
+    _make_files(tmp_path)
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=40)
+    embedder = TestingEmbedder()
+    chunks, failures = splitter.par_index_dir(tmp_path)
+    events = []
+
+    result = asyncio.run(index_dir_async(splitter, tmp_path, embedder, max_open_files=3, batch_size=5, progress=events.append))
+
+    assert result.chunks == chunks
+    assert result.vectors == [embedder.embed_text(chunk.text) for chunk in chunks]
+    assert [(failure.path, type(failure.error)) for failure in result.failures] == [
+        (failure.path, type(failure.error)) for failure in failures
+    ]
+    batches = [event for event in events if isinstance(event, EmbeddingBatch)]
+    assert batches[-1].done == len(chunks) and all(batch.done <= batch.total for batch in batches)
+    assert events[-1] == Finished(14, len(chunks), 1)
+
+def test_bounded_open_files(tmp_path):
+    """This tests if no more than max_open_files files are read at once."""
+    _make_files(tmp_path)
+    lock = threading.Lock()
+    reading = [0, 0]  # current and maximum number of files being read
+
+    class SlowReadSplitter(CodeBoundaryTextSplitter):
+        def read_path(self, path, *, root=None):
+            with lock:
+                reading[0] += 1
+                reading[1] = max(reading)
+            time.sleep(0.01)
+            with lock:
+                reading[0] -= 1
+            return super().read_path(path, root=root)
+
+    result = asyncio.run(index_dir_async(SlowReadSplitter(), tmp_path, TestingEmbedder(), max_open_files=2))
+
+    assert reading[1] == 2
+    assert len(result.chunks) == len(result.vectors) > 12
+
+def test_parity_with_mmap(tmp_path):
+    """This tests if the async index memory-maps files as the sync path does
+    with mmap_min_bytes set, and gets the same chunks."""
+    _make_files(tmp_path)
+    mapped = []
+
+    class MappingSplitter(CodeBoundaryTextSplitter):
+        def _read_mapped(self, path, root):
+            contents = super()._read_mapped(path, root)
+            if contents is not None:
+                mapped.append(Path(path).as_posix())
+            return contents
+
+    splitter = MappingSplitter(max_chunk_bytes=40, mmap_min_bytes=64)
+    chunks, failures = splitter.par_index_dir(tmp_path)
+    sync_mapped = sorted(mapped)
+    mapped.clear()
+
+    result = asyncio.run(index_dir_async(splitter, tmp_path, TestingEmbedder(), max_open_files=3))
+
+    assert result.chunks == chunks
+    assert [failure.path for failure in result.failures] == [failure.path for failure in failures]
+    assert sorted(mapped) == sync_mapped
+    assert "mod_11.rs" in sync_mapped and "notes.txt" not in sync_mapped
@@ -0,0 +1,47 @@
+from pathlib import Path
+import pytest
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language
+
+def _split(**kwargs):
+    code = Path("test_data/splitting/sample_block_comments.rs").read_text("utf-8")
+    return CodeBoundaryTextSplitter(**kwargs).split_chunks(code, language=Language.RUST)
+
+def test_block_comments_attached():
+    """This tests if block doc comments, including nested comments, are part of
+    the chunk of the following item, and code in comments is ignored."""
+    chunks = _split()
+
+    assert [(chunk.start_line, chunk.end_line, chunk.name) for chunk in chunks] == [
+        (1, 10, None),  # header comment with commented out code, and imports
+        (12, 28, "parse"),
+        (30, 30, None),  # plain block comment
+        (31, 34, "Config"),
+        (36, 41, "load"),
+    ]
+
+    parse = chunks[1]
+    assert parse.text.startswith("/**\n * Parses `key = value` lines into a map.\n")
+    assert parse.text.endswith("    map\n}")
+    assert parse.doc.raw == (
+        "Parses `key = value` lines into a map.\n\n"
+        "/* The current format has no sections. */\n\n"
+        "@param text the contents of the file"
+    )
+
+    # The plain comment doesn't leak into the struct, nor the struct into it
+    assert chunks[2].text == "/* A plain block comment /* with a } brace */ which is not a doc comment. */"
+    assert chunks[3].text.startswith("pub struct Config {") and chunks[3].doc is None
+
+    assert chunks[4].doc.raw == "Loads the configuration."
+    assert chunks[4].text.endswith("    Config { entries: parse(&text) }\n}")
+
+@pytest.mark.parametrize("max_chunk_bytes", [160, 200, 250])
+def test_pieces_dont_cut_comments(max_chunk_bytes):
+    """This tests if subdivided chunks never start or end inside a block
+    comment which fits into a chunk."""
+    chunks = _split(max_chunk_bytes=max_chunk_bytes)
+
+    assert len(chunks) > 5
+    for chunk in chunks:
+        assert chunk.text.count("/*") == chunk.text.count("*/"), chunk.text
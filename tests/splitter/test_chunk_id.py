@@ -0,0 +1,31 @@
+import dataclasses
+from pathlib import Path
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk, ChunkKind
+
+def _split_fixture():
+    return CodeBoundaryTextSplitter().split_path(Path("test_data/splitting/sample_rs.rs"))
+
+def test_chunk_ids_stable():
+    """This tests if splitting the same file twice gives the same IDs, and if
+    changing a chunk's text changes only its ID."""
+    chunks = _split_fixture()
+    ids = [chunk.id() for chunk in chunks]
+
+    assert ids == [chunk.id() for chunk in _split_fixture()]
+    assert len(set(ids)) == len(ids)
+
+    chunks[3] = dataclasses.replace(chunks[3], text=chunks[3].text.replace("LogMessage", "Message"))
+    changed = [i for i, chunk in enumerate(chunks) if chunk.id() != ids[i]]
+    assert changed == [3]
+
+def test_chunk_id_scheme():
+    """This tests if the ID depends on the location and content only, and if
+    it is the documented BLAKE2b digest, so IDs are the same on every machine."""
+    chunk = Chunk(text="fn main() {}", start_byte=10, end_byte=22, start_line=2, end_line=2, path="src/main.rs")
+    assert chunk.id() == "1cee541cf2c9b22a38108ddc24379707"
+
+    assert dataclasses.replace(chunk, kind=ChunkKind.FUNCTION, name="main", start_line=3, end_line=3).id() == chunk.id()
+    assert dataclasses.replace(chunk, path="src/lib.rs").id() != chunk.id()
+    assert dataclasses.replace(chunk, start_byte=11).id() != chunk.id()
+    assert dataclasses.replace(chunk, end_byte=23).id() != chunk.id()
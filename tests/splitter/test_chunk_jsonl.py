@@ -0,0 +1,42 @@
+import io
+import json
+from pathlib import Path
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import read_jsonl, write_jsonl
+
+def test_jsonl_round_trip():
+    """This tests if chunks written as JSONL are read back identically."""
+    code = Path("test_data/splitting/sample_rs.rs").read_text("utf-8")
+    chunks = CodeBoundaryTextSplitter(max_chunk_bytes=200).split_file("src/logger.rs", code)
+
+    buffer = io.StringIO()
+    write_jsonl(chunks, buffer)
+    buffer.seek(0)
+
+    assert list(read_jsonl(buffer)) == chunks
+
+def test_jsonl_schema():
+    """This tests the field names and values of a serialized chunk."""
+    code = Path("test_data/splitting/sample_rs.rs").read_text("utf-8")
+    chunks = CodeBoundaryTextSplitter().split_file("src/logger.rs", code)
+
+    buffer = io.StringIO()
+    write_jsonl(chunks, buffer)
+    lines = buffer.getvalue().splitlines()
+    assert len(lines) == len(chunks)
+
+    records = [json.loads(line) for line in lines]
+    record = next(record for record in records if record["text"].lstrip().startswith("fn log(&self, message: &LogMessage) {"))
+
+    assert record["language"] == "rust"
+    assert record["path"] == "src/logger.rs"
+    assert record["start_line"] == 62
+    assert "impl LogBackend for ConsoleLogger" in record["parent_context"]
+    assert set(record) == {
+        "text", "language", "path", "start_line", "end_line", "start_byte", "end_byte",
+        "parent_context", "signature", "overlap_prefix_lines", "heading_path", "doc", "also_at", "imports", "kind", "name",
+        "part", "partial_parse",
+    }
+
+    record = next(record for record in records if "struct LogMessage" in record["text"])
+    assert record["doc"]["brief"] == "A simple structure representing a log message."
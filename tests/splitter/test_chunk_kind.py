@@ -0,0 +1,52 @@
+from pathlib import Path
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import ChunkKind
+from pipelines.detectors import Language
+
+def _split_kinds(filename: str, language: Language, **kwargs):
+    code = Path(f"test_data/splitting/{filename}").read_text("utf-8")
+    chunks = CodeBoundaryTextSplitter(**kwargs).split_chunks(code, language=language)
+    return [((chunk.start_line, chunk.end_line), chunk.kind) for chunk in chunks]
+
+def test_chunk_kinds_rust():
+    """This tests the kind assigned to each chunk of the Rust sample."""
+    assert _split_kinds("sample_rs.rs", Language.RUST) == [
+        ((1, 6), ChunkKind.IMPORT),      # module docs and use declarations
+        ((8, 14), ChunkKind.FUNCTION),   # current_timestamp
+        ((16, 22), ChunkKind.ENUM),      # LogLevel
+        ((24, 30), ChunkKind.STRUCT),    # LogMessage
+        ((32, 42), ChunkKind.IMPL),      # impl fmt::Display for LogMessage
+        ((33, 41), ChunkKind.METHOD),    # LogMessage::fmt
+        ((44, 48), ChunkKind.TRAIT),     # LogBackend
+        ((46, 47), ChunkKind.METHOD),    # LogBackend::log
+        ((50, 51), ChunkKind.STRUCT),    # ConsoleLogger
+        ((53, 59), ChunkKind.IMPL),      # impl ConsoleLogger
+        ((55, 58), ChunkKind.METHOD),    # ConsoleLogger::new
+        ((61, 65), ChunkKind.IMPL),      # impl LogBackend for ConsoleLogger
+        ((62, 64), ChunkKind.METHOD),    # ConsoleLogger::log
+        ((67, 76), ChunkKind.FUNCTION),  # log_message
+        ((78, 85), ChunkKind.FUNCTION),  # main
+    ]
+
+def test_chunk_kinds_go():
+    """This tests if Go methods, interfaces, and var and const blocks are
+    classified, even though methods are not nested in their type."""
+    kinds = dict(_split_kinds("sample_go.go", Language.GO))
+
+    assert kinds[(19, 23)] == ChunkKind.TRAIT
+    assert kinds[(25, 29)] == ChunkKind.STRUCT
+    assert kinds[(31, 44)] == ChunkKind.METHOD
+    assert kinds[(50, 56)] == ChunkKind.FUNCTION
+    assert kinds[(9, 13)] == ChunkKind.OTHER
+
+def test_chunk_kinds_comment_and_merge():
+    """This tests if code between definitions consisting of comments is a
+    comment, and stays a function once merged into the following function."""
+    code = "// Helpers for reading input.\n// See below.\n\nfn read() -> String {\n    String::new()\n}\n"
+    splitter = CodeBoundaryTextSplitter()
+    assert [chunk.kind for chunk in splitter.split_chunks(code, language=Language.RUST)] == [
+        ChunkKind.COMMENT, ChunkKind.FUNCTION,
+    ]
+
+    merged = CodeBoundaryTextSplitter(min_chunk_bytes=60).split_chunks(code, language=Language.RUST)
+    assert [chunk.kind for chunk in merged] == [ChunkKind.FUNCTION]
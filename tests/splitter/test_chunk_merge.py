@@ -0,0 +1,84 @@
+from pathlib import Path
+from typing import List
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk
+from pipelines.detectors import Language
+
+def _get_test_code() -> str:
+    return Path("test_data/splitting/sample_rs.rs").read_text("utf-8")
+
+def _split(code: str, language: Language = Language.RUST, **kwargs) -> List[Chunk]:
+    splitter = CodeBoundaryTextSplitter(**kwargs)
+    return splitter.split_chunks(code, language=language)
+
+def test_merge_disabled_by_default():
+    """This tests that chunks are not merged unless `min_chunk_bytes` is set."""
+    chunks = _split(_get_test_code())
+
+    assert "/// @brief A backend that logs messages to stdout.\nstruct ConsoleLogger;" in [
+        chunk.text for chunk in chunks
+    ]
+
+def test_merge_no_closing_brace_chunks():
+    """This tests that the remainder of an impl after its last method
+    is not emitted as a separate chunk.
+    """
+    chunks = _split(_get_test_code())
+
+    assert "}" not in [chunk.text.strip() for chunk in chunks]
+
+def test_merge_unit_struct_forward():
+    """This tests if a small unit struct is merged forward into
+    the definition that follows it.
+    """
+    chunks = _split(_get_test_code(), min_chunk_bytes=100)
+
+    merged = next(chunk for chunk in chunks if "struct ConsoleLogger;" in chunk.text)
+    assert merged.text.startswith("/// @brief A backend that logs messages to stdout.\nstruct ConsoleLogger;")
+    assert merged.text.endswith("impl ConsoleLogger {\n    /// @brief Create a new ConsoleLogger.\n    fn new() -> Self {\n        ConsoleLogger\n    }\n}")
+    assert (merged.start_line, merged.end_line) == (50, 59)
+
+    # the methods nested inside the impl are not merged with it
+    assert "    /// @brief Create a new ConsoleLogger.\n    fn new() -> Self {\n        ConsoleLogger\n    }" in [
+        chunk.text for chunk in chunks
+    ]
+
+def test_merge_trailing_use_backward():
+    """This tests if small trailing `use` statements, which have no following
+    definition, are merged backward into the last definition of the file.
+    """
+    code = _get_test_code() + "\nuse std::io;\nuse std::fs;\n"
+    chunks = _split(code, min_chunk_bytes=100)
+
+    assert chunks[-1].text.startswith("/// @brief Entry point for the program.\nfn main() {")
+    assert chunks[-1].text.endswith("}\n\nuse std::io;\nuse std::fs;")
+    assert (chunks[-1].start_line, chunks[-1].end_line) == (78, 88)
+
+def test_merge_comment_down():
+    """This tests that a small comment preceding a definition is merged down
+    into that definition, rather than up into the previous one.
+    """
+    code = """def first():
+    return "a string long enough not to be merged with anything"
+
+# The second function.
+def second():
+    return "another string long enough not to be merged"
+"""
+    chunks = _split(code, Language.PYTHON, min_chunk_bytes=30)
+
+    assert [chunk.text for chunk in chunks] == [
+        'def first():\n    return "a string long enough not to be merged with anything"',
+        '# The second function.\ndef second():\n    return "another string long enough not to be merged"',
+    ]
+    assert (chunks[1].start_line, chunks[1].end_line) == (4, 6)
+
+def test_merge_respects_max_size():
+    """This tests that chunks are not merged if the result would exceed
+    the maximum chunk size.
+    """
+    chunks = _split(_get_test_code(), min_chunk_bytes=100, max_chunk_bytes=150)
+
+    assert "/// @brief A backend that logs messages to stdout.\nstruct ConsoleLogger;" in [
+        chunk.text for chunk in chunks
+    ]
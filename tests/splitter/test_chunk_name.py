@@ -0,0 +1,60 @@
+from pathlib import Path
+import pytest
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language, RustBoundaryDetector, TypeScriptBoundaryDetector
+
+def _split_names(filename: str, language: Language):
+    code = Path(f"test_data/splitting/{filename}").read_text("utf-8")
+    chunks = CodeBoundaryTextSplitter().split_chunks(code, language=language)
+    return [(chunk.start_line, chunk.name) for chunk in chunks]
+
+def test_chunk_names_rust():
+    """This tests the name extracted for every chunk of the Rust sample."""
+    assert _split_names("sample_rs.rs", Language.RUST) == [
+        (1, None),  # module docs and use declarations
+        (8, "current_timestamp"),
+        (16, "LogLevel"),
+        (24, "LogMessage"),
+        (32, "LogMessage"),  # impl fmt::Display for LogMessage
+        (33, "fmt"),
+        (44, "LogBackend"),
+        (46, "log"),
+        (50, "ConsoleLogger"),
+        (53, "ConsoleLogger"),  # impl ConsoleLogger
+        (55, "new"),
+        (61, "ConsoleLogger"),  # impl LogBackend for ConsoleLogger
+        (62, "log"),
+        (67, "log_message"),  # declared as `log_message<B: LogBackend>`
+        (78, "main"),
+    ]
+
+def test_chunk_names_other_languages():
+    """This tests the names of chunks in the Python, TypeScript, Go and
+    Markdown samples."""
+    python = dict(_split_names("sample_py.py", Language.PYTHON))
+    assert (python[6], python[31], python[36], python[61]) == ("DEFAULT_PRIORITY", "Task", "__init__", "from_description")
+
+    typescript = dict(_split_names("sample_ts.ts", Language.TYPESCRIPT))
+    assert (typescript[7], typescript[14], typescript[29], typescript[50]) == ("Priority", "TaskId", "constructor", "onDrain")
+
+    go = dict(_split_names("sample_go.go", Language.GO))
+    assert (go[9], go[15], go[31], go[50]) == (None, "usage", "Do", "New")
+
+    markdown = dict(_split_names("sample_md.md", Language.MARKDOWN))
+    assert markdown[9] == "From crates.io"
+
+@pytest.mark.parametrize("signature, name", [
+    ("impl<T: Display> fmt::Display for Wrapper<T> where T: Clone {", "Wrapper"),
+    ("impl<'a> Iterator for Iter<'a> {", "Iter"),
+    ("unsafe impl Send for Ptr {", "Ptr"),
+    ("impl<K, V> Cache<K, V> {", "Cache"),
+    ("pub(crate) struct Handler<F: Fn()> {", "Handler"),
+    ("macro_rules! hashmap {", "hashmap"),
+])
+def test_rust_names_without_generics(signature, name):
+    """This tests if generic parameters and lifetimes are not part of names."""
+    assert RustBoundaryDetector().extract_name(signature) == name
+
+def test_anonymous_default_export():
+    """This tests if an anonymous default export has no name."""
+    assert TypeScriptBoundaryDetector().extract_name("export default function (options: Options) {") is None
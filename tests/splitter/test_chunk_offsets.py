@@ -0,0 +1,104 @@
+from pathlib import Path
+from typing import List
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk
+from pipelines.detectors import Language
+
+def _read_source(filename: str) -> str:
+    # read as bytes so that line endings are kept exactly as they are on disk
+    return Path(f"test_data/splitting/{filename}").read_bytes().decode("utf-8")
+
+def _split(code: str, language: Language) -> List[Chunk]:
+    splitter = CodeBoundaryTextSplitter()
+    return splitter.split_chunks(code, language=language)
+
+def _assert_reslices(code: str, chunks: List[Chunk]):
+    source = code.encode("utf-8")
+    for chunk in chunks:
+        assert source[chunk.start_byte:chunk.end_byte].decode("utf-8") == chunk.text
+
+def test_offsets_reslice_rust():
+    """This tests if the byte offsets of every chunk index back into
+    the original source text.
+    """
+    code = _read_source("sample_rs.rs")
+    chunks = _split(code, Language.RUST)
+
+    assert len(chunks) > 0
+    _assert_reslices(code, chunks)
+
+def test_offsets_lines_rust():
+    """This tests the 1-based, inclusive line range of a chunk."""
+    code = _read_source("sample_rs.rs")
+    chunks = _split(code, Language.RUST)
+
+    chunk = next(chunk for chunk in chunks if "fn current_timestamp" in chunk.text)
+    assert (chunk.start_line, chunk.end_line) == (8, 14)
+
+def test_offsets_crlf():
+    """This tests if line ranges and byte offsets are correct
+    for a file with CRLF line endings.
+    """
+    code = _read_source("sample_crlf.rs")
+    assert "\r\n" in code
+
+    chunks = _split(code, Language.RUST)
+    _assert_reslices(code, chunks)
+
+    assert [(chunk.start_line, chunk.end_line) for chunk in chunks] == [(1, 3), (5, 8)]
+    assert chunks[1].text == "/// @brief Second function.\r\nfn second() -> u32 {\r\n    2\r\n}"
+
+def test_offsets_crlf_normalized():
+    """This tests if normalized chunks of a CRLF file have LF line endings,
+    while their line ranges and byte offsets refer to the original source.
+    """
+    code = _read_source("sample_crlf.rs")
+    chunks = CodeBoundaryTextSplitter(normalize_newlines=True).split_chunks(code, language=Language.RUST)
+    source = code.encode("utf-8")
+
+    assert [(chunk.start_line, chunk.end_line) for chunk in chunks] == [(1, 3), (5, 8)]
+    assert chunks[1].text == "/// @brief Second function.\nfn second() -> u32 {\n    2\n}"
+    for chunk in chunks:
+        assert "\r" not in chunk.text
+        original = source[chunk.start_byte:chunk.end_byte].decode("utf-8")
+        assert original.replace("\r\n", "\n") == chunk.text
+        assert not original.endswith("\r")
+
+def test_offsets_mixed_newlines_normalized():
+    """This tests if CRLF, LF and lone CR line endings mixed in one file are
+    all normalized, and the chunks match those of the Unix copy of the file.
+    """
+    code = "fn first() {\r\n    1\r\n}\n\nfn second() {\r    2\r}\r\n\r\nfn third() {\n    3\n}\r\n"
+    unix = code.replace("\r\n", "\n").replace("\r", "\n")
+
+    chunks = CodeBoundaryTextSplitter(normalize_newlines=True).split_chunks(code, language=Language.RUST)
+    expected = _split(unix, Language.RUST)
+
+    assert [chunk.text for chunk in chunks] == [chunk.text for chunk in expected]
+    assert [(chunk.start_line, chunk.end_line) for chunk in chunks] == [(1, 3), (5, 7), (9, 11)]
+    assert [chunk.start_byte for chunk in chunks] == [code.index("fn first"), code.index("fn second"), code.index("fn third")]
+    assert chunks[-1].end_byte == len(code) - 2
+
+def test_offsets_no_trailing_newline():
+    """This tests if line ranges and byte offsets are correct for a file
+    which does not end with a newline, and which contains multi-byte characters.
+    """
+    code = _read_source("sample_no_newline.py")
+    assert not code.endswith("\n")
+
+    chunks = _split(code, Language.PYTHON)
+    _assert_reslices(code, chunks)
+
+    assert [(chunk.start_line, chunk.end_line) for chunk in chunks] == [(1, 2), (5, 6)]
+    assert chunks[1].end_byte == len(code.encode("utf-8"))
+
+def test_offsets_default_splitter():
+    """This tests if chunks produced without a boundary detector
+    still carry their location in the source text.
+    """
+    code = Path("test_data/test_repo/README.md").read_text("utf-8")
+    chunks = _split(code, None)
+
+    assert len(chunks) > 0
+    _assert_reslices(code, chunks)
+    assert chunks[0].start_line == 1
@@ -0,0 +1,37 @@
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk
+from pipelines.detectors import Language, get_boundary_detectors
+
+BODY = "".join(f"    let v{i} = compute({i});\n" for i in range(30))
+CODE = f"impl Engine {{\n    /// Runs the engine.\n    fn run(&self) {{\n{BODY}    }}\n}}\n"
+
+def test_parts_cover_unit():
+    """This tests if the pieces of an oversized method are numbered
+    contiguously and cover its whole body, each with the method's name and
+    parent context."""
+    (method,) = [chunk for chunk in CodeBoundaryTextSplitter().split_chunks(CODE, language=Language.RUST)
+                 if chunk.name == "run"]
+    assert method.part is None
+
+    chunks = CodeBoundaryTextSplitter(max_chunk_bytes=200).split_chunks(CODE, language=Language.RUST)
+    parts = [chunk for chunk in chunks if chunk.name == "run"]
+
+    assert len(parts) == 5
+    assert [chunk.part for chunk in parts] == [(i, 5) for i in range(1, 6)]
+    assert all(chunk.parent_context == "impl Engine {" and chunk.signature == "fn run(&self) {" for chunk in parts)
+    assert "\n".join(chunk.text for chunk in parts) == method.text
+    assert all(a.end_byte + 1 == b.start_byte for a, b in zip(parts, parts[1:]))
+
+    # The enclosing impl is numbered separately
+    assert [chunk.part for chunk in chunks if chunk.name == "Engine"] == [(i, 5) for i in range(1, 6)]
+    assert Chunk.from_dict(parts[1].to_dict()).part == (2, 5)
+
+def test_parts_in_metadata():
+    """This tests if the part of a piece is added to the document metadata."""
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=200)
+    documents = splitter.create_documents(
+        [CODE], metadatas=[{"source": "src/engine.rs"}], boundary_detectors=get_boundary_detectors()
+    )
+
+    parts = [(doc.metadata.get("part_index"), doc.metadata.get("part_count")) for doc in documents]
+    assert parts[:5] == [(i, 5) for i in range(1, 6)]
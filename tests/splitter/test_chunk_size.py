@@ -0,0 +1,106 @@
+from pathlib import Path
+from typing import List
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk
+from pipelines.detectors import Language
+
+def _oversized_rust_function(statements: int) -> str:
+    body = "\n".join(f"    let value_{i} = compute(value_{i - 1}, {i});" for i in range(1, statements))
+    return f"""/// @brief A deliberately oversized function.
+fn oversized() -> u64 {{
+    let value_0 = 0;
+{body}
+    value_{statements - 1}
+}}
+"""
+
+def _split(code: str, max_chunk_bytes: int, language: Language = Language.RUST) -> List[Chunk]:
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=max_chunk_bytes)
+    return splitter.split_chunks(code, language=language)
+
+def test_default_limit_keeps_function():
+    """This tests that a function within the default limit is not subdivided."""
+    code = _oversized_rust_function(20)
+    chunks = CodeBoundaryTextSplitter().split_chunks(code, language=Language.RUST)
+
+    assert len(chunks) == 1
+    assert chunks[0].signature is None
+
+def test_oversized_function_subdivided():
+    """This tests if an oversized function is subdivided into pieces within
+    the limit, cut at statement boundaries and carrying the function signature.
+    """
+    code = _oversized_rust_function(100)
+    chunks = _split(code, 300)
+
+    assert len(chunks) > 1
+    for chunk in chunks:
+        assert len(chunk.text.encode("utf-8")) <= 300
+        assert chunk.signature == "fn oversized() -> u64 {"
+        assert chunk.text.rstrip()[-1] in ";{}"
+
+    # pieces cover the whole function, line by line
+    assert "\n".join(chunk.text for chunk in chunks) == code.rstrip("\n")
+
+def test_python_oversized_statement_not_split():
+    """This tests that a multi-line Python statement is kept in one piece
+    when an oversized function is subdivided.
+    """
+    assignments = "\n".join(f"    value_{i} = {i}" for i in range(20))
+    items = "\n".join(f"        {i}," for i in range(10))
+    code = f"def build():\n{assignments}\n    values = [\n{items}\n    ]\n    return values\n"
+    chunks = _split(code, 400, Language.PYTHON)
+
+    # cutting at the last line within the limit would split the list
+    assert len(chunks) == 2
+    assert chunks[0].text.endswith("    value_19 = 19")
+    assert chunks[1].text.startswith("    values = [")
+    assert chunks[1].signature == "def build():"
+
+def test_hard_split_multibyte():
+    """This tests the fallback of splitting a single huge line at the byte
+    limit, which must never split inside a multi-byte character.
+    """
+    code = "fn emoji() -> &'static str { \"" + "é🦀" * 100 + "\" }"
+    chunks = _split(code, 64)
+
+    source = code.encode("utf-8")
+    assert len(chunks) > 1
+    for chunk in chunks:
+        assert 0 < len(chunk.text.encode("utf-8")) <= 64
+        assert source[chunk.start_byte:chunk.end_byte].decode("utf-8") == chunk.text
+        assert (chunk.start_line, chunk.end_line) == (1, 1)
+
+    assert "".join(chunk.text for chunk in chunks) == code
+
+def test_overlap_subdivided():
+    """This tests if each piece of a subdivided function starts with
+    the configured number of lines from the end of the previous piece.
+    """
+    code = _oversized_rust_function(100)
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=300, overlap_lines=2)
+    chunks = splitter.split_chunks(code, language=Language.RUST)
+
+    assert len(chunks) > 1
+    assert chunks[0].overlap_prefix_lines == 0
+
+    for previous, chunk in zip(chunks, chunks[1:]):
+        assert chunk.overlap_prefix_lines == 2
+        assert chunk.text.split("\n")[:2] == previous.text.split("\n")[-2:]
+        assert chunk.start_line == previous.end_line - 1
+        assert len(chunk.text.encode("utf-8")) <= 300
+
+def test_overlap_not_between_definitions():
+    """This tests that no overlap is added between distinct definitions."""
+    code = Path("test_data/splitting/sample_rs.rs").read_text("utf-8")
+    chunks = CodeBoundaryTextSplitter(overlap_lines=3).split_chunks(code, language=Language.RUST)
+
+    assert all(chunk.overlap_prefix_lines == 0 for chunk in chunks)
+
+def test_overlap_zero_unchanged():
+    """This tests that an overlap of zero produces the same chunks as before."""
+    code = _oversized_rust_function(100)
+    without = _split(code, 300)
+    with_zero = CodeBoundaryTextSplitter(max_chunk_bytes=300, overlap_lines=0).split_chunks(code, language=Language.RUST)
+
+    assert with_zero == without
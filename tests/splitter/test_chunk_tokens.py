@@ -0,0 +1,48 @@
+from pipelines.abstract_tokenizer import AbstractTokenizer
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language
+from pipelines.heuristic_tokenizer import HeuristicTokenizer
+
+class WordTokenizer(AbstractTokenizer):
+    """Counts each whitespace-separated word as one token."""
+
+    def count_tokens(self, text: str) -> int:
+        return len(text.split())
+
+def _rust_function(statements: int) -> str:
+    body = "\n".join(f"    let value_{i} = compute(value_{i - 1}, {i});" for i in range(1, statements))
+    return f"""/// @brief Counted function.
+fn counted() -> u64 {{
+    let value_0 = 0;
+{body}
+    value_{statements - 1}
+}}
+"""
+
+def test_token_limit_subdivides():
+    """This tests if the token limit is measured with the tokenizer, and if
+    subdivision cuts at the last statement within the limit.
+    """
+    splitter = CodeBoundaryTextSplitter(tokenizer=WordTokenizer(), max_chunk_tokens=20)
+    chunks = splitter.split_chunks(_rust_function(10), language=Language.RUST)
+
+    # The header takes 13 words and each statement 5 words
+    assert [(chunk.start_line, chunk.end_line) for chunk in chunks] == [(1, 4), (5, 8), (9, 12), (13, 14)]
+    assert all(WordTokenizer().count_tokens(chunk.text) <= 20 for chunk in chunks)
+
+def test_token_limit_without_tokenizer():
+    """This tests if the byte-based limit is used when no tokenizer is set."""
+    code = _rust_function(10)
+    splitter = CodeBoundaryTextSplitter(max_chunk_tokens=20)
+    chunks = splitter.split_chunks(code, language=Language.RUST)
+
+    assert len(chunks) == 1
+    assert chunks[0].text == code.rstrip("\n")
+
+def test_heuristic_tokenizer():
+    """This tests the estimates of the default heuristic tokenizer."""
+    tokenizer = HeuristicTokenizer()
+
+    assert tokenizer.count_tokens("") == 0
+    assert tokenizer.count_tokens("fn main() {}") == 6
+    assert tokenizer.count_tokens("a_very_long_identifier_name") == 7
@@ -0,0 +1,33 @@
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import dedup_chunks
+
+HELPER = """fn clamp(value: i32, low: i32, high: i32) -> i32 {
+    value.max(low).min(high)
+}
+"""
+
+def test_dedup_identical_function():
+    """This tests if an identical function in two files is kept once,
+    recording the location of the dropped copy."""
+    splitter = CodeBoundaryTextSplitter()
+    chunks = splitter.split_file("src/a.rs", HELPER + "\nfn a() {}\n") \
+        + splitter.split_file("vendor/b.rs", "fn b() {}\n\n" + HELPER)
+
+    result = dedup_chunks(chunks)
+
+    assert [chunk.location for chunk in result] == ["src/a.rs:1-3", "src/a.rs:5-5", "vendor/b.rs:1-1"]
+    assert result[0].also_at == ["vendor/b.rs:3-5"]
+    assert result[1].also_at == []
+
+def test_dedup_whitespace():
+    """This tests if whitespace differences are only ignored when requested."""
+    splitter = CodeBoundaryTextSplitter()
+    indented = "impl Helper {\n" + "".join(f"    {line}\n" for line in HELPER.splitlines()) + "}\n"
+
+    def split():
+        return splitter.split_file("src/a.rs", HELPER) + splitter.split_file("src/b.rs", indented)
+
+    assert len(dedup_chunks(split())) == 3
+    result = dedup_chunks(split(), ignore_whitespace=True)
+    assert len(result) == 2
+    assert result[0].also_at == ["src/b.rs:2-4"]
@@ -0,0 +1,55 @@
+from langchain_core.documents import Document
+from pipelines import ConsoleDiagnostics, Diagnostic, DiagnosticKind, LogLevel, TestingDiagnostics
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+
+def _make_files(root):
+    (root / "main.rs").write_text("fn main() {\n    let a = 1;\n    let b = 2;\n    let c = 3;\n}\n", "utf-8")
+    (root / "notes.txt").write_text("Some notes.\n", "utf-8")
+    (root / "broken.rs").write_bytes(b"fn broken() {\xff}\n")
+
+def test_diagnostics_emitted(tmp_path):
+    """This tests if splitting a directory emits a parse fallback for the
+    unsupported file, a skipped file and a subdivided function."""
+    _make_files(tmp_path)
+    diagnostics = TestingDiagnostics()
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=30, diagnostics=diagnostics)
+
+    list(splitter.index_dir(tmp_path, failures=[]))
+
+    (fallback,) = diagnostics.of_kind(DiagnosticKind.PARSE_FALLBACK)
+    assert fallback.path == "notes.txt" and fallback.level == LogLevel.INFO
+
+    (skipped,) = diagnostics.of_kind(DiagnosticKind.SKIPPED_FILE)
+    assert skipped.path == "broken.rs" and skipped.level == LogLevel.WARNING
+
+    (oversized,) = diagnostics.of_kind(DiagnosticKind.OVERSIZED_CHUNK)
+    assert oversized.path == "main.rs"
+    assert oversized.message.startswith("main.rs:1-5: `fn main() {` exceeds the chunk size limit")
+
+def test_parallel_split_and_documents(tmp_path):
+    """This tests if diagnostics are emitted from the parallel split and when
+    splitting LangChain documents."""
+    _make_files(tmp_path)
+    diagnostics = TestingDiagnostics()
+    splitter = CodeBoundaryTextSplitter(diagnostics=diagnostics)
+
+    splitter.par_index_dir(tmp_path, max_workers=4)
+    assert sorted(diagnostic.path for diagnostic in diagnostics.diagnostics) == ["broken.rs", "notes.txt"]
+
+    splitter.split_documents([Document(page_content="key: value\n", metadata={"source": "config.yaml"})], boundary_detectors={})
+    assert diagnostics.diagnostics[-1].kind == DiagnosticKind.PARSE_FALLBACK
+    assert diagnostics.diagnostics[-1].path == "config.yaml"
+
+def test_silent_by_default(tmp_path, capsys):
+    """This tests if nothing is printed unless a console sink is given, which
+    prints the diagnostics of at least its level."""
+    _make_files(tmp_path)
+
+    list(CodeBoundaryTextSplitter().index_dir(tmp_path))
+    assert capsys.readouterr().out == ""
+
+    list(CodeBoundaryTextSplitter(diagnostics=ConsoleDiagnostics(LogLevel.WARNING)).index_dir(tmp_path))
+    assert capsys.readouterr().out.startswith("WARNING: Skipping file which could not be split: broken.rs: ")
+
+    ConsoleDiagnostics().emit(Diagnostic(LogLevel.INFO, DiagnosticKind.OTHER, "Indexed 3 files"))
+    assert capsys.readouterr().out == "INFO: Indexed 3 files\n"
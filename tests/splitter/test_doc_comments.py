@@ -0,0 +1,65 @@
+from pathlib import Path
+from typing import List
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk
+from pipelines.detectors import Language
+from pipelines.doc_comments import parse_doc_comment
+
+def _split_file(filename: str, language: Language) -> List[Chunk]:
+    code = Path(f"test_data/splitting/{filename}").read_text("utf-8")
+    return CodeBoundaryTextSplitter().split_chunks(code, language=language)
+
+def _find_chunk(chunks: List[Chunk], text: str) -> Chunk:
+    return next(chunk for chunk in chunks if chunk.text.lstrip().startswith(text))
+
+def test_doc_brief_struct():
+    """This tests if the @brief of a doc comment is extracted for a struct,
+    even though an attribute sits between the comment and the struct.
+    """
+    chunks = _split_file("sample_rs.rs", Language.RUST)
+    chunk = _find_chunk(chunks, "/// @brief A simple structure")
+
+    assert chunk.doc.brief == "A simple structure representing a log message."
+    assert chunk.doc.raw == "@brief A simple structure representing a log message."
+
+def test_doc_allow():
+    """This tests if @allow tags are extracted."""
+    chunks = _split_file("sample_rs.rs", Language.RUST)
+    chunk = _find_chunk(chunks, "/// @allow(dead_code)")
+
+    assert chunk.doc.allow == ["dead_code"]
+    assert chunk.doc.brief is None
+
+def test_doc_none():
+    """This tests that a definition without a doc comment has no doc."""
+    chunks = _split_file("sample_rs.rs", Language.RUST)
+    chunk = _find_chunk(chunks, "impl fmt::Display for LogMessage")
+
+    assert chunk.doc is None
+
+def test_doc_python_docstring():
+    """This tests if the docstring of a Python function is used as its doc comment."""
+    chunks = _split_file("sample_py.py", Language.PYTHON)
+    chunk = _find_chunk(chunks, "def generate_task_id")
+
+    assert chunk.doc.raw == "Utility function to generate a random task ID."
+
+def test_doc_parse_tags():
+    """This tests parsing of all recognized tags, multi-line values,
+    and preserving unrecognized tags.
+    """
+    doc = parse_doc_comment("""@brief Send a message
+over the wire.
+@param backend The backend to send with.
+@param content Text of the message.
+@return Number of bytes sent.
+@since 1.2
+@since 1.3
+
+Some more text.""")
+
+    assert doc.brief == "Send a message over the wire."
+    assert doc.params == {"backend": "The backend to send with.", "content": "Text of the message."}
+    assert doc.returns == "Number of bytes sent."
+    assert doc.tags == {"since": ["1.2", "1.3"]}
+    assert doc.raw.endswith("Some more text.")
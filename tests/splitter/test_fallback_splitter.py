@@ -0,0 +1,49 @@
+from pathlib import Path
+from typing import List
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter, FallbackMode
+from pipelines.chunk import Chunk
+from pipelines.detectors import Language
+
+def _split_file(filename: str, max_chunk_bytes: int) -> List[Chunk]:
+    code = Path(f"test_data/splitting/{filename}").read_text("utf-8")
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=max_chunk_bytes, fallback=FallbackMode.LINES)
+    return splitter.split_file(filename, code)
+
+def test_fallback_toml_paragraphs():
+    """This tests if the line fallback packs whole paragraphs (here, TOML tables)
+    into chunks within the size limit, cutting at blank lines.
+    """
+    chunks = _split_file("sample_toml.toml", 120)
+
+    assert [(chunk.start_line, chunk.end_line) for chunk in chunks] == [(1, 4), (6, 11), (13, 15)]
+    assert all(len(chunk.text.encode("utf-8")) <= 120 for chunk in chunks)
+    assert all(chunk.language is None for chunk in chunks)
+
+def test_fallback_txt_lines():
+    """This tests if a paragraph which is too large on its own is cut between
+    lines, never in the middle of a line.
+    """
+    lines = Path("test_data/splitting/sample_txt.txt").read_text("utf-8").split("\n")
+    chunks = _split_file("sample_txt.txt", 120)
+
+    assert [(chunk.start_line, chunk.end_line) for chunk in chunks] == [(1, 1), (3, 3), (4, 4), (5, 5), (7, 8), (10, 10)]
+    for chunk in chunks:
+        assert chunk.text == "\n".join(lines[chunk.start_line - 1:chunk.end_line])
+
+def test_fallback_one_enormous_line():
+    """This tests if a file consisting of one enormous line is hard split
+    into pieces within the size limit."""
+    text = "é" * 5000
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=1000, fallback=FallbackMode.LINES)
+    chunks = splitter.split_file("data.min.txt", text)
+
+    assert len(chunks) == 10
+    assert all(len(chunk.text.encode("utf-8")) <= 1000 for chunk in chunks)
+    assert "".join(chunk.text for chunk in chunks) == text
+
+def test_chunk_language():
+    """This tests if structurally split chunks record their language."""
+    code = Path("test_data/splitting/sample_rs.rs").read_text("utf-8")
+    chunks = CodeBoundaryTextSplitter(fallback=FallbackMode.LINES).split_file("sample_rs.rs", code)
+
+    assert all(chunk.language == Language.RUST for chunk in chunks)
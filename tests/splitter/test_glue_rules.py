@@ -0,0 +1,63 @@
+from pathlib import Path
+import pytest
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import GlueRules, Language
+from pipelines.splitter_builder import SplitterBuilder
+
+def _split(filename, language, glue_rules=None):
+    code = Path(f"test_data/splitting/{filename}").read_text("utf-8")
+    splitter = CodeBoundaryTextSplitter(glue_rules={language: glue_rules} if glue_rules else None)
+    return splitter.split_chunks(code, language=language)
+
+def _chunk_with(chunks, text):
+    (chunk,) = [chunk for chunk in chunks if text in chunk.text]
+    return chunk
+
+def test_default_glue():
+    """This tests if by default, doc comments and attributes are attached to
+    the following Rust item, even across blank lines."""
+    chunks = _split("sample_glue.rs", Language.RUST)
+
+    assert [(chunk.start_line, chunk.name) for chunk in chunks] == [(1, None), (3, "greet"), (9, "Greeter")]
+    assert _chunk_with(chunks, "/// Greets people").name == "Greeter"
+    assert Language.RUST.default_glue_rules == GlueRules()
+
+def test_max_blank_lines():
+    """This tests if a doc comment separated from the item by more blank lines
+    than allowed is left out of the item's chunk."""
+    chunks = _split("sample_glue.rs", Language.RUST, GlueRules(max_blank_lines=1))
+
+    comment = _chunk_with(chunks, "/// Greets people")
+    assert comment.text == "/// Greets people by name."
+    assert _chunk_with(chunks, "pub struct Greeter").text.startswith("#[derive(Debug)]")
+    assert _chunk_with(chunks, "pub fn greet").text.startswith("/// Formats a greeting.")
+
+@pytest.mark.parametrize("glue_rules, greet_start", [
+    (GlueRules(comments=False), "#[inline]\npub fn greet"),
+    (GlueRules(attributes=False), "pub fn greet"),
+])
+def test_detached_comments_and_attributes(glue_rules, greet_start):
+    """This tests if doc comments, or attributes and the doc comments above
+    them, stay out of the item's chunk when they are not glued."""
+    chunks = _split("sample_glue.rs", Language.RUST, glue_rules)
+
+    greet = _chunk_with(chunks, "pub fn greet")
+    assert greet.text.startswith(greet_start) and greet.doc is None
+    assert "/// Formats a greeting." in _chunk_with(chunks, "use std::fmt;").text
+
+def test_python_comments():
+    """This tests if comments preceding a Python definition are only attached
+    when enabled, while decorators are attached by default."""
+    code = "import os\n\n# Returns the home directory.\n@cache\ndef home():\n    return os.environ['HOME']\n"
+    splitter = CodeBoundaryTextSplitter()
+    glued = SplitterBuilder().glue_rules(Language.PYTHON, GlueRules(comments=True)).build()
+
+    home = _chunk_with(splitter.split_chunks(code, language=Language.PYTHON), "def home")
+    assert home.text.startswith("@cache")
+    home = _chunk_with(glued.split_chunks(code, language=Language.PYTHON), "def home")
+    assert home.text.startswith("# Returns the home directory.")
+
+    with pytest.raises(ValueError):
+        SplitterBuilder().glue_rules(Language.MARKDOWN, GlueRules()).build()
+    with pytest.raises(ValueError):
+        GlueRules(max_blank_lines=-1)
@@ -0,0 +1,56 @@
+from pathlib import Path
+from typing import List
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk
+from pipelines.detectors import GoBoundaryDetector, Language
+
+def _split_file(filename: str) -> List[Chunk]:
+    code = Path(f"test_data/splitting/{filename}").read_text("utf-8")
+    return CodeBoundaryTextSplitter().split_chunks(code, language=Language.GO)
+
+def test_go_declarations():
+    """This tests if a Go file is split into a chunk for each top-level
+    declaration, with its leading doc comment attached.
+    """
+    chunks = _split_file("sample_go.go")
+
+    assert [(chunk.start_line, chunk.end_line) for chunk in chunks] == [
+        (1, 7),    # package doc comment, package clause and imports
+        (9, 13),   # const block, with its comment
+        (15, 17),  # var initialized with a raw string containing braces
+        (19, 23),  # interface
+        (25, 29),  # struct
+        (31, 44),  # pointer receiver method, with a directive in its doc comment
+        (46, 48),  # value receiver method
+        (50, 56),  # function
+    ]
+
+def test_go_method_receivers():
+    """This tests if methods carry their receiver type as context, unlike
+    functions and the type itself."""
+    chunks = {chunk.start_line: chunk for chunk in _split_file("sample_go.go")}
+
+    assert chunks[31].parent_context == "type Client struct {"
+    assert chunks[46].parent_context == "type Client struct {"
+    assert chunks[25].parent_context is None
+    assert chunks[50].parent_context is None
+
+def test_go_receiver_declared_elsewhere():
+    """This tests if the receiver itself is the context of a method whose type
+    is declared in another file."""
+    code = "package cache\n\nfunc (l *LRU[K, V]) Get(key K) (V, bool) {\n\treturn l.get(key)\n}\n"
+    contexts = GoBoundaryDetector().find_parent_contexts(code)
+
+    assert contexts == {2: "(l *LRU[K, V])"}
+
+def test_go_doc_comments():
+    """This tests if `//` doc comments are parsed without compiler directives."""
+    chunks = {chunk.start_line: chunk for chunk in _split_file("sample_go.go")}
+
+    assert chunks[19].doc.raw == "Doer sends HTTP requests.\nIt is implemented by *Client."
+    assert chunks[31].doc.raw == "Do sends req, retrying up to DefaultRetries times."
+    assert chunks[46].doc is None
+
+def test_go_language_detect():
+    """This tests if `.go` files are detected as Go."""
+    assert Language.detect("cmd/server/main.go", "") == Language.GO
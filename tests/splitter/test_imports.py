@@ -0,0 +1,54 @@
+from pathlib import Path
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language
+from pipelines.imports import import_names, relevant_imports
+
+def test_rust_chunks_carry_imports():
+    """Test that every chunk of the Rust sample lists the file's use declarations,
+    and that only the ones used by a chunk are relevant to it."""
+    text = Path("test_data/splitting/sample_rs.rs").read_text()
+    chunks = CodeBoundaryTextSplitter().split_chunks(text, language=Language.RUST)
+
+    for chunk in chunks:
+        assert chunk.imports == ["use std::fmt;", "use std::time::{SystemTime, UNIX_EPOCH};"]
+
+    now = next(chunk for chunk in chunks if "SystemTime::now()" in chunk.text)
+    assert now.relevant_imports() == ["use std::time::{SystemTime, UNIX_EPOCH};"]
+
+    display = next(chunk for chunk in chunks if "impl fmt::Display" in chunk.text)
+    assert "use std::fmt;" in display.relevant_imports()
+
+def test_python_chunks_carry_imports():
+    """Test that Python imports are collected, including multi-line ones, but
+    not imports nested inside functions."""
+    text = (
+        "import os.path\n"
+        "from typing import (\n"
+        "    List,  # lists\n"
+        "    Optional as Opt,\n"
+        ")\n"
+        "\n"
+        "def load(path) -> List[str]:\n"
+        "    import json\n"
+        "    return json.load(open(path))\n"
+    )
+    chunks = CodeBoundaryTextSplitter().split_chunks(text, language=Language.PYTHON)
+
+    assert chunks[0].imports == ["import os.path", "from typing import (List, Optional as Opt)"]
+    load = next(chunk for chunk in chunks if "def load" in chunk.text)
+    assert load.relevant_imports() == ["from typing import (List, Optional as Opt)"]
+
+def test_import_names():
+    """Test the names bound by various import statements."""
+    assert import_names("use std::time::{SystemTime, UNIX_EPOCH};") == ["SystemTime", "UNIX_EPOCH"]
+    assert import_names("pub(crate) use std::io::{self, Write as W, prelude::*};") == ["io", "W"]
+    assert import_names("use std::collections::{hash_map::{Entry, HashMap}, BTreeSet};") == ["Entry", "HashMap", "BTreeSet"]
+    assert import_names("import os.path, numpy as np") == ["os", "np"]
+    assert import_names("from . import models, views as v") == ["models", "v"]
+    assert import_names("from typing import *") == []
+
+def test_relevant_imports_match_whole_words():
+    """Test that imports are only relevant if their names occur as whole words."""
+    imports = ["use std::fmt;", "use std::io;"]
+    assert relevant_imports(imports, "let s = format!(\"{}\", fmt_value);") == []
+    assert relevant_imports(imports, "io::stdout().flush()") == ["use std::io;"]
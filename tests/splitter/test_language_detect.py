@@ -0,0 +1,41 @@
+from pathlib import Path
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language
+
+def test_detect_extension():
+    """This tests detecting the language from the file extension."""
+    assert Language.detect("src/main.rs", "") == Language.RUST
+    assert Language.detect(Path("app/index.tsx"), "") == Language.TYPESCRIPT
+    assert Language.detect("notes.txt", "") is None
+
+def test_detect_extension_case_insensitive():
+    """This tests if the extension is matched case-insensitively."""
+    assert Language.detect("scripts/BUILD.PY", "") == Language.PYTHON
+
+def test_detect_shebang():
+    """This tests detecting the language of an extensionless script from its shebang."""
+    assert Language.detect("bin/deploy", "#!/usr/bin/env python3\nprint('hi')\n") == Language.PYTHON
+    assert Language.detect("bin/serve", "#!/usr/local/bin/node\n") == Language.JAVASCRIPT
+    assert Language.detect("bin/check", "#!/usr/bin/env -S ts-node --swc\n") == Language.TYPESCRIPT
+    assert Language.detect("bin/run", "#!/bin/sh\necho hi\n") is None
+    assert Language.detect("bin/run", "echo hi\n") is None
+
+def test_split_file_detected():
+    """This tests if split_file uses the detected language to split a shebang-only script."""
+    code = Path("test_data/splitting/sample_py.py").read_text("utf-8")
+    script = "#!/usr/bin/env python3\n" + code
+
+    splitter = CodeBoundaryTextSplitter()
+    chunks = splitter.split_file("bin/tasks", script)
+    expected = splitter.split_chunks(script, language=Language.PYTHON)
+
+    assert [chunk.text for chunk in chunks] == [chunk.text for chunk in expected]
+
+def test_split_file_fallback():
+    """This tests if split_file falls back to the default splitter for unsupported files."""
+    text = "Some notes.\n\nThey are not code.\n"
+    chunks = CodeBoundaryTextSplitter().split_file("NOTES", text)
+
+    assert chunks[0].text.startswith("Some notes.")
+    assert chunks[-1].text.endswith("They are not code.")
+    assert all(chunk.signature is None for chunk in chunks)
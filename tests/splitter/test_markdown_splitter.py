@@ -0,0 +1,65 @@
+from pathlib import Path
+from typing import List
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk
+from pipelines.detectors import Language
+
+def _split(max_chunk_bytes: int | None = None) -> List[Chunk]:
+    code = Path("test_data/splitting/sample_md.md").read_text("utf-8")
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=max_chunk_bytes)
+    return splitter.split_chunks(code, language=Language.MARKDOWN)
+
+def test_markdown_sections():
+    """This tests if a Markdown document is split into one chunk per section,
+    each spanning up to the next heading of the same or a higher level.
+    """
+    chunks = _split()
+
+    assert [(chunk.start_line, chunk.end_line) for chunk in chunks] == [
+        (1, 34),   # logger
+        (5, 24),   # Installation
+        (9, 14),   # From crates.io
+        (16, 24),  # From source
+        (26, 34),  # Usage
+        (36, 38),  # License
+    ]
+
+def test_markdown_heading_path():
+    """This tests if each section carries the path of headings enclosing it."""
+    chunks = _split()
+
+    assert [chunk.heading_path for chunk in chunks] == [
+        ["logger"],
+        ["logger", "Installation"],
+        ["logger", "Installation", "From crates.io"],
+        ["logger", "Installation", "From source"],
+        ["logger", "Usage"],
+        ["License"],
+    ]
+
+def test_markdown_code_fence_atomic():
+    """This tests if `#` comments in fenced code blocks are not mistaken for
+    headings, and if fenced code blocks are not cut when a section is subdivided.
+    """
+    chunks = _split(max_chunk_bytes=200)
+
+    for chunk in chunks:
+        assert len(chunk.text.encode("utf-8")) <= 200
+        for fence in ["```", "~~~"]:
+            fences = [line for line in chunk.text.split("\n") if line.startswith(fence)]
+            assert len(fences) % 2 == 0
+
+    source = [chunk for chunk in chunks if chunk.heading_path == ["logger", "Installation", "From source"]]
+    assert len(source) == 1
+    assert "## build in release mode" in source[0].text
+
+def test_markdown_heading_path_metadata():
+    """This tests if the heading path is added to the document metadata as a string."""
+    code = Path("test_data/splitting/sample_md.md").read_text("utf-8")
+    documents = CodeBoundaryTextSplitter().create_documents(
+        [code],
+        [{"source": "docs/README.md"}],
+        boundary_detectors={".md": Language.MARKDOWN.get_boundary_detector()},
+    )
+
+    assert documents[3].metadata["heading_path"] == "logger > Installation > From source"
@@ -0,0 +1,46 @@
+from pathlib import Path
+import pytest
+from pipelines.boundary_splitter import _MMAP_REGION_BYTES, CodeBoundaryTextSplitter
+from pipelines.split_errors import SplitEncodingError
+
+def _write_large_file(path: Path) -> bytes:
+    """Write a Rust file of several mmap regions, with multibyte characters
+    which are cut at region ends."""
+    functions = "\n".join(
+        f"/// Returns the größe of item {i} 🦀\nfn item_{i}() -> &'static str {{\n    \"ü{i}\"\n}}\n" for i in range(40000)
+    )
+    data = functions.encode("utf-8")
+    assert len(data) > 2 * _MMAP_REGION_BYTES
+    path.write_bytes(data)
+    return data
+
+def test_mmap_matches_in_memory(tmp_path):
+    """This tests if splitting a large file via mmap gives exactly the chunks
+    of splitting it after reading it into memory."""
+    data = _write_large_file(tmp_path / "generated.rs")
+
+    expected = CodeBoundaryTextSplitter().split_path("generated.rs", root=tmp_path)
+    chunks = CodeBoundaryTextSplitter(mmap_min_bytes=1 << 20).split_path("generated.rs", root=tmp_path)
+
+    assert chunks == expected
+    assert all(data[chunk.start_byte:chunk.end_byte] == chunk.text.encode("utf-8") for chunk in chunks)
+
+    # Small files and empty files, which can't be mapped, are read into memory
+    (tmp_path / "small.rs").write_text("fn main() {}\n", "utf-8")
+    (tmp_path / "empty.rs").write_bytes(b"")
+    assert len(CodeBoundaryTextSplitter(mmap_min_bytes=1024).split_path("small.rs", root=tmp_path)) == 1
+    assert CodeBoundaryTextSplitter(mmap_min_bytes=0).split_path("empty.rs", root=tmp_path) == []
+
+def test_mmap_invalid_utf8(tmp_path):
+    """This tests if invalid UTF-8 in a later region of a mapped file,
+    including a character cut at a region end, raises an encoding error with
+    its byte offset."""
+    data = bytearray(_write_large_file(tmp_path / "generated.rs"))
+    data[_MMAP_REGION_BYTES - 1:_MMAP_REGION_BYTES + 1] = b"\xc3\x28"
+    (tmp_path / "generated.rs").write_bytes(bytes(data))
+
+    with pytest.raises(SplitEncodingError) as ex:
+        CodeBoundaryTextSplitter(mmap_min_bytes=1).split_path("generated.rs", root=tmp_path)
+
+    assert ex.value.path == "generated.rs"
+    assert str(ex.value).endswith(f"at byte {_MMAP_REGION_BYTES - 1}")
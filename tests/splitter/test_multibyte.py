@@ -0,0 +1,53 @@
+import unicodedata
+from pathlib import Path
+import pytest
+from pipelines import HeuristicTokenizer
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language
+
+FIXTURE = Path("test_data/splitting/sample_multibyte.rs")
+
+def _starts_joined(text: str) -> bool:
+    """Return True if *text* starts with a character which belongs to the
+    preceding character, e.g. a combining accent or a joined emoji."""
+    char = text[0]
+    return unicodedata.combining(char) != 0 or char == "‍" or "\U0001f3fb" <= char <= "\U0001f3ff"
+
+@pytest.mark.parametrize("max_chunk_bytes", [8, 32, 57, 100, 256, 1000])
+@pytest.mark.parametrize("overlap_lines", [0, 2])
+def test_multibyte_chunks_reslice(max_chunk_bytes, overlap_lines):
+    """Test that every chunk of multibyte content split near the size limit is
+    valid UTF-8 and reslices exactly from the source bytes."""
+    data = FIXTURE.read_bytes()
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=max_chunk_bytes, overlap_lines=overlap_lines)
+    chunks = splitter.split_chunks(data.decode("utf-8"), language=Language.RUST)
+
+    assert chunks
+    for chunk in chunks:
+        assert data[chunk.start_byte:chunk.end_byte].decode("utf-8") == chunk.text
+        assert len(chunk.text.encode("utf-8")) <= max_chunk_bytes or max_chunk_bytes < 8
+        if max_chunk_bytes >= 32:
+            assert not _starts_joined(chunk.text)
+
+def test_multibyte_token_limit():
+    data = FIXTURE.read_bytes()
+    splitter = CodeBoundaryTextSplitter(tokenizer=HeuristicTokenizer(), max_chunk_tokens=10)
+    chunks = splitter.split_chunks(data.decode("utf-8"), language=Language.RUST)
+
+    for chunk in chunks:
+        assert data[chunk.start_byte:chunk.end_byte].decode("utf-8") == chunk.text
+
+    # Without overlap, the chunks of the long lines cover them exactly
+    lines = data.decode("utf-8").split("\n")
+    line_number, thumbs = next((i + 1, line) for i, line in enumerate(lines) if line.startswith("const THUMBS"))
+    pieces = [chunk.text for chunk in chunks if chunk.start_line == line_number]
+    assert len(pieces) > 1
+    assert "".join(pieces) == thumbs
+
+def test_emoji_sequences_kept_together():
+    family = "\U0001f468‍\U0001f469‍\U0001f466"  # 18 bytes
+    text = "x" * 10 + family * 4 + "\n"
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=20)
+    chunks = splitter.split_chunks(text, language=Language.RUST)
+
+    assert [chunk.text for chunk in chunks] == ["x" * 10, family, family, family, family]
@@ -0,0 +1,48 @@
+from pathlib import Path
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.split_errors import SplitIoError
+
+def _make_files(root: Path, count: int):
+    """Create *count* Rust and Python files of varying size."""
+    for i in range(count):
+        functions = "\n".join(
+            f"fn function_{i}_{j}() -> u32 {{\n    {i} + {j}\n}}\n" for j in range(i % 7 + 1)
+        )
+        (root / f"module_{i:03}.rs").write_text(functions, "utf-8")
+        (root / f"script_{i:03}.py").write_text(f"def main():\n    return {i}\n", "utf-8")
+
+def test_par_split_matches_sequential(tmp_path):
+    """This tests if splitting many files concurrently gives the same chunks,
+    in the same order, as splitting them one by one."""
+    _make_files(tmp_path, 60)
+    splitter = CodeBoundaryTextSplitter()
+
+    sequential = list(splitter.index_dir(tmp_path))
+    sequential.sort(key=lambda chunk: (chunk.path, chunk.start_byte))
+    chunks, failures = splitter.par_index_dir(tmp_path, max_workers=8)
+
+    assert failures == []
+    assert len(chunks) > 120
+    assert chunks == sequential
+
+def test_par_split_thread_count(tmp_path):
+    """This tests if the result doesn't depend on the number of threads."""
+    _make_files(tmp_path, 20)
+    splitter = CodeBoundaryTextSplitter()
+
+    assert splitter.par_index_dir(tmp_path, max_workers=1) == splitter.par_index_dir(tmp_path, max_workers=4)
+
+def test_par_split_failure_isolated(tmp_path):
+    """This tests if a file which can't be split produces a failure entry
+    without affecting the other files."""
+    _make_files(tmp_path, 3)
+    splitter = CodeBoundaryTextSplitter()
+
+    chunks, failures = splitter.par_split_files(
+        ["module_000.rs", "missing.rs", "script_001.py"], root=tmp_path, max_workers=2
+    )
+
+    assert sorted({chunk.path for chunk in chunks}) == ["module_000.rs", "script_001.py"]
+    assert [failure.path for failure in failures] == ["missing.rs"]
+    assert isinstance(failures[0].error, SplitIoError)
+    assert isinstance(failures[0].error.__cause__, FileNotFoundError)
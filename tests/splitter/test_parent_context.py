@@ -0,0 +1,31 @@
+from pathlib import Path
+from typing import List
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk
+from pipelines.detectors import Language
+
+def _split_file(filename: str, language: Language) -> List[Chunk]:
+    code = Path(f"test_data/splitting/{filename}").read_text("utf-8")
+    return CodeBoundaryTextSplitter().split_chunks(code, language=language)
+
+def test_parent_context_impl_method():
+    """This tests if a method inside an impl carries the impl header as context."""
+    chunks = _split_file("sample_rs.rs", Language.RUST)
+    chunk = next(chunk for chunk in chunks if chunk.text.lstrip().startswith("fn log(&self, message: &LogMessage) {"))
+
+    assert "impl LogBackend for ConsoleLogger" in chunk.parent_context
+    assert "impl LogBackend for ConsoleLogger" not in chunk.text
+
+def test_parent_context_free_function():
+    """This tests if a free function has no parent context."""
+    chunks = _split_file("sample_rs.rs", Language.RUST)
+    chunk = next(chunk for chunk in chunks if "fn log_message<" in chunk.text)
+
+    assert chunk.parent_context is None
+
+def test_parent_context_python_method():
+    """This tests if a Python method carries the class header as context."""
+    chunks = _split_file("sample_py.py", Language.PYTHON)
+    chunk = next(chunk for chunk in chunks if chunk.text.lstrip().startswith("def mark_completed"))
+
+    assert chunk.parent_context == "class Task:"
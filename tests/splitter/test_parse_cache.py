@@ -0,0 +1,110 @@
+from pathlib import Path
+import pytest
+from pipelines.abstract_tokenizer import AbstractTokenizer
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language, RustBoundaryDetector
+from pipelines.heuristic_tokenizer import HeuristicTokenizer
+from pipelines.parse_cache import ParseCache
+
+class _CountingDetector(RustBoundaryDetector):
+    """Rust detector counting how often it parses a text."""
+
+    def __init__(self):
+        super().__init__()
+        self.parses = 0
+
+    def find_boundaries(self, text):
+        self.parses += 1
+        return super().find_boundaries(text)
+
+def test_cache_hit_skips_parsing():
+    """This tests if splitting the same content twice parses it once, and the
+    second call registers a cache hit with the same chunks."""
+    code = Path("test_data/splitting/sample_rs.rs").read_text("utf-8")
+    cache = ParseCache()
+    splitter = CodeBoundaryTextSplitter(parse_cache=cache)
+    detector = _CountingDetector()
+
+    first = splitter.split_chunks(code, boundary_detector=detector)
+    assert (cache.hits, cache.misses) == (0, 1)
+
+    second = splitter.split_chunks(code, boundary_detector=detector)
+    assert (cache.hits, cache.misses) == (1, 1)
+    assert detector.parses == 1
+    assert second == first
+
+    # Chunks returned from the cache are copies
+    second[0].path = "src/main.rs"
+    assert splitter.split_chunks(code, boundary_detector=detector)[0].path is None
+
+    # Changed content, another language or other settings miss the cache
+    splitter.split_chunks(code + "\n", boundary_detector=detector)
+    splitter.split_chunks(code, language=Language.PYTHON)
+    CodeBoundaryTextSplitter(parse_cache=cache, max_chunk_bytes=200).split_chunks(code, boundary_detector=detector)
+    assert (cache.hits, cache.misses) == (2, 4)
+
+def test_cache_evicts_least_recently_used():
+    """This tests if the cache is bounded by its capacity."""
+    cache = ParseCache(capacity=2)
+    splitter = CodeBoundaryTextSplitter(parse_cache=cache)
+
+    for code in ["fn a() {}", "fn b() {}", "fn a() {}", "fn c() {}"]:
+        splitter.split_chunks(code, language=Language.RUST)
+    assert len(cache) == 2
+    assert (cache.hits, cache.misses) == (1, 3)
+
+    # `fn b` was evicted, as `fn a` was used more recently
+    splitter.split_chunks("fn a() {}", language=Language.RUST)
+    splitter.split_chunks("fn b() {}", language=Language.RUST)
+    assert (cache.hits, cache.misses) == (2, 4)
+
+    with pytest.raises(ValueError):
+        ParseCache(capacity=0)
+
+def test_cache_shared_by_parallel_split(tmp_path):
+    """This tests if a cache shared by the worker threads gives the same chunks
+    and counts a hit for each file split again."""
+    for i in range(30):
+        (tmp_path / f"module_{i:02}.rs").write_text(f"fn function_{i}() -> u32 {{\n    {i}\n}}\n", "utf-8")
+    cache = ParseCache()
+    splitter = CodeBoundaryTextSplitter(parse_cache=cache)
+
+    first, _ = splitter.par_index_dir(tmp_path, max_workers=8)
+    second, _ = splitter.par_index_dir(tmp_path, max_workers=8)
+
+    assert second == first
+    assert (cache.hits, cache.misses) == (30, 30)
+    assert first == CodeBoundaryTextSplitter().par_index_dir(tmp_path)[0]
+
+def test_cache_keyed_by_length_function_and_tokenizer():
+    """This tests if splitters sharing a cache, but measuring chunks with
+    another length function or tokenizer, get their own chunks, and a
+    tokenizer without a cache key bypasses the cache."""
+    code = Path("test_data/splitting/sample_rs.rs").read_text("utf-8")
+    cache = ParseCache()
+
+    def longer(text):
+        return len(text) * 4
+
+    CodeBoundaryTextSplitter(chunk_size=400, parse_cache=cache).split_chunks(code, language=Language.RUST)
+    chunks = CodeBoundaryTextSplitter(chunk_size=400, length_function=longer, parse_cache=cache) \
+        .split_chunks(code, language=Language.RUST)
+    assert chunks == CodeBoundaryTextSplitter(chunk_size=400, length_function=longer) \
+        .split_chunks(code, language=Language.RUST)
+
+    for chars_per_token in [4, 1]:
+        tokenizer = HeuristicTokenizer(chars_per_token)
+        chunks = CodeBoundaryTextSplitter(tokenizer=tokenizer, max_chunk_tokens=200, parse_cache=cache) \
+            .split_chunks(code, language=Language.RUST)
+        assert chunks == CodeBoundaryTextSplitter(tokenizer=tokenizer, max_chunk_tokens=200) \
+            .split_chunks(code, language=Language.RUST)
+    assert cache.hits == 0
+
+    class UnkeyedTokenizer(AbstractTokenizer):
+        def count_tokens(self, text):
+            return len(text.split())
+
+    splitter = CodeBoundaryTextSplitter(tokenizer=UnkeyedTokenizer(), max_chunk_tokens=50, parse_cache=cache)
+    splitter.split_chunks(code, language=Language.RUST)
+    splitter.split_chunks(code, language=Language.RUST)
+    assert (cache.hits, len(cache)) == (0, 4)
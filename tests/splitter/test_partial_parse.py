@@ -0,0 +1,85 @@
+from pathlib import Path
+import pytest
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import Chunk, ChunkKind
+from pipelines.detectors import RustBoundaryDetector
+from pipelines.abstract_diagnostics import DiagnosticKind, LogLevel
+from pipelines.testing_diagnostics import TestingDiagnostics
+
+CODE = Path("test_data/splitting/sample_broken.rs").read_text("utf-8")
+
+def test_valid_items_recovered():
+    """This tests if the items before and after a function missing its
+    closing braces are chunked normally, while the broken function is split as
+    plain code flagged as partially parsed."""
+    diagnostics = TestingDiagnostics()
+    splitter = CodeBoundaryTextSplitter(diagnostics=diagnostics)
+    chunks = splitter.split_file("src/words.rs", CODE)
+
+    (count_words,) = [chunk for chunk in chunks if chunk.name == "count_words"]
+    assert count_words.kind == ChunkKind.FUNCTION
+    assert count_words.text.startswith("/// Counts the words of a text.")
+    assert count_words.text.endswith("    counts\n}")
+    assert count_words.doc.raw == "Counts the words of a text."
+
+    (options,) = [chunk for chunk in chunks if chunk.name == "Options"]
+    assert options.kind == ChunkKind.STRUCT and options.text.startswith("/// Options of the word counter.")
+
+    broken = [chunk for chunk in chunks if chunk.partial_parse]
+    assert "\n".join(chunk.text for chunk in broken).startswith("/// Returns the most frequent word")
+    assert broken[-1].text.endswith("best.map(|(word, _)| *word)")
+    assert all(chunk.name is None for chunk in broken)
+    assert not count_words.partial_parse and not options.partial_parse
+    assert Chunk.from_dict(broken[0].to_dict()).partial_parse
+
+    (diagnostic,) = diagnostics.of_kind(DiagnosticKind.PARTIAL_PARSE)
+    assert diagnostic.level == LogLevel.WARNING
+    assert diagnostic.path == "src/words.rs" and "src/words.rs:12-18" in diagnostic.message
+
+def test_unclosed_at_end():
+    """This tests if an item which is never closed takes the rest of the file,
+    and well-formed code gives no partial chunks."""
+    splitter = CodeBoundaryTextSplitter()
+    chunks = splitter.split_file("src/lib.rs", "fn ok() {}\n\nfn broken() {\n    run(\n")
+
+    assert [(chunk.name, chunk.partial_parse) for chunk in chunks] == [("ok", False), (None, True)]
+    assert not any(chunk.partial_parse for chunk in splitter.split_file("src/lib.rs", "fn ok() {}\n"))
+
+@pytest.mark.parametrize("code", [
+    'fn open() -> &\'static str {\n    "{"\n}\n',
+    "fn brace() -> char { '{' }\n",
+    'fn raw() -> &\'static str {\n    r#"fn x() { "#\n}\n',
+    'fn multiline() -> &\'static str {\n    "a \\" {\n    b"\n}\n',
+])
+def test_braces_in_literals(code):
+    """This tests if braces in string, raw string and character literals don't
+    make valid items look malformed."""
+    diagnostics = TestingDiagnostics()
+    splitter = CodeBoundaryTextSplitter(diagnostics=diagnostics)
+    chunks = splitter.split_file("src/lib.rs", code + "\nfn after() {}\n")
+
+    assert [(chunk.kind, chunk.partial_parse) for chunk in chunks] == [(ChunkKind.FUNCTION, False)] * 2
+    assert chunks[0].text == code.rstrip() and chunks[1].name == "after"
+    assert diagnostics.of_kind(DiagnosticKind.PARTIAL_PARSE) == []
+
+def test_text_scanned_once():
+    """This tests if finding the boundaries and unparsed ranges of a text
+    scans it once, and the splitter can't change the kept result."""
+    class CountingDetector(RustBoundaryDetector):
+        scans = 0
+
+        def _scan_uncached(self, text):
+            CountingDetector.scans += 1
+            return super()._scan_uncached(text)
+
+    detector = CountingDetector()
+    splitter = CodeBoundaryTextSplitter()
+    first = splitter.split_chunks(CODE, boundary_detector=detector)
+    assert CountingDetector.scans == 1
+
+    # Splitting the same text again reuses the scan, unchanged by the first split
+    assert splitter.split_chunks(CODE, boundary_detector=detector) == first
+    assert CountingDetector.scans == 1
+
+    splitter.split_chunks(CODE + "\n", boundary_detector=detector)
+    assert CountingDetector.scans == 2
@@ -0,0 +1,61 @@
+from collections import Counter
+from pathlib import Path
+from threading import Lock
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.progress import FileChunked, FileFailed, FileStarted, Finished
+
+class _Recorder:
+    """Thread-safe progress callback collecting the events."""
+
+    def __init__(self):
+        self.lock = Lock()
+        self.events = []
+
+    def __call__(self, event):
+        with self.lock:
+            self.events.append(event)
+
+    def of_type(self, event_type):
+        return [event for event in self.events if isinstance(event, event_type)]
+
+def _make_files(root: Path):
+    """Create Rust files with 1 to 5 functions and a file which is not UTF-8."""
+    for i in range(20):
+        functions = "\n".join(f"fn function_{j}() -> u32 {{\n    {j}\n}}\n" for j in range(i % 5 + 1))
+        (root / f"module_{i:02}.rs").write_text(functions, "utf-8")
+    (root / "broken.rs").write_bytes(b"fn broken() {\xff}\n")
+
+def _check_events(recorder: _Recorder, chunks, failures):
+    produced = Counter(chunk.path for chunk in chunks)
+    reported = {event.path: event.chunk_count for event in recorder.of_type(FileChunked)}
+
+    assert reported == produced
+    assert [event.path for event in recorder.of_type(FileFailed)] == [failure.path for failure in failures]
+    assert len(recorder.of_type(FileStarted)) == 21
+    assert recorder.events[-1] == Finished(files=21, chunks=len(chunks), failures=1)
+
+def test_progress_sequential(tmp_path):
+    """This tests if the reported chunk count of each file matches the chunks
+    produced by index_dir, including a file which fails."""
+    _make_files(tmp_path)
+    recorder = _Recorder()
+    failures = []
+
+    chunks = list(CodeBoundaryTextSplitter().index_dir(tmp_path, failures=failures, progress=recorder))
+
+    _check_events(recorder, chunks, failures)
+
+    # Each file is started before it is chunked
+    paths = [event.path for event in recorder.events if isinstance(event, (FileStarted, FileChunked, FileFailed))]
+    assert paths[0::2] == paths[1::2]
+
+def test_progress_parallel(tmp_path):
+    """This tests if the per-file events are accurate when files are split
+    concurrently, and the callback is called from the worker threads."""
+    _make_files(tmp_path)
+    recorder = _Recorder()
+
+    chunks, failures = CodeBoundaryTextSplitter().par_index_dir(tmp_path, max_workers=8, progress=recorder)
+
+    assert len(failures) == 1
+    _check_events(recorder, chunks, failures)
@@ -22,6 +22,7 @@ def test_python_imports():
     result = _split_with_detector(code, detector)
 
     expected = """import datetime
+import functools
 import random
 from typing import List"""
     
@@ -119,4 +120,86 @@ if __name__ == "__main__":
     for task in manager.get_incomplete_tasks():
         print(task)"""
     
-    assert expected in result
\ No newline at end of file
+    assert expected in result
+
+def test_python_module_assignment():
+    """This tests if the splitter can successfully split a module-level
+    assignment, including one that spans several lines.
+    """
+    code, detector = _get_test_code_and_detector()
+    result = _split_with_detector(code, detector)
+
+    expected = """PRIORITY_LEVELS = {
+    "low": 0,
+    "normal": 1,
+    "high": 2,
+}"""
+
+    assert expected in result
+    assert 'DEFAULT_PRIORITY = "normal"' in result
+
+def test_python_decorator_attached():
+    """This tests if the splitter keeps decorators attached
+    to the function definition which follows them.
+    """
+    code, detector = _get_test_code_and_detector()
+    result = _split_with_detector(code, detector)
+
+    expected = """@staticmethod
+    def from_description(description: str) -> "Task":
+        return Task(description.strip())"""
+
+    assert expected in result
+    assert "@property" not in result
+
+def test_python_multiline_decorator_attached():
+    """This tests if the splitter keeps a decorator spanning several lines
+    attached to the function definition which follows it.
+    """
+    code, detector = _get_test_code_and_detector()
+    chunks = CodeBoundaryTextSplitter().split_chunks(code, boundary_detector=detector)
+
+    expected = """@functools.lru_cache(
+    maxsize=None,
+)
+def default_manager() -> TaskManager:
+    \"\"\"Utility function to get the task manager shared by all callers.\"\"\"
+    return TaskManager()"""
+
+    chunk = next(chunk for chunk in chunks if chunk.name == "default_manager")
+    assert chunk.text == expected
+    assert not any(chunk.text.startswith("maxsize") or chunk.text == ")" for chunk in chunks)
+
+def test_python_nested_func():
+    """This tests if the splitter keeps a function nested inside another
+    function as part of the outer function, rather than splitting it out.
+    """
+    code, detector = _get_test_code_and_detector()
+    result = _split_with_detector(code, detector)
+
+    expected = """def summarize_tasks(tasks: List["Task"]) -> str:
+    \"\"\"Utility function to summarize a list of tasks.\"\"\"
+    def format_task(task: "Task") -> str:
+        return f"- {task}"
+
+    return "\\n".join(format_task(task) for task in tasks)"""
+
+    assert expected in result
+    assert not any(chunk.startswith("def format_task") for chunk in result)
+
+def test_python_chunk_count_and_lines():
+    """This tests the number of chunks produced for the sample file, as well
+    as the (1-based, inclusive) line ranges of the chunks, where the methods
+    of a class follow the chunk of the class.
+    """
+    code, detector = _get_test_code_and_detector()
+    chunks = CodeBoundaryTextSplitter().split_chunks(code, boundary_detector=detector)
+
+    assert [(chunk.start_line, chunk.end_line) for chunk in chunks] == [
+        (1, 4), (6, 6), (8, 12), (14, 18), (20, 22), (24, 29),
+        # class Task and its methods
+        (31, 70), (36, 43), (45, 47), (49, 51), (53, 55), (57, 59), (61, 63), (65, 66), (68, 70),
+        # class TaskManager and its methods
+        (72, 96), (77, 78), (80, 83), (85, 86), (88, 89), (91, 96),
+        (98, 103), (105, 124),
+    ]
@@ -0,0 +1,54 @@
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter, FallbackMode
+from pipelines.detectors import Language
+from pipelines.recursive_split import recursive_split
+
+PARAGRAPH = " ".join(
+    f"Sentence number {i} describes how the splitter handles prose of {i * 7} words." for i in range(40)
+)
+
+def test_long_paragraph():
+    """This tests if a long paragraph is split into pieces within the limit
+    which reproduce the input, cutting after sentences."""
+    pieces = recursive_split(PARAGRAPH, 200)
+
+    assert len(pieces) > 10
+    assert all(0 < len(piece) <= 200 for piece in pieces)
+    assert "".join(pieces) == PARAGRAPH
+    assert all(piece.endswith(". ") for piece in pieces[:-1])
+
+def test_separator_priority():
+    """This tests if paragraphs are kept whole where possible, and only
+    paragraphs which are too large are split by the next separators."""
+    text = "First paragraph.\n\nSecond line one.\nSecond line two.\n\n" + "x" * 45
+    pieces = recursive_split(text, 20)
+
+    assert pieces == [
+        "First paragraph.\n\n",
+        "Second line one.\n",
+        "Second line two.\n\n",
+        "x" * 20, "x" * 20, "x" * 5,
+    ]
+    assert recursive_split(text, 1000) == [text]
+    assert recursive_split("", 10) == []
+
+def test_custom_size_and_separators():
+    """This tests splitting by a custom size function and separators, and if
+    characters are never split from their combining marks."""
+    text = "e\u0301" * 30
+    pieces = recursive_split(text, 10, separators=[], size=lambda piece: len(piece.encode("utf-8")))
+
+    assert "".join(pieces) == text
+    assert all(len(piece.encode("utf-8")) <= 10 and piece.startswith("e") for piece in pieces)
+
+def test_reused_by_markdown_and_fallback():
+    """This tests if a paragraph written on one line is cut after sentences
+    by the markdown splitter and the line fallback."""
+    text = f"# Notes\n\n{PARAGRAPH}\n"
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=200, fallback=FallbackMode.LINES)
+
+    for chunks in [splitter.split_chunks(text, language=Language.MARKDOWN), splitter.split_file("notes.txt", text)]:
+        pieces = [chunk for chunk in chunks if chunk.start_line == 3]
+        assert len(pieces) > 10
+        assert all(len(chunk.text.encode("utf-8")) <= 200 for chunk in pieces)
+        assert all(chunk.text.endswith(". ") for chunk in pieces[:-1])
+        assert "".join(chunk.text for chunk in pieces) == PARAGRAPH
@@ -0,0 +1,55 @@
+from pathlib import Path
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.reindex import FileStatus
+
+def _make_repo(root: Path):
+    (root / "src").mkdir()
+    (root / "src" / "main.rs").write_text("fn main() {\n    run();\n}\n", "utf-8")
+    (root / "src" / "lib.rs").write_text("pub fn run() {}\n", "utf-8")
+    (root / "README.md").write_text("# Project\n", "utf-8")
+
+def test_reindex_initial(tmp_path):
+    """This tests if every file is added when there is no previous index."""
+    _make_repo(tmp_path)
+    result = CodeBoundaryTextSplitter().reindex_dir(tmp_path, {})
+
+    assert result.paths_with_status(FileStatus.ADDED) == ["README.md", "src/lib.rs", "src/main.rs"]
+    assert sorted(result.hashes) == ["README.md", "src/lib.rs", "src/main.rs"]
+    assert sorted({chunk.path for chunk in result.chunks}) == ["README.md", "src/lib.rs", "src/main.rs"]
+
+def test_reindex_modified(tmp_path):
+    """This tests if only a modified file is reported as modified and re-chunked."""
+    _make_repo(tmp_path)
+    splitter = CodeBoundaryTextSplitter()
+    first = splitter.reindex_dir(tmp_path, {})
+
+    (tmp_path / "src" / "lib.rs").write_text("pub fn run() {\n    println!(\"running\");\n}\n", "utf-8")
+    second = splitter.reindex_dir(tmp_path, first.hashes)
+
+    assert second.statuses == {
+        "README.md": FileStatus.UNCHANGED,
+        "src/lib.rs": FileStatus.MODIFIED,
+        "src/main.rs": FileStatus.UNCHANGED,
+    }
+    assert [chunk.path for chunk in second.chunks] == ["src/lib.rs"]
+    assert "running" in second.chunks[0].text
+    assert second.hashes["src/lib.rs"] != first.hashes["src/lib.rs"]
+    assert second.hashes["src/main.rs"] == first.hashes["src/main.rs"]
+
+def test_reindex_added_deleted(tmp_path):
+    """This tests if added and deleted files are reported, and if rewriting a
+    file with the same contents leaves it unchanged."""
+    _make_repo(tmp_path)
+    splitter = CodeBoundaryTextSplitter()
+    first = splitter.reindex_dir(tmp_path, {})
+
+    (tmp_path / "README.md").unlink()
+    (tmp_path / "src" / "util.rs").write_text("pub fn util() {}\n", "utf-8")
+    (tmp_path / "src" / "main.rs").write_text("fn main() {\n    run();\n}\n", "utf-8")
+    second = splitter.reindex_dir(tmp_path, first.hashes)
+
+    assert second.paths_with_status(FileStatus.DELETED) == ["README.md"]
+    assert second.paths_with_status(FileStatus.ADDED) == ["src/util.rs"]
+    assert second.paths_with_status(FileStatus.UNCHANGED) == ["src/lib.rs", "src/main.rs"]
+    assert "README.md" not in second.hashes
+    assert [chunk.path for chunk in second.chunks] == ["src/util.rs"]
@@ -150,4 +150,77 @@ struct ConsoleLogger;"""
     
     assert expected in result
 
-    
\ No newline at end of file
+    
+def test_rust_enum_derive():
+    """This tests if the splitter can successfully split an enum,
+    keeping its derive attribute glued to it.
+    """
+    code, detector = _get_test_code_and_detector()
+    result = _split_with_detector(code, detector)
+
+    chunk = next(chunk for chunk in result if "enum LogLevel {" in chunk)
+
+    # The chunk starts with the doc comment, followed by the derive
+    doc_comment = "/// @brief Levels of logging severity.\n"
+    assert chunk.startswith(doc_comment)
+    assert chunk[len(doc_comment):].startswith("""#[derive(Debug, Clone, Copy)]
+enum LogLevel {""")
+
+def test_rust_attributes_multiline():
+    """This tests if attributes spanning several lines are kept with
+    the item they annotate, and if inner attributes are kept with the module.
+    """
+    code = """#![allow(dead_code)]
+//! Configuration types.
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(
+    feature = "strict",
+    serde(deny_unknown_fields)
+)]
+pub(crate) struct Config {
+    name: String,
+}
+"""
+    result = _split_with_detector(code, RustBoundaryDetector())
+
+    assert result == [
+        """#![allow(dead_code)]
+//! Configuration types.
+
+use serde::Deserialize;""",
+        """#[derive(Debug, Deserialize)]
+#[cfg_attr(
+    feature = "strict",
+    serde(deny_unknown_fields)
+)]
+pub(crate) struct Config {
+    name: String,
+}""",
+    ]
+
+def test_rust_macro_rules():
+    """This tests if `macro_rules!` definitions are split as their own chunks,
+    including their attributes and doc comments, with nested delimiters
+    balanced so that each chunk ends at the end of its definition.
+    """
+    code = Path("test_data/splitting/sample_macros.rs").read_text("utf-8")
+    detector = RustBoundaryDetector()
+    result = _split_with_detector(code, detector)
+
+    hashmap = code[code.index("/// Builds a `HashMap`"):code.index("}};\n}") + len("}};\n}")]
+    assert hashmap in result
+
+    getters = code[code.index("macro_rules! getters"):code.index("    };\n}\n\nmacro_rules! square") + len("    };\n}")]
+    assert any(chunk.endswith(getters) for chunk in result)
+
+    square = "macro_rules! square_brackets [\n    ($x:expr) => { [$x, '}', '{'] };\n];"
+    assert square in result
+
+    boundaries = detector.find_boundaries(code)
+    assert [b[2] for b in boundaries] == ["macro", "macro", "macro", "function"]
+    # Functions generated by a macro are part of the macro, not chunks of their own
+    assert all(chunk.count("macro_rules!") <= 1 for chunk in result)
+    assert any(chunk.startswith("pub fn counts") for chunk in result)
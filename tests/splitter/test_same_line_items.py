@@ -0,0 +1,36 @@
+from pathlib import Path
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language
+from pipelines.detectors.detector_rust import RustBoundaryDetector
+
+def _split(**kwargs):
+    code = Path("test_data/splitting/sample_same_line.rs").read_text("utf-8")
+    return code.encode("utf-8"), CodeBoundaryTextSplitter(**kwargs).split_chunks(code, language=Language.RUST)
+
+def test_items_on_one_line():
+    """This tests if unit structs and items with bodies sharing a line each
+    get their own chunk, with byte offsets pointing at the item's own span."""
+    source, chunks = _split()
+
+    assert [(chunk.start_line, chunk.name) for chunk in chunks] == [
+        (1, None),
+        (2, "Meters"), (2, "Seconds"), (2, "Kilograms"),
+        (3, "Point"), (3, "Point"), (3, "label"),
+        (5, "main"),
+    ]
+    assert [chunk.text for chunk in chunks[1:4]] == ["struct Meters;", "struct Seconds;", "#[derive(Debug)] pub struct Kilograms;"]
+    assert chunks[5].text.startswith("impl Point {") and chunks[6].text.endswith('"}; struct Fake;" }')
+
+    for chunk in chunks:
+        assert source[chunk.start_byte:chunk.end_byte].decode("utf-8") == chunk.text
+    assert all(a.end_byte <= b.start_byte for a, b in zip(chunks, chunks[1:]))
+
+def test_lines_not_split():
+    """This tests if lines with a single item or with code which isn't an
+    item are left whole."""
+    detector = RustBoundaryDetector()
+
+    assert detector.split_line_items("struct A; struct B;") == [(0, 9, "struct"), (10, 19, "struct")]
+    assert detector.split_line_items("struct A; // struct B;") is None
+    assert detector.split_line_items("fn f() { g(); } let x = 1;") is None
+    assert detector.split_line_items("impl A { fn f() {} fn g() {} }") is None
@@ -0,0 +1,66 @@
+import io
+import pytest
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.split_errors import SplitEncodingError
+
+CODE = "fn main() {\n    run();\n}\n\nfn run() {}\n"
+
+class ChunkedReader(io.RawIOBase):
+    """Reader returning at most *size* bytes per read, so that characters are
+    split between reads."""
+
+    def __init__(self, data: bytes, size: int):
+        self.data = data
+        self.size = size
+
+    def readable(self):
+        return True
+
+    def read(self, n=-1):
+        piece, self.data = self.data[:self.size], self.data[self.size:]
+        return piece
+
+def test_bytes_and_reader_match_text():
+    """This tests if splitting bytes or a reader gives the chunks of splitting
+    the decoded text, also with characters split between reads."""
+    splitter = CodeBoundaryTextSplitter()
+    code = CODE.replace("run()", "ränn()")
+    expected = splitter.split_file("src/main.rs", code)
+
+    assert splitter.split_bytes("src/main.rs", code.encode("utf-8")) == expected
+    assert splitter.split_reader("src/main.rs", ChunkedReader(code.encode("utf-8"), 3)) == expected
+    assert splitter.split_reader("src/main.rs", io.BytesIO(code.encode("utf-8"))) == expected
+
+def test_empty_input():
+    """This tests if empty input gives no chunks."""
+    splitter = CodeBoundaryTextSplitter()
+
+    assert splitter.split_bytes("src/main.rs", b"") == []
+    assert splitter.split_reader("src/main.rs", io.BytesIO(b"")) == []
+    assert splitter.split_bytes("src/main.rs", "\ufeff".encode("utf-8")) == []
+
+def test_bom_stripped():
+    """This tests if a byte order mark is not part of the first chunk, but is
+    counted by the byte offsets, which still index into the file."""
+    data = "\ufeff".encode("utf-8") + CODE.encode("utf-8")
+    splitter = CodeBoundaryTextSplitter()
+
+    for chunks in [splitter.split_bytes("src/main.rs", data), splitter.split_reader("src/main.rs", io.BytesIO(data))]:
+        assert [chunk.name for chunk in chunks] == ["main", "run"]
+        assert chunks[0].text.startswith("fn main()") and chunks[0].start_byte == 3
+        assert chunks[0].start_line == 1
+        for chunk in chunks:
+            assert data[chunk.start_byte:chunk.end_byte].decode("utf-8") == chunk.text
+
+def test_invalid_utf8():
+    """This tests if invalid UTF-8 raises an encoding error with the offset of
+    the invalid byte, also when it is split between reads."""
+    data = "fn café() {}\n".encode("utf-8")[:7] + b"\xff() {}\n"
+    splitter = CodeBoundaryTextSplitter()
+
+    with pytest.raises(SplitEncodingError, match="at byte 6") as error:
+        splitter.split_bytes("src/cafe.rs", data)
+    assert error.value.path == "src/cafe.rs"
+
+    with pytest.raises(SplitEncodingError, match="at byte 6"):
+        splitter.split_reader("src/cafe.rs", ChunkedReader(data, 2))
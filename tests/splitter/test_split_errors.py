@@ -0,0 +1,51 @@
+import pytest
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter, FallbackMode
+from pipelines.chunk import SplitFailure
+from pipelines.split_errors import SplitEncodingError, SplitIoError, SplitParseError, UnsupportedLanguageError
+
+def test_split_path_nonexistent(tmp_path):
+    """This tests if a nonexistent path raises an IO error."""
+    with pytest.raises(SplitIoError) as ex:
+        CodeBoundaryTextSplitter().split_path("missing.rs", root=tmp_path)
+
+    assert ex.value.path == "missing.rs"
+
+def test_split_path_invalid_utf8(tmp_path):
+    """This tests if a file which is not valid UTF-8 raises an encoding error."""
+    (tmp_path / "latin1.py").write_bytes("name = 'Jos\xe9'\n".encode("latin-1"))
+
+    with pytest.raises(SplitEncodingError):
+        CodeBoundaryTextSplitter().split_path("latin1.py", root=tmp_path)
+
+def test_split_file_unsupported_language():
+    """This tests if an unsupported language raises an error if so configured."""
+    splitter = CodeBoundaryTextSplitter(fallback=FallbackMode.ERROR)
+
+    with pytest.raises(UnsupportedLanguageError):
+        splitter.split_file("Cargo.toml", "[package]\n")
+    assert len(splitter.split_file("main.rs", "fn main() {}\n")) == 1
+
+def test_split_file_parse_error(monkeypatch):
+    """This tests if an exception raised while splitting becomes a parse error."""
+    splitter = CodeBoundaryTextSplitter()
+
+    def fail(*args, **kwargs):
+        raise RuntimeError("detector bug")
+    monkeypatch.setattr(splitter, "split_chunks", fail)
+
+    with pytest.raises(SplitParseError) as ex:
+        splitter.split_file("main.rs", "fn main() {}\n")
+    assert isinstance(ex.value.__cause__, RuntimeError)
+
+def test_index_dir_collects_failures(tmp_path):
+    """This tests if files which can't be split are collected while walking
+    a directory, without stopping the other files from being split."""
+    (tmp_path / "good.py").write_text("def main():\n    pass\n", "utf-8")
+    (tmp_path / "bad.py").write_bytes(b"x = '\xff'\n")
+
+    failures = []
+    chunks = list(CodeBoundaryTextSplitter().index_dir(tmp_path, failures=failures))
+
+    assert [chunk.path for chunk in chunks] == ["good.py"]
+    assert [(failure.path, type(failure.error)) for failure in failures] == [("bad.py", SplitEncodingError)]
+    assert isinstance(failures[0], SplitFailure)
@@ -0,0 +1,46 @@
+from pathlib import Path
+from types import GeneratorType
+from langchain_core.documents import Document
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language, get_boundary_detectors
+
+def _get_test_code() -> str:
+    return Path("test_data/splitting/sample_rs.rs").read_text("utf-8")
+
+def test_split_iter_lazy():
+    """This tests if ``split_iter`` returns a lazy iterator, which yields
+    the same chunks as ``split_chunks``.
+    """
+    code = _get_test_code()
+    splitter = CodeBoundaryTextSplitter(min_chunk_bytes=100)
+
+    chunks = splitter.split_iter(code, language=Language.RUST)
+    assert isinstance(chunks, GeneratorType)
+
+    first = next(chunks)
+    assert first.start_line == 1
+
+    assert [first, *chunks] == splitter.split_chunks(code, language=Language.RUST)
+
+def test_split_documents_iter_lazy():
+    """This tests if ``split_documents_iter`` consumes its input lazily,
+    splitting one document before the next one is requested.
+    """
+    code = _get_test_code()
+    requested = []
+
+    def documents():
+        for name in ["a.rs", "b.rs"]:
+            requested.append(name)
+            yield Document(page_content=code, metadata={"source": name})
+
+    splitter = CodeBoundaryTextSplitter()
+    docs = splitter.split_documents_iter(documents(), boundary_detectors=get_boundary_detectors())
+
+    first = next(docs)
+    assert first.metadata["source"] == "a.rs"
+    assert requested == ["a.rs"]
+
+    rest = list(docs)
+    assert requested == ["a.rs", "b.rs"]
+    assert len(rest) + 1 == 2 * len(splitter.split_chunks(code, language=Language.RUST))
@@ -0,0 +1,31 @@
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.split_errors import SplitEncodingError, SplitIoError
+
+def _make_files(root):
+    (root / "main.rs").write_text("fn main() {\n    run();\n}\n\nfn run() {}\n", "utf-8")
+    (root / "notes.txt").write_text("Some notes.\n", "utf-8")
+    (root / "latin1.rs").write_bytes("fn caf\xe9() {}\n".encode("latin-1"))
+
+def test_split_files_report(tmp_path):
+    """This tests the counters and errors of splitting a Rust file, an
+    unsupported file and an unreadable path."""
+    _make_files(tmp_path)
+    splitter = CodeBoundaryTextSplitter()
+
+    report = splitter.split_files_report(["main.rs", "notes.txt", "missing.rs"], root=tmp_path)
+
+    assert (report.files_parsed, report.files_fallback, report.files_skipped) == (1, 1, 1)
+    assert [(error.path, type(error.error)) for error in report.errors] == [("missing.rs", SplitIoError)]
+    assert [chunk.path for chunk in report.chunks] == ["main.rs", "main.rs", "notes.txt"]
+    assert report.summary() == "3 files: 1 parsed, 1 fallback, 1 skipped, 3 chunks"
+
+def test_index_dir_report(tmp_path):
+    """This tests if the report of a directory has the chunks of index_dir."""
+    _make_files(tmp_path)
+    splitter = CodeBoundaryTextSplitter()
+
+    report = splitter.index_dir_report(tmp_path)
+
+    assert report.chunks == list(splitter.index_dir(tmp_path, failures=[]))
+    assert (report.files, report.files_parsed, report.files_fallback, report.files_skipped) == (3, 1, 1, 1)
+    assert [(error.path, type(error.error)) for error in report.errors] == [("latin1.rs", SplitEncodingError)]
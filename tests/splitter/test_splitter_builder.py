@@ -0,0 +1,54 @@
+import pytest
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter, FallbackMode
+from pipelines.detectors import Language
+from pipelines.heuristic_tokenizer import HeuristicTokenizer
+from pipelines.splitter_builder import SplitterBuilder
+
+def _settings(splitter: CodeBoundaryTextSplitter) -> dict:
+    return {
+        "chunk_size": splitter._chunk_size,
+        "max_chunk_bytes": splitter.max_chunk_bytes,
+        "max_chunk_tokens": splitter.max_chunk_tokens,
+        "min_chunk_bytes": splitter.min_chunk_bytes,
+        "overlap_lines": splitter.overlap_lines,
+        "tokenizer": splitter.tokenizer,
+        "fallback": splitter.fallback,
+        "language": splitter.language,
+    }
+
+def test_builder_default():
+    """This tests if the default builder matches the default splitter."""
+    assert _settings(SplitterBuilder().build()) == _settings(CodeBoundaryTextSplitter())
+
+def test_builder_chained():
+    """This tests if chained setters configure the splitter."""
+    tokenizer = HeuristicTokenizer()
+    splitter = (
+        SplitterBuilder()
+        .max_chunk_tokens(500)
+        .tokenizer(tokenizer)
+        .overlap(3)
+        .min_chunk_bytes(50)
+        .fallback(FallbackMode.LINES)
+        .language(Language.PYTHON)
+        .build()
+    )
+
+    assert splitter.max_chunk_tokens == 500
+    assert splitter.tokenizer is tokenizer
+    assert splitter.overlap_lines == 3
+    assert splitter.min_chunk_bytes == 50
+    assert splitter.fallback == FallbackMode.LINES
+    assert splitter.split_chunks("def main():\n    pass\n")[0].language == Language.PYTHON
+
+@pytest.mark.parametrize("builder", [
+    SplitterBuilder().max_chunk_bytes(10).overlap(10),
+    SplitterBuilder().max_chunk_bytes(100).min_chunk_bytes(200),
+    SplitterBuilder().max_chunk_tokens(100),
+    SplitterBuilder().max_chunk_bytes(0),
+    SplitterBuilder().overlap(-1),
+])
+def test_builder_invalid(builder):
+    """This tests if conflicting or invalid settings are rejected."""
+    with pytest.raises(ValueError):
+        builder.build()
@@ -0,0 +1,60 @@
+from pathlib import Path
+from typing import List, Tuple
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language
+
+def _split_ranges(filename: str, language: Language) -> List[Tuple[int, int]]:
+    code = Path(f"test_data/splitting/{filename}").read_text("utf-8")
+    chunks = CodeBoundaryTextSplitter().split_chunks(code, language=language)
+    return [(chunk.start_line, chunk.end_line) for chunk in chunks]
+
+def test_typescript_class_methods():
+    """This tests if a TypeScript class is split into a chunk for the class
+    and a chunk for each of its methods, with leading comments attached.
+    """
+    ranges = _split_ranges("sample_ts.ts", Language.TYPESCRIPT)
+
+    assert ranges == [
+        (1, 5),    # file doc comment and imports
+        (7, 12),   # enum, with its comment
+        (14, 14),  # type alias
+        (16, 20),  # interface
+        (22, 53),  # class, with its doc comment
+        (29, 31),  # constructor
+        (33, 38),  # push, with its comment
+        (40, 43),  # pop, with its doc comment
+        (45, 48),  # describe, with braces in template literals
+        (50, 52),  # arrow function field
+        (55, 57),  # default exported function
+    ]
+
+def test_typescript_doc_comment():
+    """This tests if a `/** */` doc comment is parsed for a class."""
+    code = Path("test_data/splitting/sample_ts.ts").read_text("utf-8")
+    chunks = CodeBoundaryTextSplitter().split_chunks(code, language=Language.TYPESCRIPT)
+    chunk = next(chunk for chunk in chunks if chunk.start_line == 22)
+
+    assert chunk.doc.brief == "Queue of pending tasks, ordered by priority."
+    assert chunk.doc.params == {"name": "Name used in log messages."}
+
+def test_javascript_exported_arrows():
+    """This tests if top-level exported arrow functions, function expressions
+    and function declarations are each split into their own chunk.
+    """
+    ranges = _split_ranges("sample_js.js", Language.JAVASCRIPT)
+
+    assert ranges == [
+        (1, 3),    # file comment and constant
+        (5, 11),   # formatDate
+        (13, 26),  # formatBytes, with its doc comment
+        (28, 29),  # capitalize, an expression body spanning two lines
+        (31, 34),  # fetchJson, a function expression
+        (36, 42),  # debounce, with nested arrow functions
+    ]
+
+def test_javascript_jsx():
+    """This tests if JSX, including an apostrophe in element text,
+    does not break splitting."""
+    ranges = _split_ranges("sample_jsx.jsx", Language.JAVASCRIPT)
+
+    assert ranges == [(1, 1), (3, 11), (13, 20)]
@@ -0,0 +1,55 @@
+import asyncio
+import random
+import pytest
+from pipelines import BatchingEmbedder, EmbedError, HeuristicTokenizer, TestingEmbedder
+
+class RecordingEmbedder(TestingEmbedder):
+    """Testing embedder which records the size of each batch and finishes
+    batches in random order."""
+
+    def __init__(self):
+        super().__init__(dimension=8)
+        self.batch_sizes = []
+
+    async def embed(self, texts):
+        self.batch_sizes.append(len(texts))
+        await asyncio.sleep(random.random() / 100)
+        return await super().embed(texts)
+
+def test_batches_honor_batch_size():
+    texts = [f"fn item_{i}() {{}}" for i in range(23)]
+    recording = RecordingEmbedder()
+    embedder = BatchingEmbedder(recording, batch_size=5, max_concurrency=4)
+
+    vectors = asyncio.run(embedder.embed(texts))
+
+    assert max(recording.batch_sizes) <= 5
+    assert sorted(recording.batch_sizes) == [3, 5, 5, 5, 5]
+    # Each vector is reattached to its own text despite concurrent batches
+    assert vectors == [recording.embed_text(text) for text in texts]
+
+def test_batches_honor_token_cap():
+    tokenizer = HeuristicTokenizer()
+    texts = ["word " * 30, "word " * 30, "short", "word " * 200, "short", "short"]
+    recording = RecordingEmbedder()
+    embedder = BatchingEmbedder(recording, batch_size=10, max_batch_tokens=50, tokenizer=tokenizer)
+
+    vectors = asyncio.run(embedder.embed(texts))
+
+    assert len(vectors) == len(texts)
+    for batch in embedder.batches(texts):
+        tokens = sum(tokenizer.count_tokens(texts[i]) for i in batch)
+        # Oversized texts are sent on their own
+        assert tokens <= 50 or len(batch) == 1
+    assert sum(recording.batch_sizes) == len(texts)
+
+def test_batch_count_mismatch():
+    class DroppingEmbedder(TestingEmbedder):
+        async def embed(self, texts):
+            return (await super().embed(texts))[1:]
+
+    with pytest.raises(EmbedError):
+        asyncio.run(BatchingEmbedder(DroppingEmbedder(), batch_size=2).embed(["a", "b", "c"]))
+
+def test_empty_input():
+    assert asyncio.run(BatchingEmbedder(RecordingEmbedder()).embed([])) == []
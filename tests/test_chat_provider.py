@@ -0,0 +1,93 @@
+import asyncio
+import json
+import pytest
+from langchain_core.documents import Document
+from pipelines import (ChatError, OllamaChatProvider, OpenAIChatProvider, ProviderChatPipeline,
+                       TestingChatProvider)
+
+class FakeMemory:
+    def __init__(self, documents):
+        self.documents = documents
+        self.questions = []
+
+    def ready_for_retrieval(self):
+        return True
+
+    def invoke(self, question):
+        self.questions.append(question)
+        return self.documents
+
+def test_pipeline_through_mock_provider():
+    memory = FakeMemory([Document(page_content="fn main() {}", metadata={"source": "src/main.rs"})])
+    provider = TestingChatProvider(lambda messages: "The entry point is `main`.")
+    pipeline = ProviderChatPipeline(provider, memory, chat_settings={
+        "system_prompt": "You answer questions about code.",
+        "temperature": 0.2,
+        "top_k": 40,
+    })
+
+    messages = [
+        {"role": "user", "content": "Hi"},
+        {"role": "assistant", "content": "Hello!"},
+        {"role": "user", "content": "Where does the program start?"},
+    ]
+    answer = "".join(chunk.content for chunk in pipeline.query(messages))
+
+    assert answer == "The entry point is `main`."
+    assert memory.questions == ["Where does the program start?"]
+
+    (prompt, options), = provider.requests
+    assert options == {"temperature": 0.2, "top_k": 40}
+    assert prompt[0]["role"] == "system"
+    assert prompt[0]["content"].startswith("You answer questions about code.")
+    assert "src/main.rs\nfn main() {}" in prompt[0]["content"]
+    assert prompt[1:] == messages
+
+def test_testing_provider_complete():
+    provider = TestingChatProvider()
+    completion = asyncio.run(provider.complete([{"role": "user", "content": "echo this"}]))
+    assert completion.text == "echo this"
+    assert list(provider.stream([{"role": "user", "content": "a b  c"}])) == ["a", " b", "  c"]
+
+def test_openai_provider_config():
+    requests = []
+
+    def transport(url, body, headers):
+        requests.append((url, json.loads(body), headers))
+        return [b'data: {"choices": [{"delta": {"content": "ok"}}]}\n\ndata: [DONE]\n\n']
+
+    provider = OpenAIChatProvider("http://localhost:8080/v1", "local-model", transport=transport)
+    completion = asyncio.run(provider.complete([{"role": "user", "content": "hi"}], {"temperature": 0.5}))
+
+    assert completion.text == "ok"
+    url, body, headers = requests[0]
+    assert url == "http://localhost:8080/v1/chat/completions"
+    assert body["model"] == "local-model" and body["temperature"] == 0.5
+    assert "Authorization" not in headers
+
+def test_ollama_provider():
+    requests = []
+    lines = (
+        b'{"message": {"role": "assistant", "content": "Hel"}, "done": false}\n'
+        b'{"message": {"role": "assistant", "content": "lo"}, "done": false}\n'
+        b'{"message": {"role": "assistant", "content": ""}, "done": true, "done_reason": "stop"}\n'
+    )
+
+    def transport(url, body, headers):
+        requests.append((url, json.loads(body)))
+        return [lines[i:i + 10] for i in range(0, len(lines), 10)]
+
+    provider = OllamaChatProvider("http://localhost:11434", "qwen3", transport=transport)
+    completion = asyncio.run(provider.complete([{"role": "user", "content": "hi"}], {"num_ctx": 4096}))
+
+    assert completion.text == "Hello"
+    assert completion.finish_reason == "stop"
+    url, body = requests[0]
+    assert url == "http://localhost:11434/api/chat"
+    assert body["options"] == {"num_ctx": 4096}
+    assert list(provider.stream([{"role": "user", "content": "hi"}])) == ["Hel", "lo"]
+
+def test_ollama_provider_error():
+    provider = OllamaChatProvider(transport=lambda url, body, headers: [b'{"error": "model not found"}\n'])
+    with pytest.raises(ChatError, match="model not found"):
+        asyncio.run(provider.complete([{"role": "user", "content": "hi"}]))
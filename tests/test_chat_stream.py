@@ -0,0 +1,71 @@
+import json
+import pytest
+from pipelines import ChatError
+from pipelines.chat_stream import SseParser, chat_completion, iter_completion_deltas, stream_chat_completion
+
+def event(content: str) -> str:
+    return "data: " + json.dumps({"choices": [{"index": 0, "delta": {"content": content}}]}) + "\n\n"
+
+def split_every(data: bytes, size: int):
+    return [data[i:i + size] for i in range(0, len(data), size)]
+
+def test_reassemble_chunked_response():
+    """Test that events split across arbitrary network reads, including in
+    the middle of a UTF-8 sequence, are reassembled."""
+    stream = (
+        ": keep-alive\n\n"
+        + "data: " + json.dumps({"choices": [{"index": 0, "delta": {"role": "assistant"}}]}) + "\n\n"
+        + event("Hello")
+        + event(", wörld")
+        + event(" — done.")
+        + "data: [DONE]\n\n"
+    ).encode("utf-8")
+
+    for size in (1, 3, 7, len(stream)):
+        deltas = list(iter_completion_deltas(split_every(stream, size)))
+        assert "".join(deltas) == "Hello, wörld — done."
+        assert deltas == ["Hello", ", wörld", " — done."]
+
+def test_crlf_and_multiline_data():
+    parser = SseParser()
+    events = parser.feed(b"data: first\r")
+    events += parser.feed(b"\ndata: second\r\n\r\n")
+    assert events == ["first\nsecond"]
+
+def test_stream_stops_at_done():
+    chunks = [(event("a") + "data: [DONE]\n\n" + event("ignored")).encode("utf-8")]
+    assert list(iter_completion_deltas(chunks)) == ["a"]
+
+def test_stream_without_done_is_error():
+    with pytest.raises(ChatError):
+        list(iter_completion_deltas([event("partial").encode("utf-8")]))
+
+def test_error_event():
+    chunks = [b'data: {"error": {"message": "model not found"}}\n\n']
+    with pytest.raises(ChatError, match="model not found"):
+        list(iter_completion_deltas(chunks))
+
+def test_chat_completion_request():
+    requests = []
+
+    def transport(url, body, headers):
+        requests.append((url, json.loads(body), headers))
+        return split_every((event("4") + event("2") + "data: [DONE]\n\n").encode("utf-8"), 5)
+
+    messages = [{"role": "user", "content": "What is 6 * 7?"}]
+    answer = chat_completion("http://localhost:11434/v1/", "qwen3", messages,
+                             api_key="secret", options={"temperature": 0.0}, transport=transport)
+
+    assert answer == "42"
+    url, body, headers = requests[0]
+    assert url == "http://localhost:11434/v1/chat/completions"
+    assert body == {"temperature": 0.0, "model": "qwen3", "messages": messages, "stream": True}
+    assert headers["Authorization"] == "Bearer secret"
+
+def test_stream_is_lazy():
+    """Test that deltas are yielded before the whole response has arrived."""
+    def transport(url, body, headers):
+        yield event("first").encode("utf-8")
+        raise AssertionError("read past the first delta")
+
+    assert next(stream_chat_completion("http://localhost/v1", "m", [], transport=transport)) == "first"
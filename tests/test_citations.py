@@ -0,0 +1,35 @@
+import asyncio
+from pipelines import InMemoryStore, TestingEmbedder
+from pipelines.citations import Citation, answer_with_citations, parse_citations
+
+def test_citations_match_context(log_chunks):
+    embedder = TestingEmbedder()
+    store = InMemoryStore()
+    store.add(log_chunks, asyncio.run(embedder.embed_chunks(log_chunks)))
+
+    question = "How do I log a message?"
+    (query,) = asyncio.run(embedder.embed([question]))
+    retrieved = [chunk for chunk, _ in store.search(query, k=3)]
+
+    prompts = []
+    def complete(messages):
+        prompts.append(messages)
+        return "Call `log_message` [1] with a level [2, 1]. Unknown passages [9] and [0] are ignored."
+
+    answer = answer_with_citations(question, retrieved, complete)
+
+    expected = [Citation(i, c.path, c.start_line, c.end_line) for i, c in enumerate(retrieved, start=1)]
+    assert answer.citations == expected
+    assert answer.cited == [expected[0], expected[1]]
+
+    # Each chunk is placed in the prompt with its number and location
+    system, user = prompts[0]
+    assert user == {"role": "user", "content": question}
+    for citation, chunk in zip(expected, retrieved):
+        assert f"[{citation.index}] {citation.location}\n{chunk.text}" in system["content"]
+
+def test_parse_citations_ignores_unknown():
+    citations = [Citation(1, "a.rs", 1, 5)]
+    assert parse_citations("See [3] and [1] and [1].", citations) == citations
+    assert parse_citations("No citations here, just arr[0].", citations) == []
+    assert Citation(2, "src/lib.rs", 4, 9).location == "src/lib.rs:4-9"
@@ -0,0 +1,44 @@
+import json
+import os
+import subprocess
+import sys
+from pathlib import Path
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+
+def run_cli(*args):
+    # Run the CLI as a separate process, with the same import paths as the tests
+    env = dict(os.environ, PYTHONPATH=os.pathsep.join(path for path in sys.path if path))
+    return subprocess.run([sys.executable, "aqchat/cli.py", *args], capture_output=True, text=True, env=env)
+
+def test_split_file_json():
+    fixture = "test_data/splitting/sample_rs.rs"
+    result = run_cli("split", fixture, "--format", "json")
+
+    assert result.returncode == 0, result.stderr
+    chunks = json.loads(result.stdout)
+    expected = CodeBoundaryTextSplitter().split_path(fixture)
+
+    assert len(chunks) == len(expected) == 15
+    assert [chunk["text"] for chunk in chunks] == [chunk.text for chunk in expected]
+    assert all(chunk["language"] == "rust" and chunk["path"] == fixture for chunk in chunks)
+
+def test_split_directory_jsonl(tmp_path):
+    (tmp_path / ".gitignore").write_text("ignored.py\n")
+    (tmp_path / "ignored.py").write_text("def ignored():\n    pass\n")
+    (tmp_path / "main.py").write_text("def main():\n    pass\n")
+    (tmp_path / "bad.py").write_bytes(b"def bad():\n    return '\xff'\n")
+
+    result = run_cli("split", str(tmp_path), "--language", "python")
+
+    # The file which could be split is still emitted despite the error
+    assert result.returncode == 1
+    assert "bad.py" in result.stderr
+    chunks = [json.loads(line) for line in result.stdout.splitlines()]
+    paths = {chunk["path"] for chunk in chunks}
+    assert "main.py" in paths
+    assert "ignored.py" not in paths and "bad.py" not in paths
+
+def test_split_text_max_tokens():
+    result = run_cli("split", "test_data/splitting/sample_py.py", "--format", "text", "--max-tokens", "50")
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.startswith("==> test_data/splitting/sample_py.py:1-")
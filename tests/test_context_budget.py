@@ -0,0 +1,109 @@
+import pytest
+from langchain_core.documents import Document
+from pipelines import HeuristicTokenizer
+from pipelines.chunk import Chunk
+from pipelines.context_budget import fit_documents_to_budget, fit_to_budget, format_context
+from pipelines.detectors import Language
+
+def make_chunk(name: str, body_lines: int, parent_context: str | None = None, language: Language | None = None) -> Chunk:
+    text = f"fn {name}() {{\n" + "".join(f"    let value_{i} = compute({i});\n" for i in range(body_lines)) + "}"
+    return Chunk(text=text, start_byte=0, end_byte=len(text.encode("utf-8")), start_line=1,
+                 end_line=text.count("\n") + 1, parent_context=parent_context, path=f"{name}.rs",
+                 language=language)
+
+@pytest.mark.parametrize("budget", [20, 60, 150, 400, 1000])
+def test_context_never_exceeds_budget(budget):
+    tokenizer = HeuristicTokenizer()
+    results = [
+        (make_chunk("huge", 200), 0.9),
+        (make_chunk("small", 2), 0.5),
+        (make_chunk("method", 50, parent_context="impl Foo {"), 0.7),
+    ]
+
+    chunks = fit_to_budget(results, budget, tokenizer)
+    assert tokenizer.count_tokens(format_context(chunks)) <= budget
+
+def test_highest_scores_prioritized():
+    tokenizer = HeuristicTokenizer()
+    best = make_chunk("best", 5)
+    results = [(make_chunk("worst", 5), 0.1), (best, 0.9), (make_chunk("middle", 5), 0.5)]
+    budget = tokenizer.count_tokens(best.text) + 5
+
+    chunks = fit_to_budget(results, budget, tokenizer)
+    assert chunks[0] == best
+    assert all(chunk.path != "worst.rs" for chunk in chunks)
+
+    # With enough budget, everything is included in score order
+    chunks = fit_to_budget(results, 10_000, tokenizer)
+    assert [chunk.path for chunk in chunks] == ["best.rs", "middle.rs", "worst.rs"]
+
+def test_truncation_keeps_parent_context():
+    tokenizer = HeuristicTokenizer()
+    chunk = make_chunk("method", 100, parent_context="impl Foo for Bar {")
+
+    (truncated,) = fit_to_budget([(chunk, 1.0)], 80, tokenizer)
+    assert truncated.parent_context == "impl Foo for Bar {"
+    assert format_context([truncated]).startswith("impl Foo for Bar {\nfn method() {")
+    assert chunk.text.startswith(truncated.text)
+    assert truncated.end_line < chunk.end_line
+    assert truncated.end_byte == len(truncated.text.encode("utf-8"))
+    assert tokenizer.count_tokens(format_context([truncated])) <= 80
+
+def test_chunk_dropped_if_nothing_fits():
+    assert fit_to_budget([(make_chunk("huge", 10, parent_context="impl Foo {"), 1.0)], 3) == []
+
+def test_fit_documents_to_budget():
+    tokenizer = HeuristicTokenizer()
+    chunk = make_chunk("method", 100, parent_context="impl Foo {")
+    documents = [
+        Document(page_content=chunk.embedding_text, metadata={"source": "method.rs", "parent_context": "impl Foo {"}),
+        Document(page_content="short", metadata={"source": "b.md"}),
+    ]
+
+    fitted = fit_documents_to_budget(documents, 60, tokenizer)
+    assert fitted[0].page_content.startswith("impl Foo {\nfn method() {")
+    assert fitted[0].metadata["source"] == "method.rs"
+    assert sum(tokenizer.count_tokens(d.page_content) for d in fitted) <= 60
+
+@pytest.mark.parametrize("budget", [30, 80, 300])
+def test_code_truncation_keeps_signature_and_closing_brace(budget):
+    tokenizer = HeuristicTokenizer()
+    chunk = make_chunk("huge", 200, parent_context="impl Foo {", language=Language.RUST)
+
+    (truncated,) = fit_to_budget([(chunk, 1.0)], budget, tokenizer)
+    lines = truncated.text.split("\n")
+    assert lines[0] == "fn huge() {" and lines[-1] == "}"
+    assert lines[-2].startswith("    // \u2026truncated (") and lines[-2].endswith(" lines omitted)")
+
+    # The kept lines are whole statements from the start of the body
+    omitted = int(lines[-2].split("(")[1].split()[0])
+    assert len(lines) - 3 + omitted == 200
+    assert lines[:-2] == chunk.text.split("\n")[:len(lines) - 2]
+    assert (truncated.start_line, truncated.end_line) == (chunk.start_line, chunk.end_line)
+    assert tokenizer.count_tokens(format_context([truncated])) <= budget
+
+def test_code_truncation_in_python():
+    text = "def run():\n" + "".join(f"    total += compute({i})\n" for i in range(100)) + "    return total"
+    chunk = Chunk(text=text, start_byte=0, end_byte=len(text), start_line=1, end_line=102, language=Language.PYTHON)
+
+    (truncated,) = fit_to_budget([(chunk, 1.0)], 60)
+    lines = truncated.text.split("\n")
+    assert lines[0] == "def run():" and lines[-1] == "    return total"
+    assert lines[-2].startswith("    # \u2026truncated (")
+
+def test_prose_truncated_at_sentence():
+    text = "The splitter cuts files into chunks. " * 20 + "Chunks are embedded. Then they are stored."
+    chunk = Chunk(text=text, start_byte=0, end_byte=len(text), start_line=1, end_line=1, language=Language.MARKDOWN)
+
+    (truncated,) = fit_to_budget([(chunk, 1.0)], 50)
+    assert truncated.text.endswith("chunks.") and text.startswith(truncated.text)
+    assert truncated.end_byte == len(truncated.text)
+
+def test_fit_documents_truncates_code():
+    chunk = make_chunk("method", 100, parent_context="impl Foo {")
+    document = Document(page_content=chunk.embedding_text,
+                        metadata={"source": "method.rs", "parent_context": "impl Foo {", "language": "rust"})
+
+    (fitted,) = fit_documents_to_budget([document], 60)
+    assert fitted.page_content.startswith("impl Foo {\nfn method() {")
+    assert fitted.page_content.endswith("lines omitted)\n}")
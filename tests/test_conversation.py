@@ -0,0 +1,68 @@
+from pipelines import Conversation, HeuristicTokenizer, ProviderChatPipeline, TestingChatProvider
+from pipelines.conversation import MESSAGE_OVERHEAD_TOKENS
+
+def test_old_turns_evicted():
+    conversation = Conversation("You are a helpful assistant.", max_tokens=200)
+    for i in range(50):
+        conversation.add_user(f"Question number {i} about the splitter?")
+        conversation.add_assistant(f"Answer number {i}: the splitter splits code at boundaries.")
+
+    evicted = conversation.trim()
+
+    assert evicted > 0
+    assert conversation.turn_count == 50 - evicted
+    assert conversation.token_usage() <= 200
+
+    messages = conversation.messages
+    assert messages[0] == {"role": "system", "content": "You are a helpful assistant."}
+    assert messages[-1]["content"].startswith("Answer number 49")
+    # Only whole turns are evicted, so the history starts with a user message
+    assert messages[1]["role"] == "user"
+    assert all("number 0 " not in message["content"] for message in messages)
+
+def test_context_tokens_reserved():
+    conversation = Conversation("system", max_tokens=300)
+    for i in range(10):
+        conversation.add_user("word " * 10)
+        conversation.add_assistant("word " * 10)
+
+    without_context = Conversation.from_messages(conversation.messages, max_tokens=300)
+    without_context.trim()
+    conversation.trim(context_tokens=200)
+
+    assert conversation.turn_count < without_context.turn_count
+    assert conversation.token_usage(context_tokens=200) <= 300
+
+def test_latest_turn_kept():
+    conversation = Conversation("system", max_tokens=10)
+    conversation.add_user("an old question")
+    conversation.add_user("a very long question " * 20)
+
+    assert conversation.trim() == 1
+    assert [m["role"] for m in conversation.messages] == ["system", "user"]
+
+def test_token_usage_estimate():
+    tokenizer = HeuristicTokenizer()
+    conversation = Conversation("be brief", tokenizer=tokenizer)
+    conversation.add_user("hello there")
+
+    expected = tokenizer.count_tokens("be brief") + tokenizer.count_tokens("hello there") + 2 * MESSAGE_OVERHEAD_TOKENS
+    assert conversation.token_usage() == expected
+    assert conversation.token_usage(context_tokens=10) == expected + 10
+
+def test_pipeline_trims_history():
+    provider = TestingChatProvider()
+    pipeline = ProviderChatPipeline(provider, chat_settings={"system_prompt": "system", "num_ctx": 60})
+
+    messages = []
+    for i in range(20):
+        messages.append({"role": "user", "content": f"question {i} " * 3})
+        messages.append({"role": "assistant", "content": f"answer {i} " * 3})
+    messages.append({"role": "user", "content": "latest question"})
+
+    assert "".join(chunk.content for chunk in pipeline.query(messages)) == "latest question"
+
+    (prompt, _), = provider.requests
+    assert prompt[0] == {"role": "system", "content": "system"}
+    assert prompt[-1] == {"role": "user", "content": "latest question"}
+    assert len(prompt) < len(messages)
@@ -0,0 +1,63 @@
+import asyncio
+import math
+import pytest
+from pipelines import EmbedError, LangchainEmbedder, TestingEmbedder
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.detectors import Language
+from pipelines.langchain_embedder import EmbedderEmbeddings
+
+SOURCE = '''class Greeter:
+    def greet(self, name):
+        return f"Hello, {name}!"
+
+def main():
+    Greeter().greet("world")
+'''
+
+def test_testing_embedder_chunks():
+    """Test that indexing chunks through the testing embedder gives one
+    vector of the configured dimension per chunk."""
+    splitter = CodeBoundaryTextSplitter(max_chunk_bytes=60, language=Language.PYTHON)
+    chunks = list(splitter.split_iter(SOURCE))
+    assert len(chunks) > 1
+
+    embedder = TestingEmbedder(dimension=12)
+    vectors = asyncio.run(embedder.embed_chunks(chunks))
+
+    assert len(vectors) == len(chunks)
+    for vector in vectors:
+        assert len(vector) == 12
+        assert math.isclose(math.sqrt(sum(value * value for value in vector)), 1.0)
+
+def test_testing_embedder_deterministic():
+    embedder = TestingEmbedder()
+    first, second, other = asyncio.run(embedder.embed(["fn main() {}", "fn main() {}", "fn other() {}"]))
+    assert first == second
+    assert first != other
+
+def test_embed_chunks_count_mismatch():
+    class DroppingEmbedder(TestingEmbedder):
+        async def embed(self, texts):
+            return (await super().embed(texts))[1:]
+
+    chunks = list(CodeBoundaryTextSplitter(max_chunk_bytes=60, language=Language.PYTHON).split_chunks(SOURCE))
+
+    with pytest.raises(EmbedError):
+        asyncio.run(DroppingEmbedder().embed_chunks(chunks))
+
+def test_langchain_embedder_wraps_errors():
+    class FailingEmbeddings:
+        def embed_documents(self, texts):
+            raise ConnectionError("model unreachable")
+
+    with pytest.raises(EmbedError):
+        asyncio.run(LangchainEmbedder(FailingEmbeddings()).embed(["text"]))
+
+def test_embedder_embeddings_adapter():
+    """Test that an embedder can be used as Langchain embeddings, e.g. by Chroma."""
+    embedder = TestingEmbedder(dimension=4)
+    embeddings = EmbedderEmbeddings(embedder)
+
+    documents = embeddings.embed_documents(["a", "b"])
+    assert len(documents) == 2
+    assert embeddings.embed_query("a") == documents[0]
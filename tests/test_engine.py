@@ -0,0 +1,108 @@
+import asyncio
+from pipelines import ChatError, Engine, InMemoryStore, TestingChatProvider, TestingEmbedder
+from pipelines.chunk import Chunk
+from pipelines.query_expansion import HYPOTHESIS_PROMPT
+
+def make_store(embedder: TestingEmbedder, chunks: list[Chunk]) -> InMemoryStore:
+    store = InMemoryStore()
+    store.add(chunks, asyncio.run(embedder.embed_chunks(chunks)))
+    return store
+
+def test_ask_answers_with_citations(log_chunks):
+    embedder = TestingEmbedder()
+    provider = TestingChatProvider(respond=lambda messages: "Call `log_message` [2] with a level [1, 2].")
+    engine = Engine(embedder, make_store(embedder, log_chunks), provider, k=3, options={"temperature": 0})
+
+    answer = asyncio.run(engine.ask("How do I log a message?"))
+
+    assert answer.text == "Call `log_message` [2] with a level [1, 2]."
+    assert sorted(chunk.path for chunk in answer.used_chunks) == ["README.md", "src/level.rs", "src/log.rs"]
+
+    # Citations refer to the used chunks by their number in the context
+    assert [citation.index for citation in answer.citations] == [2, 1]
+    for citation in answer.citations:
+        assert citation.location == answer.used_chunks[citation.index - 1].location
+
+    # The question is embedded and the used chunks are placed in the prompt
+    (messages, options), = provider.requests
+    assert options == {"temperature": 0}
+    assert messages[-1] == {"role": "user", "content": "How do I log a message?"}
+    for index, chunk in enumerate(answer.used_chunks, start=1):
+        assert f"[{index}] {chunk.location}\n{chunk.text}" in messages[0]["content"]
+
+def test_ask_respects_k_and_budget(log_chunks):
+    embedder = TestingEmbedder()
+    store = make_store(embedder, log_chunks)
+
+    answer = asyncio.run(Engine(embedder, store, TestingChatProvider(), k=2).ask("LogLevel"))
+    assert len(answer.used_chunks) == 2
+    assert answer.citations == []
+
+    # Only the first line of the most relevant chunk fits into a tiny budget
+    answer = asyncio.run(Engine(embedder, store, TestingChatProvider(), k=3, context_budget=12).ask("LogLevel"))
+    assert len(answer.used_chunks) == 1
+    assert "\n" not in answer.used_chunks[0].text
+
+def test_ask_steps_overridable(log_chunks):
+    class ReadmeFirstEngine(Engine):
+        def build_context(self, results):
+            chunks = super().build_context(results)
+            return sorted(chunks, key=lambda chunk: chunk.path != "README.md")
+
+    embedder = TestingEmbedder()
+    provider = TestingChatProvider(respond=lambda messages: "See [1].")
+    engine = ReadmeFirstEngine(embedder, make_store(embedder, log_chunks), provider, k=3, context_budget=0)
+
+    answer = asyncio.run(engine.ask("Where is the log level configured?"))
+
+    assert answer.used_chunks[0].path == "README.md"
+    assert [citation.path for citation in answer.citations] == ["README.md"]
+
+class RecordingEmbedder(TestingEmbedder):
+    def __init__(self):
+        super().__init__()
+        self.texts = []
+
+    async def embed(self, texts):
+        self.texts.extend(texts)
+        return await super().embed(texts)
+
+def test_query_expansion(log_chunks):
+    hypothesis = "pub fn log_message(level: LogLevel, msg: &str) { println!(\"{msg}\"); }"
+    def respond(messages):
+        if messages[0]["content"] == HYPOTHESIS_PROMPT:
+            return f" {hypothesis}\n"
+        return "See [1]."
+
+    embedder = RecordingEmbedder()
+    store = make_store(embedder, log_chunks)
+    provider = TestingChatProvider(respond=respond)
+    embedder.texts.clear()
+
+    answer = asyncio.run(Engine(embedder, store, provider, k=1, query_expansion=True).ask("How do I log?"))
+
+    # The question and hypothesis are embedded, but only the question is answered
+    assert embedder.texts == [f"How do I log?\n\n{hypothesis}"]
+    assert provider.requests[0][0][-1] == {"role": "user", "content": "How do I log?"}
+    assert provider.requests[1][0][-1] == {"role": "user", "content": "How do I log?"}
+    assert answer.used_chunks[0].path == "src/log.rs"
+
+    # Disabled by default
+    embedder.texts.clear()
+    asyncio.run(Engine(embedder, store, provider, k=1).ask("How do I log?"))
+    assert embedder.texts == ["How do I log?"]
+
+def test_query_expansion_failure(log_chunks):
+    def respond(messages):
+        if messages[0]["content"] == HYPOTHESIS_PROMPT:
+            raise ChatError("model not found")
+        return "See [1]."
+
+    embedder = RecordingEmbedder()
+    store = make_store(embedder, log_chunks)
+    embedder.texts.clear()
+
+    answer = asyncio.run(Engine(embedder, store, TestingChatProvider(respond=respond), query_expansion=True).ask("LogLevel"))
+
+    assert embedder.texts == ["LogLevel"]
+    assert answer.text == "See [1]."
@@ -0,0 +1,46 @@
+import asyncio
+import json
+from pipelines import InMemoryStore, TestingEmbedder
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.chunk import dedup_chunks
+from pipelines.repo_walker import path_sort_key
+
+def make_repo(root):
+    (root / "src").mkdir()
+    (root / "src" / "lib.rs").write_text("fn shared() {}\n\nimpl Config {\n    fn load() {}\n}\n", "utf-8")
+    (root / "src" / "a").mkdir()
+    (root / "src" / "a" / "mod.rs").write_text("fn shared() {}\n\nfn a() {}\n", "utf-8")
+    (root / "src" / "a.rs").write_text("fn shared() {}\n", "utf-8")
+    (root / "README.md").write_text("# Readme\n\nfn shared() {}\n", "utf-8")
+
+def index_and_search(root) -> bytes:
+    splitter = CodeBoundaryTextSplitter()
+    embedder = TestingEmbedder()
+    chunks, _ = splitter.par_index_dir(root, max_workers=4)
+    store = InMemoryStore()
+    store.add(chunks, asyncio.run(embedder.embed_chunks(chunks)))
+
+    # Identical texts get identical vectors, so the results tie
+    results = store.search(embedder.embed_text("fn shared() {}"), k=len(chunks))
+    return json.dumps([[chunk.to_dict(), score] for chunk, score in results]).encode("utf-8")
+
+def test_index_and_search_deterministic(tmp_path):
+    make_repo(tmp_path)
+
+    assert index_and_search(tmp_path) == index_and_search(tmp_path)
+
+def test_split_order(tmp_path):
+    make_repo(tmp_path)
+    splitter = CodeBoundaryTextSplitter()
+
+    chunks = list(splitter.index_dir(tmp_path))
+    paths = list(dict.fromkeys(chunk.path for chunk in chunks))
+    assert paths == ["README.md", "src/a/mod.rs", "src/a.rs", "src/lib.rs"]
+    assert paths == sorted(paths, key=path_sort_key)
+    assert splitter.par_index_dir(tmp_path, max_workers=4)[0] == chunks
+
+    # Dedup keeps the first of the identical chunks in that order
+    (shared,) = [chunk for chunk in dedup_chunks(chunks) if chunk.text == "fn shared() {}"]
+    assert shared.path == "src/a/mod.rs"
+    assert shared.also_at == [chunks[i].location for i in range(len(chunks))
+                              if chunks[i].text == "fn shared() {}" and chunks[i] is not shared]
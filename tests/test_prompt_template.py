@@ -0,0 +1,52 @@
+import asyncio
+import pytest
+from pipelines import Engine, InMemoryStore, PromptTemplate, TestingChatProvider, TestingEmbedder
+from pipelines.citations import CITATION_INSTRUCTIONS, cited_messages
+
+def test_template_substitutes_placeholders(log_chunks):
+    template = PromptTemplate(
+        system="You answer questions about {{aqchat}}.\n{citations_instruction}",
+        user="Passages:\n{context}\n\nQuestion: {question}",
+    )
+
+    (system, user), citations = cited_messages("How do I log?", [log_chunks[0], log_chunks[2]], "Be brief.", template)
+
+    assert system == {"role": "system", "content": f"Be brief.\n\nYou answer questions about {{aqchat}}.\n{CITATION_INSTRUCTIONS}"}
+    assert user == {
+        "role": "user",
+        "content": "Passages:\n[1] src/log.rs:10-12\npub fn log_message(level: LogLevel, msg: &str) {\n    println!(\"{msg}\");\n}"
+                   "\n\n[2] README.md:3-3\nLogging is configured with LOG_LEVEL.\n\nQuestion: How do I log?",
+    }
+    assert [citation.location for citation in citations] == ["src/log.rs:10-12", "README.md:3-3"]
+
+def test_default_template(log_chunks):
+    messages, _ = cited_messages("How do I log?", log_chunks[2:])
+
+    assert messages == [
+        {"role": "system", "content": f"{CITATION_INSTRUCTIONS}\n\nContext:\n\n[1] README.md:3-3\nLogging is configured with LOG_LEVEL."},
+        {"role": "user", "content": "How do I log?"},
+    ]
+
+@pytest.mark.parametrize("system, user", [
+    ("Context: {context}", "Answer briefly."),
+    ("{citations_instruction}", "{question}"),
+    ("{context}", "{question} in {language}"),
+    ("{context} {0}", "{question}"),
+    ("{context", "{question}"),
+])
+def test_invalid_templates(system, user):
+    with pytest.raises(ValueError):
+        PromptTemplate(system, user)
+
+def test_engine_uses_template(log_chunks):
+    embedder = TestingEmbedder()
+    store = InMemoryStore()
+    store.add(log_chunks, asyncio.run(embedder.embed_chunks(log_chunks)))
+    provider = TestingChatProvider()
+    template = PromptTemplate(system="Only use these passages:\n{context}", user="Q: {question}")
+
+    asyncio.run(Engine(embedder, store, provider, k=2, template=template).ask("How do I log?"))
+
+    (messages, _), = provider.requests
+    assert messages[0]["content"].startswith("Only use these passages:\n[1] ")
+    assert messages[1] == {"role": "user", "content": "Q: How do I log?"}
@@ -0,0 +1,67 @@
+import io
+import json
+import uuid
+import pytest
+from pipelines import QdrantUploader, VectorStoreError
+from pipelines.chunk import Chunk, ChunkKind
+from pipelines.detectors import Language
+from pipelines.qdrant_export import qdrant_point_id, write_qdrant_batch
+
+CHUNKS = [
+    Chunk(text="fn main() {}", start_byte=0, end_byte=12, start_line=1, end_line=1,
+          path="src/main.rs", language=Language.RUST, kind=ChunkKind.FUNCTION, name="main"),
+    Chunk(text="struct Config;", start_byte=14, end_byte=28, start_line=3, end_line=3,
+          path="src/main.rs", language=Language.RUST, kind=ChunkKind.STRUCT, name="Config"),
+]
+VECTORS = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+
+def test_write_batch():
+    writer = io.StringIO()
+    write_qdrant_batch(CHUNKS, VECTORS, writer)
+    points = json.loads(writer.getvalue())["points"]
+
+    assert [point["id"] for point in points] == [str(uuid.UUID(hex=chunk.id())) for chunk in CHUNKS]
+    assert [point["vector"] for point in points] == VECTORS
+    payload = points[1]["payload"]
+    assert (payload["path"], payload["start_line"], payload["end_line"]) == ("src/main.rs", 3, 3)
+    assert (payload["kind"], payload["name"], payload["chunk_id"]) == ("struct", "Config", CHUNKS[1].id())
+    assert Chunk.from_dict(payload) == CHUNKS[1]
+
+    # Re-exporting gives the same IDs, so the points are overwritten
+    assert qdrant_point_id(Chunk.from_dict(payload)) == points[1]["id"]
+
+    with pytest.raises(VectorStoreError):
+        write_qdrant_batch(CHUNKS, [[1.0, 0.0], [0.0, 1.0, 0.0]], io.StringIO())
+
+class FakeQdrant:
+    def __init__(self, size=None):
+        self.size = size
+        self.requests = []
+
+    def __call__(self, method, url, body, headers):
+        self.requests.append((method, url, json.loads(body) if body else None))
+        if method == "GET":
+            if self.size is None:
+                return 404, b'{"status": {"error": "Not found"}}'
+            return 200, json.dumps({"result": {"config": {"params": {"vectors": {"size": self.size}}}}}).encode()
+        return 200, b'{"result": true}'
+
+def test_upload_creates_collection():
+    qdrant = FakeQdrant()
+    QdrantUploader("http://localhost:6333/", "code", batch_size=1, transport=qdrant).upload(CHUNKS, VECTORS)
+
+    assert [(method, url) for method, url, _ in qdrant.requests] == [
+        ("GET", "http://localhost:6333/collections/code"),
+        ("PUT", "http://localhost:6333/collections/code"),
+        ("PUT", "http://localhost:6333/collections/code/points?wait=true"),
+        ("PUT", "http://localhost:6333/collections/code/points?wait=true"),
+    ]
+    assert qdrant.requests[1][2] == {"vectors": {"size": 3, "distance": "Cosine"}}
+    assert qdrant.requests[3][2]["points"][0]["id"] == qdrant_point_id(CHUNKS[1])
+
+def test_upload_dimension_mismatch():
+    qdrant = FakeQdrant(size=768)
+
+    with pytest.raises(VectorStoreError, match="dimension 768"):
+        QdrantUploader("http://localhost:6333", "code", transport=qdrant).upload(CHUNKS, VECTORS)
+    assert [method for method, _, _ in qdrant.requests] == ["GET"]
@@ -0,0 +1,62 @@
+from pathlib import Path
+from pipelines.boundary_splitter import CodeBoundaryTextSplitter
+from pipelines.repo_walker import walk_repo
+
+def _make_repo(root: Path):
+    """Create a small repository with ignored folders and a binary blob."""
+    files = {
+        ".gitignore": "target/\nnode_modules/\n*.log\n!keep.log\n",
+        ".git/HEAD": "ref: refs/heads/main\n",
+        ".git/info/exclude": "/scratch.rs\n",
+        "src/main.rs": "fn main() {\n    println!(\"hello\");\n}\n",
+        "src/lib.rs": "pub fn add(a: u32, b: u32) -> u32 {\n    a + b\n}\n",
+        "src/generated/.gitignore": "*.rs\n!mod.rs\n",
+        "src/generated/mod.rs": "pub mod bindings;\n",
+        "src/generated/bindings.rs": "pub fn ffi() {}\n",
+        "target/debug/main.rs": "fn ignored() {}\n",
+        "web/node_modules/dep/index.js": "module.exports = {};\n",
+        "web/app.js": "export const app = () => {\n  return 1;\n};\n",
+        "build.log": "compiling...\n",
+        "keep.log": "kept\n",
+        "scratch.rs": "fn scratch() {}\n",
+        "README.md": "# Project\n\nSome docs.\n",
+    }
+    for path, text in files.items():
+        (root / path).parent.mkdir(parents=True, exist_ok=True)
+        (root / path).write_text(text, "utf-8")
+
+    (root / "assets").mkdir()
+    (root / "assets" / "logo.png").write_bytes(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")
+
+def test_walk_repo_ignored(tmp_path):
+    """This tests if ignored folders and files, nested ignore files,
+    `.git/info/exclude`, the `.git` folder and binary files are skipped.
+    """
+    _make_repo(tmp_path)
+    paths = [path.as_posix() for path in walk_repo(tmp_path)]
+
+    assert paths == [
+        ".gitignore",
+        "README.md",
+        "keep.log",
+        "src/generated/.gitignore",
+        "src/generated/mod.rs",
+        "src/lib.rs",
+        "src/main.rs",
+        "web/app.js",
+    ]
+
+def test_walk_repo_include_exclude(tmp_path):
+    """This tests if include and exclude globs further restrict the files."""
+    _make_repo(tmp_path)
+    paths = [path.as_posix() for path in walk_repo(tmp_path, include=["src/**/*.rs"], exclude=["**/mod.rs"])]
+
+    assert paths == ["src/lib.rs", "src/main.rs"]
+
+def test_index_dir(tmp_path):
+    """This tests if index_dir splits only the files which are not ignored."""
+    _make_repo(tmp_path)
+    chunks = list(CodeBoundaryTextSplitter().index_dir(tmp_path, include=["**/*.rs", "**/*.js", "**/*.png"]))
+
+    assert sorted({chunk.path for chunk in chunks}) == ["src/generated/mod.rs", "src/lib.rs", "src/main.rs", "web/app.js"]
+    assert not any("ignored" in chunk.text or "ffi" in chunk.text for chunk in chunks)
@@ -0,0 +1,41 @@
+import asyncio
+from pipelines import InMemoryStore, LexicalReranker
+from pipelines.chunk import Chunk
+from pipelines.lexical_reranker import tokenize_terms
+from pipelines.retrieval import retrieve
+
+def make_chunk(path: str, text: str) -> Chunk:
+    return Chunk(text=text, start_byte=0, end_byte=len(text), start_line=1, end_line=1, path=path)
+
+def make_store():
+    chunks = [
+        make_chunk("a.rs", "fn render_page(page: &Page) -> Html { layout(page) }"),
+        make_chunk("b.rs", "fn parse_config(path: &Path) -> Config { toml::from_str(&read(path)) }"),
+        make_chunk("c.rs", "fn load_settings() -> Settings { Settings::default() }"),
+    ]
+    # The vectors rank the chunks a, c, b for the query
+    store = InMemoryStore()
+    store.add(chunks, [[1.0, 0.0], [0.2, 1.0], [0.8, 0.4]])
+    return store
+
+def test_lexical_reranker_favors_exact_terms():
+    store = make_store()
+    query = "where is parse_config defined?"
+
+    raw = asyncio.run(retrieve(store, [1.0, 0.0], query, k=2))
+    assert [chunk.path for chunk, _ in raw] == ["a.rs", "c.rs"]
+
+    reranked = asyncio.run(retrieve(store, [1.0, 0.0], query, k=2, reranker=LexicalReranker(), candidates=3))
+    assert [chunk.path for chunk, _ in reranked][0] == "b.rs"
+    assert len(reranked) == 2
+    assert reranked[0][1] > reranked[1][1]
+
+def test_reranker_keeps_vector_order_on_ties():
+    chunks = [make_chunk(f"{name}.rs", "fn unrelated() {}") for name in "xyz"]
+    ranking = asyncio.run(LexicalReranker().rerank("parse config", chunks))
+    assert ranking == [(0, 0.0), (1, 0.0), (2, 0.0)]
+
+def test_tokenize_terms():
+    terms = tokenize_terms("parseConfig(HTTPServer, max_size)")
+    assert {"parse", "config", "http", "server", "max", "size"} <= set(terms)
+    assert "max_size" in terms and "parseconfig" in terms
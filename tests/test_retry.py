@@ -0,0 +1,89 @@
+import asyncio
+import pytest
+from pipelines import ChatError, EmbedError, OpenAIChatProvider, RetryingEmbedder, RetryPolicy, TestingEmbedder
+from pipelines.retry import parse_retry_after
+
+class FlakyTransport:
+    """Mock transport which fails with the given statuses before succeeding."""
+
+    def __init__(self, *statuses, retry_after=None):
+        self.statuses = list(statuses)
+        self.retry_after = retry_after
+        self.calls = 0
+
+    def __call__(self, url, body, headers):
+        self.calls += 1
+        if self.statuses:
+            raise ChatError("failed", status=self.statuses.pop(0), retry_after=self.retry_after)
+        return [b'data: {"choices": [{"delta": {"content": "ok"}}]}\n\ndata: [DONE]\n\n']
+
+def make_provider(transport, sleeps):
+    policy = RetryPolicy(max_attempts=4, base_delay=1.0, jitter=0.0, sleep=sleeps.append)
+    return OpenAIChatProvider("http://localhost/v1", "model", transport=transport, retry_policy=policy)
+
+def test_transient_failures_retried():
+    sleeps = []
+    transport = FlakyTransport(503, 429)
+    provider = make_provider(transport, sleeps)
+
+    completion = asyncio.run(provider.complete([{"role": "user", "content": "hi"}]))
+    assert completion.text == "ok"
+    assert transport.calls == 3
+    assert sleeps == [1.0, 2.0]
+
+def test_non_retryable_fails_fast():
+    sleeps = []
+    transport = FlakyTransport(401)
+    provider = make_provider(transport, sleeps)
+
+    with pytest.raises(ChatError) as info:
+        asyncio.run(provider.complete([{"role": "user", "content": "hi"}]))
+    assert info.value.status == 401
+    assert transport.calls == 1
+    assert sleeps == []
+
+def test_attempts_exhausted():
+    sleeps = []
+    transport = FlakyTransport(503, 503, 503, 503, 503)
+    with pytest.raises(ChatError):
+        list(make_provider(transport, sleeps).stream([]))
+    assert transport.calls == 4
+
+def test_retry_after_respected():
+    sleeps = []
+    transport = FlakyTransport(429, retry_after=7.0)
+    list(make_provider(transport, sleeps).stream([]))
+    assert sleeps == [7.0]
+
+def test_backoff_with_jitter():
+    policy = RetryPolicy(base_delay=1.0, max_delay=5.0, jitter=0.5)
+    error = ChatError("failed", status=503)
+    for retry, ceiling in [(1, 1.0), (2, 2.0), (3, 4.0), (4, 5.0), (10, 5.0)]:
+        assert ceiling * 0.5 <= policy.delay(retry, error) <= ceiling
+
+def test_connection_errors_retryable():
+    policy = RetryPolicy()
+    try:
+        raise ChatError("could not connect") from ConnectionRefusedError()
+    except ChatError as ex:
+        assert policy.is_retryable(ex)
+    assert not policy.is_retryable(ValueError("bug"))
+
+def test_retrying_embedder():
+    class FlakyEmbedder(TestingEmbedder):
+        async def embed(self, texts):
+            if self.calls < 2:
+                self.calls += 1
+                raise EmbedError("rate limited", status=429)
+            return await super().embed(texts)
+
+    embedder = FlakyEmbedder(dimension=4)
+    vectors = asyncio.run(RetryingEmbedder(embedder, RetryPolicy(base_delay=0.0)).embed(["a", "b"]))
+    assert len(vectors) == 2
+    assert embedder.calls == 3
+
+def test_parse_retry_after():
+    assert parse_retry_after("120") == 120.0
+    assert parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT") == 0.0
+    assert parse_retry_after(None) is None
+    assert parse_retry_after("soon") is None
@@ -0,0 +1,68 @@
+import asyncio
+from pipelines import Engine, InMemoryStore, SearchQuery, TestingChatProvider, TestingEmbedder
+from pipelines.chunk import Chunk, ChunkKind
+from pipelines.detectors import Language
+
+def make_chunk(path: str, language: Language, kind: ChunkKind = ChunkKind.FUNCTION) -> Chunk:
+    return Chunk(text=path, start_byte=0, end_byte=len(path), start_line=1, end_line=1,
+                 path=path, language=language, kind=kind)
+
+def make_store() -> InMemoryStore:
+    # The Python chunks are the closest to the query vector [1, 0]
+    chunks = [
+        make_chunk("app/main.py", Language.PYTHON),
+        make_chunk("app/util.py", Language.PYTHON),
+        make_chunk("src/api/routes.rs", Language.RUST),
+        make_chunk("src/api/handlers.rs", Language.RUST, ChunkKind.STRUCT),
+        make_chunk("src/apis.rs", Language.RUST),
+        make_chunk("src/api/v2/users.rs", Language.RUST),
+        make_chunk("src/lib.rs", Language.RUST, ChunkKind.IMPORT),
+    ]
+    vectors = [[1.0, 0.0], [0.99, 0.1], [0.9, 0.4], [0.8, 0.6], [0.95, 0.3], [0.6, 0.8], [0.7, 0.7]]
+    store = InMemoryStore()
+    store.add(chunks, vectors)
+    return store
+
+def test_filter_language():
+    store = make_store()
+
+    results = store.search_query(SearchQuery([1.0, 0.0]).filter_language(Language.RUST).k(3))
+    assert [chunk.path for chunk, _ in results] == ["src/apis.rs", "src/api/routes.rs", "src/api/handlers.rs"]
+
+    # Fewer matching chunks than k
+    results = store.search_query(SearchQuery([1.0, 0.0]).filter_language(Language.PYTHON).k(8))
+    assert [chunk.path for chunk, _ in results] == ["app/main.py", "app/util.py"]
+
+def test_filter_path_prefix():
+    store = make_store()
+
+    results = store.search_query(SearchQuery([1.0, 0.0]).filter_path_prefix("src/api").k(8))
+    assert [chunk.path for chunk, _ in results] == ["src/api/routes.rs", "src/api/handlers.rs", "src/api/v2/users.rs"]
+
+    results = store.search_query(SearchQuery([1.0, 0.0]).filter_path_prefix("src/api/").k(2))
+    assert [chunk.path for chunk, _ in results] == ["src/api/routes.rs", "src/api/handlers.rs"]
+
+def test_filters_combine():
+    store = make_store()
+
+    query = SearchQuery([1.0, 0.0], k=8).filter_language(Language.RUST).filter_kind(ChunkKind.FUNCTION)
+    assert [chunk.path for chunk, _ in store.search_query(query)] == ["src/apis.rs", "src/api/routes.rs", "src/api/v2/users.rs"]
+
+    query.filter_path_prefix("src/api").filter(lambda chunk: "v2" not in chunk.path)
+    assert [chunk.path for chunk, _ in store.search_query(query)] == ["src/api/routes.rs"]
+
+    assert len(store.search_query(SearchQuery([1.0, 0.0]).k(4))) == 4
+
+    query = SearchQuery([1.0, 0.0], k=8).filter_language(Language.RUST).min_score(0.85)
+    assert [chunk.path for chunk, _ in store.search_query(query)] == ["src/apis.rs", "src/api/routes.rs"]
+
+def test_engine_ask_where():
+    embedder = TestingEmbedder()
+    chunks = [make_chunk("app/main.py", Language.PYTHON), make_chunk("src/lib.rs", Language.RUST)]
+    store = InMemoryStore()
+    store.add(chunks, asyncio.run(embedder.embed_chunks(chunks)))
+    query = SearchQuery([]).filter_language(Language.RUST)
+
+    answer = asyncio.run(Engine(embedder, store, TestingChatProvider(), k=2).ask("main", where=query.matches))
+
+    assert [chunk.path for chunk in answer.used_chunks] == ["src/lib.rs"]
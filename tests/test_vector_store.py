@@ -0,0 +1,201 @@
+import math
+import pytest
+from pipelines import InMemoryStore, VectorStoreError
+from pipelines.chunk import Chunk
+
+def make_chunk(path: str, start_byte: int = 0) -> Chunk:
+    return Chunk(text=f"{path}@{start_byte}", start_byte=start_byte, end_byte=start_byte + 10,
+                 start_line=1, end_line=1, path=path)
+
+@pytest.fixture
+def store():
+    store = InMemoryStore()
+    store.add(
+        [make_chunk("a.rs"), make_chunk("b.rs"), make_chunk("c.rs"), make_chunk("d.rs")],
+        [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.7, 0.7, 0.0], [0.0, 0.0, 1.0]],
+    )
+    return store
+
+def test_search_top_k_order(store):
+    results = store.search([1.0, 0.2, 0.0], k=3)
+
+    assert [chunk.path for chunk, _ in results] == ["a.rs", "c.rs", "b.rs"]
+    scores = [score for _, score in results]
+    assert scores == sorted(scores, reverse=True)
+    assert scores[0] == pytest.approx(1.0 / (1.04 ** 0.5))
+
+def test_search_k_larger_than_store(store):
+    results = store.search([0.0, 0.0, 1.0], k=10)
+
+    assert len(results) == len(store) == 4
+    assert results[0][0].path == "d.rs"
+
+def test_search_ties_break_by_chunk_id():
+    chunks = [make_chunk("z.rs"), make_chunk("a.rs", 50), make_chunk("a.rs", 5)]
+    vectors = [[2.0, 0.0], [1.0, 0.0], [3.0, 0.0]]
+    expected = sorted(chunks, key=lambda chunk: chunk.id())
+
+    for order in [[0, 1, 2], [2, 1, 0], [1, 2, 0]]:
+        store = InMemoryStore()
+        store.add([chunks[i] for i in order], [vectors[i] for i in order])
+        assert [chunk for chunk, _ in store.search([1.0, 0.0], k=3)] == expected
+
+def test_search_min_score():
+    # Vectors at 0, 30, 60, 90 and 180 degrees from the query
+    store = InMemoryStore()
+    angles = [0, 30, 60, 90, 180]
+    store.add(
+        [make_chunk(f"{angle}.rs") for angle in angles],
+        [[math.cos(math.radians(angle)), math.sin(math.radians(angle))] for angle in angles],
+    )
+
+    results = store.search([1.0, 0.0], k=5)
+    assert [chunk.path for chunk, _ in results] == ["0.rs", "30.rs", "60.rs", "90.rs", "180.rs"]
+    assert [score for _, score in results] == pytest.approx([1.0, math.sqrt(3) / 2, 0.5, 0.0, -1.0])
+
+    # Fewer than k chunks reach the threshold
+    results = store.search([1.0, 0.0], k=5, min_score=0.5)
+    assert [chunk.path for chunk, _ in results] == ["0.rs", "30.rs", "60.rs"]
+    assert [chunk.path for chunk, _ in store.search([1.0, 0.0], k=2, min_score=0.5)] == ["0.rs", "30.rs"]
+
+    # No chunk reaches the threshold
+    assert store.search([0.0, -1.0], k=5, min_score=0.6) == []
+
+def test_dimension_mismatch_is_error(store):
+    with pytest.raises(VectorStoreError):
+        store.add([make_chunk("e.rs")], [[1.0, 0.0]])
+    with pytest.raises(VectorStoreError):
+        store.search([1.0, 0.0], k=1)
+
+    # A rejected insert must not leave a partial batch behind
+    with pytest.raises(VectorStoreError):
+        store.add([make_chunk("e.rs"), make_chunk("f.rs")], [[1.0, 0.0, 0.0], [1.0]])
+    assert len(store) == 4
+
+def test_fixed_dimension():
+    store = InMemoryStore(dimension=2)
+    with pytest.raises(VectorStoreError):
+        store.add([make_chunk("a.rs")], [[1.0, 0.0, 0.0]])
+
+def test_search_empty_store():
+    assert InMemoryStore().search([1.0], k=5) == []
+
+def test_save_load_round_trip(store, tmp_path):
+    path = tmp_path / "index.bin"
+    chunk = Chunk(text="    fn area(&self) -> f64 {\n        0.0\n    }", start_byte=120, end_byte=163,
+                  start_line=9, end_line=11, signature="impl Shape for Circle {", overlap_prefix_lines=1,
+                  parent_context="impl Shape for Circle {", heading_path=["Shapes"], path="src/shape.rs",
+                  also_at=["src/copy.rs:3-5"])
+    store.add([chunk], [[0.1, 0.2, 0.3]])
+    store.save(path)
+
+    loaded = InMemoryStore.load(path)
+    assert len(loaded) == len(store)
+    assert loaded.dimension == 3
+
+    query = [0.3, 0.5, 0.2]
+    assert loaded.search(query, k=10) == store.search(query, k=10)
+    restored = next(c for c, _ in loaded.search(query, k=10) if c.path == "src/shape.rs")
+    assert restored == chunk
+
+def test_load_incompatible_version(store, tmp_path):
+    path = tmp_path / "index.bin"
+    store.save(path)
+
+    data = bytearray(path.read_bytes())
+    data[4] = 99
+    path.write_bytes(bytes(data))
+
+    with pytest.raises(VectorStoreError, match="version"):
+        InMemoryStore.load(path)
+
+def test_load_invalid_file(store, tmp_path):
+    path = tmp_path / "index.bin"
+    path.write_bytes(b"not an index")
+    with pytest.raises(VectorStoreError):
+        InMemoryStore.load(path)
+
+    store.save(path)
+    path.write_bytes(path.read_bytes()[:-4])
+    with pytest.raises(VectorStoreError, match="truncated"):
+        InMemoryStore.load(path)
+
+def test_dimension_recorded_on_first_insert():
+    store = InMemoryStore()
+    store.add([make_chunk("a.rs"), make_chunk("b.rs")], [[1.0, 0.0], [0.0, 1.0]])
+    assert store.dimension == 2
+
+    results = store.search([0.9, 0.1], k=2)
+    assert [chunk.path for chunk, _ in results] == ["a.rs", "b.rs"]
+
+    with pytest.raises(VectorStoreError, match="query of dimension 2, got 3"):
+        store.search([1.0, 0.0, 0.0], k=2)
+    with pytest.raises(VectorStoreError, match="vector of dimension 2, got 3"):
+        store.add([make_chunk("c.rs")], [[1.0, 0.0, 0.0]])
+
+    # Queries are checked even if there is nothing to find
+    with pytest.raises(VectorStoreError):
+        InMemoryStore(dimension=2).search([1.0], k=5)
+
+def test_clear_and_migrate(store):
+    store.migrate([make_chunk("e.rs"), make_chunk("f.rs")], [[1.0, 0.0], [0.0, 1.0]])
+    assert len(store) == 2 and store.dimension == 2
+    assert store.search([0.0, 1.0], k=1)[0][0].path == "f.rs"
+
+    # An invalid migration leaves the store as it was
+    with pytest.raises(VectorStoreError):
+        store.migrate([make_chunk("g.rs"), make_chunk("h.rs")], [[1.0], [1.0, 0.0]])
+    assert len(store) == 2 and store.dimension == 2
+
+    store.clear()
+    assert len(store) == 0 and store.dimension is None
+    store.add([make_chunk("g.rs")], [[1.0, 0.0, 0.0, 0.0]])
+    assert store.dimension == 4
+
+    store.clear(dimension=3)
+    with pytest.raises(VectorStoreError):
+        store.add([make_chunk("g.rs")], [[1.0, 0.0]])
+
+def test_resize():
+    with pytest.raises(ValueError):
+        InMemoryStore(resize=True)
+
+    store = InMemoryStore(dimension=3, resize=True)
+    store.add([make_chunk("a.rs"), make_chunk("b.rs")], [[1.0, 0.0], [0.0, 0.0, 1.0, 5.0]])
+
+    results = store.search([1.0, 0.0, 0.0, 0.0], k=2)
+    assert [chunk.path for chunk, _ in results] == ["a.rs", "b.rs"]
+    assert [score for _, score in results] == pytest.approx([1.0, 0.0])
+
+    store.migrate([make_chunk("c.rs")], [[0.0, 1.0, 0.0, 0.0, 0.0]])
+    assert store.dimension == 3 and store.search([0.0, 1.0], k=1)[0][0].path == "c.rs"
+
+def test_save_load_keeps_resize(tmp_path):
+    path = tmp_path / "index.bin"
+    store = InMemoryStore(dimension=3, resize=True)
+    store.add([make_chunk("a.rs"), make_chunk("b.rs")], [[1.0, 0.0], [0.0, 1.0, 0.0, 2.0]])
+    store.save(path)
+
+    loaded = InMemoryStore.load(path)
+    assert loaded.resize and loaded.dimension == 3
+    assert loaded.search([0.0, 1.0], k=2) == store.search([0.0, 1.0], k=2)
+    loaded.add([make_chunk("c.rs")], [[0.0, 0.0, 1.0, 0.0]])
+
+    # Stores which don't resize stay strict
+    store = InMemoryStore()
+    store.add([make_chunk("a.rs")], [[1.0, 0.0]])
+    store.save(path)
+    with pytest.raises(VectorStoreError):
+        InMemoryStore.load(path).search([1.0], k=1)
+
+def test_load_version_1(store, tmp_path):
+    path = tmp_path / "index.bin"
+    store.save(path)
+
+    # Version 1 files have no flags after the header
+    data = bytearray(path.read_bytes())
+    data[4] = 1
+    path.write_bytes(bytes(data[:16] + data[20:]))
+
+    loaded = InMemoryStore.load(path)
+    assert not loaded.resize and len(loaded) == len(store)